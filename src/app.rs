@@ -1,23 +1,38 @@
 use crate::{
     ComponentInputResult,
-    commander::Commander,
+    commander::{
+        CommandError, Commander, files::WorkingCopyStatus, ids::CommitId,
+        is_concurrent_operation_error, is_read_only_command,
+    },
     env::Env,
+    keybinds::Shortcut,
     ui::{
-        Component, ComponentAction, bookmarks_tab::BookmarksTab, command_log_tab::CommandLogTab,
-        command_popup::CommandPopup, files_tab::FilesTab, log_tab::LogTab,
+        Component, ComponentAction, SuspendedTask, bookmarks_tab::BookmarksTab,
+        command_log_tab::CommandLogTab, command_popup::CommandPopup, config_popup::ConfigPopup,
+        describe_popup::DescribePopup, files_tab::FilesTab, log_tab::LogTab,
+        message_popup::MessagePopup, op_log_tab::OpLogTab, operations_popup::OperationsPopup,
+        remotes_popup::RemotesPopup, utils::spawn_background, workspaces_popup::WorkspacesPopup,
     },
 };
-use anyhow::{Result, anyhow};
+use ansi_to_tui::IntoText;
+use anyhow::{Result, anyhow, bail};
+use chrono::{DateTime, Local};
 use core::fmt;
 use ratatui::crossterm::event::{self, Event, KeyCode, KeyModifiers};
-use tracing::{info, info_span};
+use std::{
+    path::PathBuf,
+    sync::mpsc,
+    time::{Duration, Instant},
+};
+use tracing::{info, info_span, trace};
 
-#[derive(PartialEq, Copy, Clone)]
+#[derive(PartialEq, Copy, Clone, Debug)]
 pub enum Tab {
     Log,
     Files,
     Bookmarks,
     CommandLog,
+    OpLog,
 }
 
 impl fmt::Display for Tab {
@@ -27,37 +42,460 @@ impl fmt::Display for Tab {
             Tab::Files => write!(f, "Files"),
             Tab::Bookmarks => write!(f, "Bookmarks"),
             Tab::CommandLog => write!(f, "Command Log"),
+            Tab::OpLog => write!(f, "Op Log"),
         }
     }
 }
 
+/// Time in which the quit key must be pressed again when `lazyjj.quit-double-press` is set
+const QUIT_DOUBLE_PRESS_WINDOW: Duration = Duration::from_secs(1);
+
+/// Delay before retrying a refresh skipped because of jj commands running at the same time
+const REFRESH_RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Auto-refresh interval used when resuming auto-refresh disabled with `lazyjj.auto-refresh-ms`
+const DEFAULT_AUTO_REFRESH_INTERVAL: Duration = Duration::from_secs(5);
+
+/// State of refreshing the current tab periodically
+#[derive(PartialEq, Eq, Copy, Clone, Debug)]
+pub enum AutoRefresh {
+    /// Disabled by `lazyjj.auto-refresh-ms = 0`, until resumed
+    Disabled,
+    Paused,
+    Running,
+}
+
+/// Remotes fetched from by a `jj git fetch` run in the background
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum FetchTarget {
+    /// The remotes from the `git.fetch` jj setting
+    Default,
+    AllRemotes,
+    Remote(String),
+}
+
+/// Bookmarks pushed by a `jj git push` run in the background
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum PushTarget {
+    /// The bookmarks of a change, or all bookmarks
+    Change {
+        commit_id: CommitId,
+        all_bookmarks: bool,
+        allow_new: bool,
+    },
+    Bookmark {
+        name: String,
+        allow_new: bool,
+    },
+}
+
+/// jj command talking to a git remote, run in the background as it can take long
+#[derive(PartialEq, Eq, Clone, Debug)]
+pub enum RemoteCommand {
+    Fetch(FetchTarget),
+    Push(PushTarget),
+}
+
+impl RemoteCommand {
+    fn run(&self, commander: &Commander) -> Result<String, CommandError> {
+        match self {
+            RemoteCommand::Fetch(FetchTarget::Default) => commander.git_fetch(false),
+            RemoteCommand::Fetch(FetchTarget::AllRemotes) => commander.git_fetch(true),
+            RemoteCommand::Fetch(FetchTarget::Remote(remote)) => commander.git_fetch_remote(remote),
+            RemoteCommand::Push(PushTarget::Change {
+                commit_id,
+                all_bookmarks,
+                allow_new,
+            }) => commander.git_push(*all_bookmarks, *allow_new, commit_id),
+            RemoteCommand::Push(PushTarget::Bookmark { name, allow_new }) => {
+                commander.git_push_bookmark(name, *allow_new)
+            }
+        }
+    }
+
+    /// Name of the command, e.g. `fetch`
+    pub fn name(&self) -> &'static str {
+        match self {
+            RemoteCommand::Fetch(_) => "fetch",
+            RemoteCommand::Push(_) => "push",
+        }
+    }
+
+    /// Shown in the header while the command runs
+    pub fn progress_label(&self) -> &'static str {
+        match self {
+            RemoteCommand::Fetch(_) => "fetching",
+            RemoteCommand::Push(_) => "pushing",
+        }
+    }
+
+    /// Popup with the output of the command
+    fn result_popup(
+        &self,
+        result: Result<String, CommandError>,
+    ) -> Result<Option<MessagePopup<'static>>> {
+        let title = match self {
+            RemoteCommand::Fetch(_) => "Fetch",
+            RemoteCommand::Push(_) => "Push",
+        };
+        let (kind, messages) = match result {
+            Ok(result) if result.is_empty() => return Ok(None),
+            Ok(result) => ("message", result.into_text()?),
+            Err(err) => {
+                let error = err.to_string();
+                let rejected = matches!(self, RemoteCommand::Push(_))
+                    && (error.contains("fast-forward") || error.contains("rejected"));
+                (
+                    "error",
+                    err.into_text(if rejected {
+                        "The remote bookmark moved, fetch and try again"
+                    } else {
+                        ""
+                    })?,
+                )
+            }
+        };
+        Ok(Some(MessagePopup {
+            title: format!("{title} {kind}").into(),
+            messages,
+            text_align: None,
+        }))
+    }
+}
+
 impl Tab {
-    pub const VALUES: [Self; 4] = [Tab::Log, Tab::Files, Tab::Bookmarks, Tab::CommandLog];
+    pub const VALUES: [Self; 5] = [
+        Tab::Log,
+        Tab::Files,
+        Tab::Bookmarks,
+        Tab::CommandLog,
+        Tab::OpLog,
+    ];
+
+    /// Name used for the tab in `lazyjj.tab-order`
+    fn name(&self) -> &'static str {
+        match self {
+            Tab::Log => "log",
+            Tab::Files => "files",
+            Tab::Bookmarks => "bookmarks",
+            Tab::CommandLog => "command-log",
+            Tab::OpLog => "op-log",
+        }
+    }
+
+    /// Order the tabs by their names in `order`, adding omitted tabs at the end
+    pub fn sequence(order: &[String]) -> Result<Vec<Tab>> {
+        let mut sequence = vec![];
+        for name in order {
+            let Some(tab) = Tab::VALUES.into_iter().find(|tab| tab.name() == name) else {
+                bail!(
+                    "Unknown tab {name:?} in lazyjj.tab-order, expected one of: {}",
+                    Tab::VALUES.map(|tab| tab.name()).join(", ")
+                );
+            };
+            if !sequence.contains(&tab) {
+                sequence.push(tab);
+            }
+        }
+        for tab in Tab::VALUES {
+            if !sequence.contains(&tab) {
+                sequence.push(tab);
+            }
+        }
+        Ok(sequence)
+    }
 }
 
 pub struct App<'a> {
     pub env: Env,
+    /// Tabs in the order they are shown and numbered
+    pub tab_sequence: Vec<Tab>,
     pub current_tab: Tab,
     pub log: Option<LogTab<'a>>,
     pub files: Option<FilesTab>,
     pub bookmarks: Option<BookmarksTab<'a>>,
     pub command_log: Option<CommandLogTab>,
+    pub op_log: Option<OpLogTab>,
     pub popup: Option<Box<dyn Component>>,
+    /// Task to run with the UI suspended, picked up by the main loop
+    pub suspended: Option<SuspendedTask>,
+    /// Commits @ is ahead of and behind trunk(), shown in the header
+    pub trunk_ahead_behind: Option<(usize, usize)>,
+    /// Operation the repo is viewed at, shown in the header
+    pub at_operation: Option<String>,
+    /// Time of the last `jj git fetch`, shown in the header
+    pub last_fetch_time: Option<DateTime<Local>>,
+    /// Change, description and changed files of @, shown in the header
+    pub working_copy_status: Option<WorkingCopyStatus>,
+    /// Name of the workspace lazyjj operates on, shown in the header when the repo has several
+    pub workspace_name: Option<String>,
+    /// Length of the command history when the header was last refreshed
+    header_history_len: usize,
+    /// Last time the quit key was pressed, when quitting requires a double press
+    pub last_quit_press: Option<Instant>,
+    /// Time to retry a refresh skipped because of jj commands running at the same time
+    refresh_retry_at: Option<Instant>,
+    pub auto_refresh: AutoRefresh,
+    pub auto_refresh_interval: Duration,
+    /// Last time the current tab was refreshed by [App::refresh]
+    last_refresh: Instant,
+    /// Receives the result of the running fetch or push
+    remote_command_rx: Option<mpsc::Receiver<Result<String, CommandError>>>,
+    /// Fetch or push running in the background, and the time it started to animate the spinner
+    /// shown in the header
+    pub remote_command: Option<(RemoteCommand, Instant)>,
 }
 
 impl<'a> App<'a> {
     pub fn new(env: Env) -> Result<App<'a>> {
+        let tab_sequence = Tab::sequence(env.config.tab_order())?;
+        let (auto_refresh, auto_refresh_interval) = match env.config.auto_refresh_ms() {
+            0 => (AutoRefresh::Disabled, DEFAULT_AUTO_REFRESH_INTERVAL),
+            ms => (AutoRefresh::Running, Duration::from_millis(ms)),
+        };
         Ok(App {
             env,
-            current_tab: Tab::Log,
+            current_tab: tab_sequence[0],
+            tab_sequence,
             log: None,
             files: None,
             bookmarks: None,
             command_log: None,
+            op_log: None,
             popup: None,
+            suspended: None,
+            trunk_ahead_behind: None,
+            at_operation: None,
+            last_fetch_time: None,
+            working_copy_status: None,
+            workspace_name: None,
+            header_history_len: 0,
+            last_quit_press: None,
+            refresh_retry_at: None,
+            auto_refresh,
+            auto_refresh_interval,
+            last_refresh: Instant::now(),
+            remote_command_rx: None,
+            remote_command: None,
         })
     }
 
+    /// Whether the quit key was pressed once and pressing it again quits
+    pub fn quit_pending(&self) -> bool {
+        self.last_quit_press
+            .is_some_and(|last_quit_press| last_quit_press.elapsed() < QUIT_DOUBLE_PRESS_WINDOW)
+    }
+
+    /// Recompute the repository state shown in the header
+    pub fn refresh_header(&mut self, commander: &mut Commander) {
+        self.trunk_ahead_behind = commander.get_trunk_ahead_behind().ok().flatten();
+        self.at_operation = commander.at_operation.clone();
+        self.last_fetch_time = commander.get_last_fetch_time().ok().flatten();
+        self.working_copy_status = commander.get_working_copy_status().ok();
+        self.header_history_len = commander.command_history.lock().unwrap().len();
+    }
+
+    /// Find the workspace shown in the header. Workspaces rarely change, so unlike the rest of
+    /// the header this is only done on startup and when switching workspace
+    pub fn refresh_workspace(&mut self, commander: &mut Commander) {
+        self.workspace_name = commander
+            .get_workspaces()
+            .ok()
+            .filter(|workspaces| workspaces.len() > 1)
+            .and_then(|workspaces| {
+                workspaces
+                    .into_iter()
+                    .find(|workspace| workspace.current)
+                    .map(|workspace| workspace.name)
+            });
+    }
+
+    /// Operate on the workspace at `path`, restarting the commander and the tabs against its
+    /// root. The current tab is kept. The current workspace stays when the other one can't be
+    /// opened, e.g. when its directory was deleted, or while a fetch or push runs, as its result
+    /// would be lost
+    pub fn switch_workspace(&mut self, commander: &mut Commander, path: String) -> Result<()> {
+        if let Some((running, _)) = self.remote_command.as_ref() {
+            self.popup = Some(Box::new(MessagePopup {
+                title: "Workspaces".into(),
+                messages: format!(
+                    "A {} is running, wait for it to complete before switching workspaces",
+                    running.name()
+                )
+                .into(),
+                text_align: None,
+            }));
+            return Ok(());
+        }
+
+        info!("Switching to workspace {}", path);
+        let env = match Env::new(
+            PathBuf::from(path),
+            self.env.default_revset.clone(),
+            self.env.jj_bin.clone(),
+        ) {
+            Ok(env) => env,
+            Err(err) => {
+                self.popup = Some(Box::new(MessagePopup {
+                    title: "Workspaces".into(),
+                    messages: format!("{err:#}").into_text()?,
+                    text_align: None,
+                }));
+                return Ok(());
+            }
+        };
+        *commander = Commander::new(&env);
+        let current_tab = self.current_tab;
+        *self = App::new(env)?;
+        // The header of the new workspace is shown even if its tab fails to load
+        self.refresh_header(commander);
+        self.refresh_workspace(commander);
+        self.set_tab(commander, current_tab)
+    }
+
+    /// Refresh the current tab and the header, to catch up with changes made outside of lazyjj.
+    /// jj commands running at the same time, e.g. in another terminal, can make jj fail with
+    /// errors like "Predecessors cycle detected". The refresh is then skipped and retried by
+    /// [App::tick], and the last good state stays shown meanwhile
+    pub fn refresh(&mut self, commander: &mut Commander) -> Result<()> {
+        self.last_refresh = Instant::now();
+        match self.get_or_init_current_tab(commander)?.focus(commander) {
+            Ok(()) => {
+                self.refresh_retry_at = None;
+                self.refresh_header(commander);
+                Ok(())
+            }
+            Err(err) if is_concurrent_operation_error(&err) => {
+                trace!("Skipping refresh during concurrent jj commands: {err:#}");
+                self.refresh_retry_at = Some(Instant::now() + REFRESH_RETRY_DELAY);
+                Ok(())
+            }
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Run pending work while waiting for input. Returns whether the UI must be drawn again
+    pub fn tick(&mut self, commander: &mut Commander) -> Result<bool> {
+        // Wait for open popups to close, so the fetch or push result doesn't replace them
+        if self.popup.is_none()
+            && let Some(remote_command_rx) = self.remote_command_rx.as_ref()
+            && let Ok(result) = remote_command_rx.try_recv()
+        {
+            self.remote_command_rx = None;
+            if let Some((remote_command, _)) = self.remote_command.take() {
+                // Remote bookmarks changed, and fetched changes are new in the log
+                self.refresh(commander)?;
+                if let Some(popup) = remote_command.result_popup(result)? {
+                    self.popup = Some(Box::new(popup));
+                }
+            }
+            return Ok(true);
+        }
+        if self
+            .refresh_retry_at
+            .is_some_and(|refresh_retry_at| Instant::now() >= refresh_retry_at)
+        {
+            self.refresh(commander)?;
+            return Ok(self.refresh_retry_at.is_none());
+        }
+        // Don't refresh under an open popup, which may be showing the state before the refresh
+        if self.auto_refresh == AutoRefresh::Running
+            && self.popup.is_none()
+            && self.last_refresh.elapsed() >= self.auto_refresh_interval
+        {
+            self.refresh(commander)?;
+            return Ok(true);
+        }
+        // Animate the spinner of a running fetch or push
+        Ok(self.remote_command.is_some())
+    }
+
+    /// Run a fetch or push in the background, keeping the UI usable meanwhile. Only one of them
+    /// runs at a time, so a push can't be started twice
+    pub fn start_remote_command(&mut self, commander: &mut Commander, command: RemoteCommand) {
+        if let Some((running, _)) = self.remote_command.as_ref() {
+            self.popup = Some(Box::new(MessagePopup {
+                title: "Git".into(),
+                messages: format!(
+                    "A {} is already running, wait for it to complete",
+                    running.name()
+                )
+                .into(),
+                text_align: None,
+            }));
+            return;
+        }
+
+        let commander = commander.clone();
+        let background_command = command.clone();
+        self.remote_command_rx = Some(spawn_background(move || background_command.run(&commander)));
+        self.remote_command = Some((command, Instant::now()));
+    }
+
+    /// Pause auto-refresh, or resume it
+    pub fn toggle_auto_refresh(&mut self) {
+        self.auto_refresh = match self.auto_refresh {
+            AutoRefresh::Running => AutoRefresh::Paused,
+            AutoRefresh::Disabled | AutoRefresh::Paused => {
+                self.last_refresh = Instant::now();
+                AutoRefresh::Running
+            }
+        };
+    }
+
+    /// Refresh the header if a command changed the repo since it was last refreshed
+    pub fn sync_header(&mut self, commander: &mut Commander) {
+        let changed = commander
+            .command_history
+            .lock()
+            .unwrap()
+            .iter()
+            .skip(self.header_history_len)
+            .any(|command| !is_read_only_command(&command.args));
+        if changed {
+            self.refresh_header(commander);
+        }
+    }
+
+    /// Undo or redo the last operation, then catch up with the new repo state.
+    /// Errors, like having nothing left to undo, are shown in a popup
+    fn undo_redo(
+        &mut self,
+        commander: &mut Commander,
+        title: &'static str,
+        run: fn(&Commander) -> Result<()>,
+    ) -> Result<()> {
+        match run(commander) {
+            Ok(()) => self.handle_action(ComponentAction::RefreshTab(), commander),
+            Err(err) => {
+                self.popup = Some(Box::new(MessagePopup {
+                    title: title.into(),
+                    messages: format!("{err:#}").into_text()?,
+                    text_align: None,
+                }));
+                Ok(())
+            }
+        }
+    }
+
+    /// Show an error from a command blocked while viewing an older operation,
+    /// instead of exiting. Other errors are returned as is.
+    pub fn show_read_only_error(&mut self, err: anyhow::Error) -> Result<()> {
+        if !err
+            .chain()
+            .any(|err| matches!(err.downcast_ref(), Some(CommandError::ReadOnly(..))))
+        {
+            return Err(err);
+        }
+
+        self.popup = Some(Box::new(MessagePopup {
+            title: "Read-only".into(),
+            messages: err.root_cause().to_string().into_text()?,
+            text_align: None,
+        }));
+        Ok(())
+    }
+
     pub fn get_or_init_current_tab(
         &mut self,
         commander: &mut Commander,
@@ -73,13 +511,14 @@ impl<'a> App<'a> {
         commander: &mut Commander,
         offset: i64,
     ) -> Result<()> {
-        let current_index = Tab::VALUES
+        let current_index = self
+            .tab_sequence
             .iter()
             .position(|&t| t == self.current_tab)
             .unwrap();
-        let new_index =
-            (current_index as i64 + Tab::VALUES.len() as i64 + offset) as usize % Tab::VALUES.len();
-        let new_tab: Tab = Tab::VALUES[new_index];
+        let new_index = (current_index as i64 + self.tab_sequence.len() as i64 + offset) as usize
+            % self.tab_sequence.len();
+        let new_tab: Tab = self.tab_sequence[new_index];
         self.set_tab(commander, new_tab)
     }
 
@@ -88,6 +527,7 @@ impl<'a> App<'a> {
         self.current_tab = tab;
 
         self.get_or_init_current_tab(commander)?.focus(commander)?;
+        self.refresh_header(commander);
         Ok(())
     }
 
@@ -145,6 +585,18 @@ impl<'a> App<'a> {
             .ok_or_else(|| anyhow!("Failed to get mutable reference to CommandLogTab"))
     }
 
+    pub fn get_op_log_tab(&mut self, commander: &mut Commander) -> Result<&mut OpLogTab> {
+        if self.op_log.is_none() {
+            let span = info_span!("Initializing op log tab");
+            let op_log_tab = span.in_scope(|| OpLogTab::new(commander))?;
+            self.op_log = Some(op_log_tab);
+        }
+
+        self.op_log
+            .as_mut()
+            .ok_or_else(|| anyhow!("Failed to get mutable reference to OpLogTab"))
+    }
+
     pub fn get_or_init_tab(
         &mut self,
         commander: &mut Commander,
@@ -155,6 +607,7 @@ impl<'a> App<'a> {
             Tab::Files => self.get_files_tab(commander)?,
             Tab::Bookmarks => self.get_bookmarks_tab(commander)?,
             Tab::CommandLog => self.get_command_log_tab(commander)?,
+            Tab::OpLog => self.get_op_log_tab(commander)?,
         })
     }
 
@@ -176,6 +629,10 @@ impl<'a> App<'a> {
                 .command_log
                 .as_mut()
                 .map(|command_log_tab| command_log_tab as &mut dyn Component),
+            Tab::OpLog => self
+                .op_log
+                .as_mut()
+                .map(|op_log_tab| op_log_tab as &mut dyn Component),
         }
     }
 
@@ -193,8 +650,13 @@ impl<'a> App<'a> {
                 self.get_log_tab(commander)?.set_head(commander, head);
                 self.set_tab(commander, Tab::Log)?;
             }
+            ComponentAction::JumpToChange(head) => {
+                self.get_log_tab(commander)?.jump_to_head(commander, head);
+                self.set_tab(commander, Tab::Log)?;
+            }
             ComponentAction::ChangeHead(head) => {
                 self.get_files_tab(commander)?.set_head(commander, &head)?;
+                self.refresh_header(commander);
             }
             ComponentAction::SetPopup(popup) => {
                 self.popup = popup;
@@ -204,7 +666,25 @@ impl<'a> App<'a> {
                     self.handle_action(component_action, commander)?;
                 }
             }
+            ComponentAction::Fetch(target) => {
+                self.start_remote_command(commander, RemoteCommand::Fetch(target))
+            }
+            ComponentAction::Push(target) => {
+                self.start_remote_command(commander, RemoteCommand::Push(target))
+            }
+            ComponentAction::SwitchWorkspace(path) => {
+                self.switch_workspace(commander, path)?;
+            }
+            ComponentAction::ClearMarks() => {
+                if let Some(log) = self.log.as_mut() {
+                    log.clear_marks();
+                }
+            }
+            ComponentAction::Suspend(task) => {
+                self.suspended = Some(task);
+            }
             ComponentAction::RefreshTab() => {
+                self.refresh_header(commander);
                 self.set_tab(commander, self.current_tab)?;
                 match self.current_tab {
                     Tab::Log => {
@@ -249,7 +729,11 @@ impl<'a> App<'a> {
                 }
             };
         } else if event == event::Event::FocusGained {
-            self.get_or_init_current_tab(commander)?.focus(commander)?;
+            // Catch up with changes made outside of lazyjj
+            if !self.env.config.refresh_on_focus() {
+                return Ok(false);
+            }
+            self.refresh(commander)?;
         } else {
             match self
                 .get_or_init_current_tab(commander)?
@@ -264,12 +748,20 @@ impl<'a> App<'a> {
                         && key.kind == event::KeyEventKind::Press
                     {
                         // Close
-                        if key.code == KeyCode::Char('q')
-                            || (key.modifiers.contains(KeyModifiers::CONTROL)
-                                && (key.code == KeyCode::Char('c')))
-                            || key.code == KeyCode::Esc
+                        if key.modifiers.contains(KeyModifiers::CONTROL)
+                            && (key.code == KeyCode::Char('c'))
                         {
                             return Ok(true);
+                        } else if self
+                            .env
+                            .config
+                            .quit_shortcuts()
+                            .contains(&Shortcut::from_event(key))
+                        {
+                            if !self.env.config.quit_double_press() || self.quit_pending() {
+                                return Ok(true);
+                            }
+                            self.last_quit_press = Some(Instant::now());
                         }
                         //
                         // Tab switching
@@ -277,8 +769,12 @@ impl<'a> App<'a> {
                             self.set_next_tab_with_offset(commander, 1)?;
                         } else if key.code == KeyCode::Char('h') {
                             self.set_next_tab_with_offset(commander, -1)?;
-                        } else if let Some((_, tab)) =
-                            Tab::VALUES.iter().enumerate().find(|(i, _)| {
+                        } else if let Some((_, tab)) = self
+                            .tab_sequence
+                            .iter()
+                            .copied()
+                            .enumerate()
+                            .find(|(i, _)| {
                                 key.code
                                     == KeyCode::Char(
                                         char::from_digit((*i as u32) + 1u32, 10)
@@ -286,12 +782,57 @@ impl<'a> App<'a> {
                                     )
                             })
                         {
-                            self.set_tab(commander, *tab)?;
+                            self.set_tab(commander, tab)?;
+                        }
+                        // Pause or resume auto-refresh
+                        else if key.code == KeyCode::Char('a')
+                            && key.modifiers.contains(KeyModifiers::CONTROL)
+                        {
+                            self.toggle_auto_refresh();
                         }
                         // General jj command runner
                         else if key.code == KeyCode::Char(':') {
                             self.popup = Some(Box::new(CommandPopup::new()));
                         }
+                        // Edit the description of @ shown in the header
+                        else if key.code == KeyCode::Char('i') {
+                            let head = commander.get_current_head()?;
+                            self.popup = Some(Box::new(DescribePopup::new(commander, head)?));
+                        }
+                        // Undo/redo the last operation, then catch up with the new repo state
+                        else if key.code == KeyCode::Char('u') {
+                            self.undo_redo(commander, "Undo", Commander::run_undo)?;
+                        } else if key.code == KeyCode::Char('U') {
+                            self.undo_redo(commander, "Redo", Commander::run_redo)?;
+                        }
+                        // View the repo at an older operation
+                        else if key.code == KeyCode::Char('O') {
+                            self.popup = Some(Box::new(OperationsPopup::new(
+                                self.env.config.clone(),
+                                commander,
+                            )));
+                        }
+                        // Manage git remotes and fetch from one of them
+                        else if key.code == KeyCode::Char('G') {
+                            self.popup = Some(Box::new(RemotesPopup::new(
+                                self.env.config.clone(),
+                                commander,
+                            )));
+                        }
+                        // View and edit common jj settings
+                        else if key.code == KeyCode::Char(',') {
+                            self.popup = Some(Box::new(ConfigPopup::new(
+                                self.env.config.clone(),
+                                commander,
+                            )));
+                        }
+                        // Switch to another workspace of the repo
+                        else if key.code == KeyCode::Char('Z') {
+                            self.popup = Some(Box::new(WorkspacesPopup::new(
+                                self.env.config.clone(),
+                                commander,
+                            )));
+                        }
                     }
                 }
             };
@@ -300,3 +841,25 @@ impl<'a> App<'a> {
         Ok(false)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tab_sequence_follows_order() -> Result<()> {
+        assert_eq!(Tab::sequence(&[])?, Tab::VALUES);
+        assert_eq!(
+            Tab::sequence(&["files".to_owned(), "log".to_owned(), "files".to_owned()])?,
+            [
+                Tab::Files,
+                Tab::Log,
+                Tab::Bookmarks,
+                Tab::CommandLog,
+                Tab::OpLog
+            ]
+        );
+        assert!(Tab::sequence(&["unknown".to_owned()]).is_err());
+        Ok(())
+    }
+}
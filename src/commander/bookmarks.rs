@@ -7,7 +7,11 @@ other jj bookmark commands are defined in module [jj][super::jj].
 It is mostly used in the [bookmarks_tab][crate::ui::bookmarks_tab] module.
 */
 use crate::{
-    commander::{CommandError, Commander, RemoveEndLine},
+    commander::{
+        CommandError, Commander, RemoveEndLine,
+        ids::CommitId,
+        log::{SignatureStatus, signature_template},
+    },
     env::DiffFormat,
 };
 use ansi_to_tui::IntoText;
@@ -15,7 +19,7 @@ use anyhow::Result;
 use itertools::Itertools;
 use ratatui::text::Text;
 use regex::Regex;
-use std::{cmp::Ordering, fmt::Display, sync::LazyLock};
+use std::{cmp::Ordering, collections::HashMap, fmt::Display, sync::LazyLock};
 use tracing::instrument;
 
 #[derive(Clone, Debug, PartialEq)]
@@ -70,10 +74,121 @@ fn parse_bookmark(text: &str) -> Option<Bookmark> {
     })
 }
 
+/// Bookmarks which a push would change on the remotes, based on local tracking info
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PushSummary {
+    pub new: Vec<String>,
+    pub updated: Vec<String>,
+    pub deleted: Vec<String>,
+}
+
+impl PushSummary {
+    pub fn is_empty(&self) -> bool {
+        self.new.is_empty() && self.updated.is_empty() && self.deleted.is_empty()
+    }
+}
+
+// Template which outputs `name|remote|present|tracked|synced|commit_id`. Used to compare local
+// and remote bookmarks
+const PUSH_SUMMARY_TEMPLATE: &str = r#"name ++ "|" ++ remote ++ "|" ++ present ++ "|" ++ tracked ++ "|" ++ synced ++ "|" ++ if(normal_target, normal_target.commit_id()) ++ "\n""#;
+
+struct BookmarkRef<'a> {
+    name: &'a str,
+    remote: Option<&'a str>,
+    present: bool,
+    tracked: bool,
+    synced: bool,
+    commit_id: &'a str,
+}
+
+fn parse_bookmark_ref(text: &str) -> Option<BookmarkRef<'_>> {
+    let mut parts = text.split('|');
+    let name = parts.next()?;
+    let remote = parts.next()?;
+    let present = parts.next()?;
+    let tracked = parts.next()?;
+    let synced = parts.next()?;
+    let commit_id = parts.next()?;
+    Some(BookmarkRef {
+        name,
+        remote: if remote.is_empty() {
+            None
+        } else {
+            Some(remote)
+        },
+        present: present == "true",
+        tracked: tracked == "true",
+        synced: synced == "true",
+        commit_id,
+    })
+}
+
+/// Number of commits a local bookmark has which its tracked remote bookmark doesn't, and the
+/// other way around
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AheadBehind {
+    pub ahead: usize,
+    pub behind: usize,
+}
+
+impl Display for AheadBehind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.ahead == 0 && self.behind == 0 {
+            write!(f, "=")
+        } else {
+            write!(f, "↑{} ↓{}", self.ahead, self.behind)
+        }
+    }
+}
+
+/// Counts of [Commander::get_bookmarks_ahead_behind] by local and remote commit id. They
+/// only change when one of the bookmarks moves
+pub type AheadBehindCache = HashMap<(CommitId, CommitId), AheadBehind>;
+
+/// Local bookmark and the change it points to
+#[derive(Clone, Debug, PartialEq)]
+pub struct LocalBookmark {
+    pub name: String,
+    /// Target commit, `None` when the bookmark is conflicted
+    pub commit_id: Option<CommitId>,
+    /// Shortest change ID and first description line of the target
+    pub target: String,
+}
+
+// Template which outputs `name|remote|commit_id|change_id|description`. Used to show where local
+// bookmarks point
+const LOCAL_BOOKMARK_TEMPLATE: &str = r#"name ++ "|" ++ remote ++ "|" ++ if(normal_target, normal_target.commit_id() ++ "|" ++ normal_target.change_id().shortest(8) ++ "|" ++ normal_target.description().first_line()) ++ "\n""#;
+
+fn parse_local_bookmark(text: &str) -> Option<LocalBookmark> {
+    let mut parts = text.splitn(5, '|');
+    let name = parts.next()?;
+    let remote = parts.next()?;
+    if !remote.is_empty() {
+        return None;
+    }
+    let commit_id = parts.next().filter(|commit_id| !commit_id.is_empty());
+    let change_id = parts.next().unwrap_or_default();
+    let description = parts.next().unwrap_or_default();
+    Some(LocalBookmark {
+        name: name.to_owned(),
+        commit_id: commit_id.map(|commit_id| CommitId(commit_id.to_owned())),
+        target: match (commit_id, description.is_empty()) {
+            (None, _) => "(conflicted)".to_owned(),
+            (Some(_), true) => format!("{change_id} (no description set)"),
+            (Some(_), false) => format!("{change_id} {description}"),
+        },
+    })
+}
+
 #[derive(Clone, Debug)]
 pub enum BookmarkLine {
     Unparsable(String),
-    Parsed { text: String, bookmark: Bookmark },
+    Parsed {
+        text: String,
+        bookmark: Bookmark,
+        /// Signature status of the target commit, if the bookmark has a single target
+        signature: Option<SignatureStatus>,
+    },
 }
 
 impl BookmarkLine {
@@ -122,7 +237,10 @@ impl Commander {
                         "bookmark",
                         "list",
                         "-T",
-                        &format!(r#"{BRANCH_TEMPLATE} ++ "\n""#),
+                        &format!(
+                            r#"{BRANCH_TEMPLATE} ++ "|" ++ if(normal_target, {}) ++ "\n""#,
+                            signature_template("normal_target")
+                        ),
                     ],
                     args,
                 ]
@@ -132,12 +250,17 @@ impl Commander {
             )?
             .lines()
             .zip(bookmarks_colored.lines())
-            .map(|(line, line_colored)| match parse_bookmark(line) {
-                Some(bookmark) => BookmarkLine::Parsed {
-                    text: line_colored.to_owned(),
-                    bookmark,
-                },
-                None => BookmarkLine::Unparsable(line_colored.to_owned()),
+            .map(|(line, line_colored)| {
+                let (line, signature) = line.rsplit_once('|').unwrap_or((line, ""));
+                match parse_bookmark(line) {
+                    Some(bookmark) => BookmarkLine::Parsed {
+                        text: line_colored.to_owned(),
+                        bookmark,
+                        signature: (!signature.is_empty())
+                            .then(|| SignatureStatus::parse(signature)),
+                    },
+                    None => BookmarkLine::Unparsable(line_colored.to_owned()),
+                }
             })
             .sorted_by(|a, b| {
                 use BookmarkLine::*;
@@ -180,6 +303,183 @@ impl Commander {
         Ok(bookmarks)
     }
 
+    /// Get local bookmarks with the change they point to.
+    /// Maps to `jj bookmark list`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_local_bookmarks(&self) -> Result<Vec<LocalBookmark>, CommandError> {
+        Ok(self
+            .execute_jj_command(
+                vec!["bookmark", "list", "-T", LOCAL_BOOKMARK_TEMPLATE],
+                false,
+                true,
+            )?
+            .lines()
+            .filter_map(parse_local_bookmark)
+            .collect())
+    }
+
+    /// Get which bookmarks `jj git push` would create, update or delete on the remotes.
+    /// Only uses the local view of the remotes, so no network access is needed.
+    /// When not pushing all bookmarks, only bookmarks pointing to the commit are included.
+    /// Maps to `jj bookmark list --all-remotes`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_push_summary(
+        &self,
+        all_bookmarks: bool,
+        commit_id: &CommitId,
+    ) -> Result<PushSummary, CommandError> {
+        let output = self.execute_jj_command(
+            vec![
+                "bookmark",
+                "list",
+                "--all-remotes",
+                "-T",
+                PUSH_SUMMARY_TEMPLATE,
+            ],
+            false,
+            true,
+        )?;
+        let refs: Vec<BookmarkRef> = output
+            .lines()
+            .filter_map(parse_bookmark_ref)
+            // The git remote is the backing git repo, not something we push to
+            .filter(|bookmark_ref| bookmark_ref.remote != Some("git"))
+            .collect();
+
+        let mut summary = PushSummary::default();
+        for local in refs
+            .iter()
+            .filter(|bookmark_ref| bookmark_ref.remote.is_none())
+        {
+            let has_tracked_remote = refs.iter().any(|bookmark_ref| {
+                bookmark_ref.remote.is_some()
+                    && bookmark_ref.name == local.name
+                    && bookmark_ref.tracked
+            });
+
+            if local.present {
+                if !all_bookmarks && local.commit_id != commit_id.as_str() {
+                    continue;
+                }
+                if !has_tracked_remote {
+                    summary.new.push(local.name.to_owned());
+                } else if !local.synced {
+                    summary.updated.push(local.name.to_owned());
+                }
+            } else if all_bookmarks && has_tracked_remote {
+                summary.deleted.push(local.name.to_owned());
+            }
+        }
+
+        Ok(summary)
+    }
+
+    /// Get how far local bookmarks are ahead of and behind their tracked remote bookmark,
+    /// preferring the `origin` remote. Bookmarks without a tracked remote bookmark are left out.
+    /// Counts are taken from `cache` when both bookmarks point to the same commits as before,
+    /// and `cache` keeps the counts of this call only.
+    /// Maps to `jj bookmark list --all-remotes` and `jj log -r <remote>..<local>`
+    #[instrument(level = "trace", skip(self, cache))]
+    pub fn get_bookmarks_ahead_behind(
+        &self,
+        cache: &mut AheadBehindCache,
+    ) -> Result<HashMap<String, AheadBehind>, CommandError> {
+        let output = self.execute_jj_command(
+            vec![
+                "bookmark",
+                "list",
+                "--all-remotes",
+                "-T",
+                PUSH_SUMMARY_TEMPLATE,
+            ],
+            false,
+            true,
+        )?;
+        let refs: Vec<BookmarkRef> = output
+            .lines()
+            .filter_map(parse_bookmark_ref)
+            .filter(|bookmark_ref| bookmark_ref.remote != Some("git"))
+            .collect();
+
+        let mut ahead_behind = HashMap::new();
+        let mut counted = AheadBehindCache::new();
+        for local in refs
+            .iter()
+            .filter(|bookmark_ref| bookmark_ref.remote.is_none() && bookmark_ref.present)
+        {
+            let Some((remote, remote_commit_id)) = refs
+                .iter()
+                .filter(|bookmark_ref| {
+                    bookmark_ref.name == local.name && bookmark_ref.tracked && bookmark_ref.present
+                })
+                .filter_map(|bookmark_ref| Some((bookmark_ref.remote?, bookmark_ref.commit_id)))
+                .sorted_by_key(|(remote, _)| *remote != "origin")
+                .next()
+            else {
+                continue;
+            };
+
+            let key = (
+                CommitId(local.commit_id.to_owned()),
+                CommitId(remote_commit_id.to_owned()),
+            );
+            let counts = if local.synced {
+                AheadBehind::default()
+            } else if let Some(counts) = cache.get(&key) {
+                *counts
+            } else {
+                let local_symbol = format!(r#""{}""#, local.name);
+                let remote_symbol = format!(r#""{}"@"{remote}""#, local.name);
+                AheadBehind {
+                    ahead: self.count_revisions(&format!("{remote_symbol}..{local_symbol}"))?,
+                    behind: self.count_revisions(&format!("{local_symbol}..{remote_symbol}"))?,
+                }
+            };
+            counted.insert(key, counts);
+            ahead_behind.insert(local.name.to_owned(), counts);
+        }
+        *cache = counted;
+
+        Ok(ahead_behind)
+    }
+
+    /// Count the commits in a revset.
+    /// Maps to `jj log -r <revset>`
+    fn count_revisions(&self, revset: &str) -> Result<usize, CommandError> {
+        Ok(self
+            .execute_jj_command(
+                vec!["log", "--no-graph", "-T", r#""\n""#, "-r", revset],
+                false,
+                true,
+            )?
+            .lines()
+            .count())
+    }
+
+    /// Get names of local bookmarks pointing to a commit.
+    /// Maps to `jj log -r <commit> -T local_bookmarks`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_commit_bookmarks(&self, commit_id: &CommitId) -> Result<Vec<String>, CommandError> {
+        Ok(self
+            .execute_jj_command(
+                vec![
+                    "log",
+                    "--no-graph",
+                    "--template",
+                    r#"local_bookmarks.map(|bookmark| bookmark.name() ++ "\n").join("")"#,
+                    "-r",
+                    commit_id.as_str(),
+                    "--limit",
+                    "1",
+                ],
+                false,
+                true,
+            )?
+            .lines()
+            .map(|line| line.to_owned())
+            .collect())
+    }
+
     /// Get bookmark details.
     /// Maps to `jj show <bookmark>`
     #[instrument(level = "trace", skip(self))]
@@ -234,6 +534,13 @@ mod tests {
                 timestamp: 0,
             })
         );
+        assert!(matches!(
+            bookmarks.first(),
+            Some(BookmarkLine::Parsed {
+                signature: Some(SignatureStatus::Unsigned),
+                ..
+            })
+        ));
 
         Ok(())
     }
@@ -266,6 +573,158 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn get_push_summary() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        test_repo.commander.create_bookmark("test")?;
+        let head = test_repo.commander.get_current_head()?;
+        let parent = test_repo.commander.get_commit_parent(&head.commit_id)?;
+
+        assert_eq!(
+            test_repo
+                .commander
+                .get_push_summary(false, &head.commit_id)?,
+            PushSummary {
+                new: vec!["test".to_owned()],
+                ..Default::default()
+            }
+        );
+        assert_eq!(
+            test_repo
+                .commander
+                .get_push_summary(false, &parent.commit_id)?,
+            PushSummary::default()
+        );
+        assert_eq!(
+            test_repo
+                .commander
+                .get_push_summary(true, &parent.commit_id)?,
+            PushSummary {
+                new: vec!["test".to_owned()],
+                ..Default::default()
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn ahead_behind_display() {
+        assert_eq!(AheadBehind::default().to_string(), "=");
+        assert_eq!(
+            AheadBehind {
+                ahead: 2,
+                behind: 0
+            }
+            .to_string(),
+            "↑2 ↓0"
+        );
+    }
+
+    #[test]
+    fn get_bookmarks_ahead_behind_without_remote() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        test_repo.commander.create_bookmark("test")?;
+        assert_eq!(
+            test_repo
+                .commander
+                .get_bookmarks_ahead_behind(&mut AheadBehindCache::new())?,
+            HashMap::new()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_bookmarks_ahead_behind_with_remote() -> Result<()> {
+        let remote = TestRepo::new()?;
+        remote.commander.run_describe("@", "Base")?;
+        remote.commander.run_new("@")?;
+        remote.commander.run_describe("@", "Remote")?;
+        remote.commander.create_bookmark("main")?;
+
+        let test_repo = TestRepo::new()?;
+        test_repo
+            .commander
+            .add_git_remote("origin", &remote.directory.path().to_string_lossy())?;
+        test_repo.commander.git_fetch_remote("origin")?;
+        test_repo.commander.track_bookmark(&Bookmark {
+            name: "main".to_owned(),
+            remote: Some("origin".to_owned()),
+            present: true,
+            timestamp: 0,
+        })?;
+
+        // Move main next to the remote commit, making it one commit ahead and one behind
+        test_repo.commander.run_new(r#""main"@"origin"-"#)?;
+        test_repo.commander.run_describe("@", "Local")?;
+        let head = test_repo.commander.get_current_head()?;
+        test_repo
+            .commander
+            .set_bookmark_commit("main", &head.commit_id)?;
+
+        let expected = HashMap::from([(
+            "main".to_owned(),
+            AheadBehind {
+                ahead: 1,
+                behind: 1,
+            },
+        )]);
+        let mut cache = AheadBehindCache::new();
+        assert_eq!(
+            test_repo.commander.get_bookmarks_ahead_behind(&mut cache)?,
+            expected
+        );
+        assert_eq!(cache.values().collect::<Vec<_>>(), [&expected["main"]]);
+        assert_eq!(
+            test_repo.commander.get_bookmarks_ahead_behind(&mut cache)?,
+            expected
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_commit_bookmarks() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        let head = test_repo.commander.get_current_head()?;
+        assert_eq!(
+            test_repo.commander.get_commit_bookmarks(&head.commit_id)?,
+            Vec::<String>::new()
+        );
+
+        test_repo.commander.create_bookmark("a")?;
+        test_repo.commander.create_bookmark("b")?;
+        let head = test_repo.commander.get_current_head()?;
+        assert_eq!(
+            test_repo.commander.get_commit_bookmarks(&head.commit_id)?,
+            ["a", "b"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_local_bookmarks() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        test_repo.commander.run_describe("@", "Target")?;
+        test_repo.commander.create_bookmark("test")?;
+        let head = test_repo.commander.get_current_head()?;
+
+        let bookmarks = test_repo.commander.get_local_bookmarks()?;
+        assert_eq!(bookmarks.len(), 1);
+        let bookmark = &bookmarks[0];
+        assert_eq!(bookmark.name, "test");
+        assert_eq!(bookmark.commit_id.as_ref(), Some(&head.commit_id));
+        assert!(bookmark.target.ends_with(" Target"));
+
+        Ok(())
+    }
+
     #[test]
     fn get_bookmark_show() -> Result<()> {
         let test_repo = TestRepo::new()?;
@@ -0,0 +1,90 @@
+/*!
+[Commander] member functions related to jj config.
+
+Config values are read and edited one at a time. It is used by the
+[config_popup][crate::ui::config_popup] module.
+*/
+
+use crate::commander::{CommandError, Commander, RemoveEndLine};
+
+use tracing::instrument;
+
+/// Config file written by `jj config set`
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ConfigScope {
+    User,
+    Repo,
+}
+
+impl ConfigScope {
+    pub fn flag(self) -> &'static str {
+        match self {
+            ConfigScope::User => "--user",
+            ConfigScope::Repo => "--repo",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            ConfigScope::User => ConfigScope::Repo,
+            ConfigScope::Repo => ConfigScope::User,
+        }
+    }
+}
+
+impl Commander {
+    /// Get a config value, or `None` when it is not set.
+    /// Maps to `jj config get <name>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_config_value(&self, name: &str) -> Result<Option<String>, CommandError> {
+        match self.execute_jj_command(vec!["config", "get", name], false, true) {
+            Ok(output) => Ok(Some(output.remove_end_line())),
+            Err(CommandError::Status(output, _)) if output.contains("Value not found") => Ok(None),
+            Err(err) => Err(err),
+        }
+    }
+
+    /// Set a config value in the user or repo config file. jj reads the value as TOML if it
+    /// can, and as a string otherwise.
+    /// Maps to `jj config set --user|--repo <name> <value>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn set_config_value(
+        &self,
+        scope: ConfigScope,
+        name: &str,
+        value: &str,
+    ) -> Result<(), CommandError> {
+        self.execute_jj_command(
+            vec!["config", "set", scope.flag(), name, value],
+            false,
+            true,
+        )?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commander::tests::TestRepo;
+    use anyhow::Result;
+
+    #[test]
+    fn set_config_value() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        test_repo
+            .commander
+            .set_config_value(ConfigScope::Repo, "ui.diff-editor", "meld")?;
+        assert_eq!(
+            test_repo.commander.get_config_value("ui.diff-editor")?,
+            Some("meld".to_owned())
+        );
+        assert_eq!(
+            test_repo.commander.get_config_value("lazyjj.not-set")?,
+            None
+        );
+
+        Ok(())
+    }
+}
@@ -36,6 +36,45 @@ pub struct Conflict {
     pub path: String,
 }
 
+/// Summary of the working copy, like `jj status`
+#[derive(Clone, Debug, PartialEq)]
+pub struct WorkingCopyStatus {
+    pub head: Head,
+    /// First line of the description of @
+    pub description: String,
+    pub added: usize,
+    /// Modified and renamed files
+    pub modified: usize,
+    pub deleted: usize,
+    pub conflicts: Vec<Conflict>,
+}
+
+/// Line of a file with the change which last modified it
+#[derive(Clone, Debug, PartialEq)]
+pub struct AnnotatedLine {
+    /// Shortest unique prefix of the change ID
+    pub change_id: String,
+    pub commit_id: CommitId,
+    pub author: String,
+    pub content: String,
+}
+
+// Template which outputs `change_id\tcommit_id\tauthor\tcontent` for each line of a file
+const ANNOTATE_TEMPLATE: &str = r#"commit.change_id().shortest(8) ++ "\t" ++ commit.commit_id() ++ "\t" ++ commit.author().name() ++ "\t" ++ content"#;
+
+impl File {
+    /// Path of the file to pass to jj. For renames, this is the new path
+    pub fn target_path(&self) -> Option<&str> {
+        let path = self.path.as_ref()?;
+        if let Some(DiffType::Renamed) = self.diff_type
+            && let Some(captures) = RENAME_REGEX.captures(path)
+        {
+            return captures.get(2).map(|path| path.as_str());
+        }
+        Some(path)
+    }
+}
+
 impl DiffType {
     pub fn parse(value: &str) -> Option<Self> {
         match value {
@@ -125,6 +164,36 @@ impl Commander {
         }
     }
 
+    /// Get the change id, description, changed file counts and conflicts of @.
+    /// Maps to `jj log -r @`, `jj diff --summary -r @` and `jj resolve --list -r @`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_working_copy_status(&self) -> Result<WorkingCopyStatus> {
+        let (head, description) = self
+            .get_revset_commits("@")?
+            .into_iter()
+            .next()
+            .context("Working copy not found")?;
+        let files = self.get_files(&head)?;
+        let count = |diff_types: &[DiffType]| {
+            files
+                .iter()
+                .filter(|file| {
+                    file.diff_type
+                        .as_ref()
+                        .is_some_and(|diff_type| diff_types.contains(diff_type))
+                })
+                .count()
+        };
+        Ok(WorkingCopyStatus {
+            added: count(&[DiffType::Added]),
+            modified: count(&[DiffType::Modified, DiffType::Renamed]),
+            deleted: count(&[DiffType::Deleted]),
+            conflicts: self.get_conflicts(&head.commit_id)?,
+            head,
+            description,
+        })
+    }
+
     /// Get diff for file change in a change.
     /// Maps to `jj diff -r <revision> <path>`
     #[instrument(level = "trace", skip(self))]
@@ -135,22 +204,10 @@ impl Commander {
         diff_format: &DiffFormat,
         ignore_working_copy: bool,
     ) -> Result<Option<String>, CommandError> {
-        let Some(path) = current_file.path.as_ref() else {
+        let Some(path) = current_file.target_path() else {
             return Ok(None);
         };
 
-        let path = if let (true, Some(captures)) = (
-            current_file.diff_type == Some(DiffType::Renamed),
-            RENAME_REGEX.captures(path),
-        ) {
-            match captures.get(2) {
-                Some(path) => path.as_str(),
-                None => return Ok(None),
-            }
-        } else {
-            path
-        };
-
         let mut args = vec!["diff", "-r", head.commit_id.as_str(), path];
         args.append(&mut diff_format.get_args());
         if ignore_working_copy {
@@ -160,23 +217,58 @@ impl Commander {
         self.execute_jj_command(args, true, true).map(Some)
     }
 
+    /// Get the lines of a file at a change, with the change which last modified each line.
+    /// Maps to `jj file annotate -r <revision> <path>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_file_annotate(
+        &self,
+        head: &Head,
+        path: &str,
+    ) -> Result<Vec<AnnotatedLine>, CommandError> {
+        Ok(self
+            .execute_jj_command(
+                vec![
+                    "file",
+                    "annotate",
+                    "-r",
+                    head.commit_id.as_str(),
+                    "-T",
+                    ANNOTATE_TEMPLATE,
+                    path,
+                ],
+                false,
+                true,
+            )?
+            .lines()
+            .filter_map(|line| {
+                let mut parts = line.splitn(4, '\t');
+                Some(AnnotatedLine {
+                    change_id: parts.next()?.to_owned(),
+                    commit_id: CommitId(parts.next()?.to_owned()),
+                    author: parts.next()?.to_owned(),
+                    content: parts.next()?.to_owned(),
+                })
+            })
+            .collect())
+    }
+
+    /// Get the full contents of a file at a change.
+    /// Maps to `jj file show -r <revision> <path>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_file_content(&self, head: &Head, path: &str) -> Result<String, CommandError> {
+        self.execute_jj_command(
+            vec!["file", "show", "-r", head.commit_id.as_str(), path],
+            false,
+            true,
+        )
+    }
+
     #[instrument(level = "trace", skip(self))]
     pub fn untrack_file(&self, current_file: &File) -> Result<Option<String>, CommandError> {
-        let Some(path) = current_file.path.as_ref() else {
+        let Some(path) = current_file.target_path() else {
             return Ok(None);
         };
 
-        let path = if let Some(DiffType::Renamed) = current_file.diff_type
-            && let Some(captures) = RENAME_REGEX.captures(path)
-        {
-            match captures.get(2) {
-                Some(path) => path.as_str(),
-                None => return Ok(None),
-            }
-        } else {
-            path
-        };
-
         Ok(Some(self.execute_jj_command(
             vec!["file", "untrack", path],
             false,
@@ -375,6 +467,52 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn get_file_annotate() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        fs::write(test_repo.directory.path().join("README"), b"AAA\nBBB\n")?;
+        let first = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new("@")?;
+        fs::write(test_repo.directory.path().join("README"), b"AAA\nCCC\n")?;
+        let second = test_repo.commander.get_current_head()?;
+        let first = test_repo.commander.get_head_latest(&first)?;
+
+        let lines = test_repo.commander.get_file_annotate(&second, "README")?;
+        assert_eq!(
+            lines
+                .iter()
+                .map(|line| (&line.commit_id, line.content.as_str()))
+                .collect::<Vec<_>>(),
+            [(&first.commit_id, "AAA"), (&second.commit_id, "CCC")]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_file_content() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        fs::write(test_repo.directory.path().join("README"), b"AAA\n\tBBB\n")?;
+        let first = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new("@")?;
+        fs::write(test_repo.directory.path().join("README"), b"CCC\n")?;
+        let second = test_repo.commander.get_current_head()?;
+        let first = test_repo.commander.get_head_latest(&first)?;
+
+        assert_eq!(
+            test_repo.commander.get_file_content(&first, "README")?,
+            "AAA\n\tBBB\n"
+        );
+        assert_eq!(
+            test_repo.commander.get_file_content(&second, "README")?,
+            "CCC\n"
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn get_conflicts() -> Result<()> {
         let test_repo = TestRepo::new()?;
@@ -413,4 +551,26 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn get_working_copy_status() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        fs::write(test_repo.directory.path().join("README"), b"AAA")?;
+        fs::write(test_repo.directory.path().join("LICENSE"), b"BBB")?;
+        test_repo.commander.run_describe("@", "First")?;
+        test_repo.commander.run_new("@")?;
+        fs::write(test_repo.directory.path().join("README"), b"CCC")?;
+        fs::remove_file(test_repo.directory.path().join("LICENSE"))?;
+        fs::write(test_repo.directory.path().join("NEW"), b"DDD")?;
+        test_repo.commander.run_describe("@", "Second\n\nBody")?;
+
+        let status = test_repo.commander.get_working_copy_status()?;
+        assert_eq!(status.head, test_repo.commander.get_current_head()?);
+        assert_eq!(status.description, "Second");
+        assert_eq!((status.added, status.modified, status.deleted), (1, 1, 1));
+        assert_eq!(status.conflicts, []);
+
+        Ok(())
+    }
 }
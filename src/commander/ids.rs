@@ -15,10 +15,6 @@ impl ChangeId {
     pub fn as_str(&self) -> &str {
         &self.0
     }
-
-    pub fn as_string(&self) -> String {
-        self.0.to_owned()
-    }
 }
 
 impl AsRef<OsStr> for ChangeId {
@@ -45,10 +41,6 @@ impl CommitId {
     pub fn as_str(&self) -> &str {
         &self.0
     }
-
-    // pub fn as_string(&self) -> String {
-    //     self.0.to_owned()
-    // }
 }
 
 impl AsRef<OsStr> for CommitId {
@@ -5,11 +5,94 @@ The module implementes a number of jj commands.
 Surprisingly, this module also contains jj bookmark commands.
 These functions are used everywhere (bookmark tab, log tab).
 */
-use crate::commander::{CommandError, Commander, bookmarks::Bookmark, ids::CommitId};
-
-use anyhow::{Context, Result};
+use crate::commander::{
+    CommandError, Commander,
+    bookmarks::{Bookmark, PushSummary},
+    ids::CommitId,
+    log::Head,
+};
+
+use anyhow::{Context, Result, bail};
+use itertools::Itertools;
 use tracing::instrument;
 
+/// Which commits `jj rebase` moves
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RebaseMode {
+    /// Only the commits of the revset (`-r`)
+    Revisions,
+    /// The commits of the revset and their descendants (`-s`)
+    Source,
+    /// The whole branch of the revset, relative to the destination (`-b`)
+    Branch,
+}
+
+impl RebaseMode {
+    pub fn flag(self) -> &'static str {
+        match self {
+            RebaseMode::Revisions => "-r",
+            RebaseMode::Source => "-s",
+            RebaseMode::Branch => "-b",
+        }
+    }
+
+    pub fn next(self) -> Self {
+        match self {
+            RebaseMode::Revisions => RebaseMode::Source,
+            RebaseMode::Source => RebaseMode::Branch,
+            RebaseMode::Branch => RebaseMode::Revisions,
+        }
+    }
+
+    /// Revset of the commits moved when rebasing the revset onto the destination
+    pub fn moved_revset(self, revset: &str, destination: &CommitId) -> String {
+        match self {
+            RebaseMode::Revisions => revset.to_owned(),
+            RebaseMode::Source => format!("({revset})::"),
+            RebaseMode::Branch => format!("({destination}..({revset}))::"),
+        }
+    }
+}
+
+/// Arguments of `jj git push` for the bookmarks of a change, or all bookmarks
+fn git_push_args(all_bookmarks: bool, allow_new: bool, commit_id: &CommitId) -> Vec<&str> {
+    let mut args = vec!["git", "push"];
+    if allow_new {
+        args.push("--allow-new");
+    }
+    if all_bookmarks {
+        args.push("--all");
+    } else {
+        args.push("-r");
+        args.push(commit_id.as_str());
+    }
+    args
+}
+
+/// Read the bookmark changes listed by `jj git push --dry-run`, e.g.
+/// `  Move forward bookmark main from 0123abcd to 4567cdef`
+fn parse_push_dry_run(output: &str) -> PushSummary {
+    let mut summary = PushSummary::default();
+    for line in output.lines() {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let Some(name) = words
+            .iter()
+            .position(|word| *word == "bookmark")
+            .and_then(|position| words.get(position + 1))
+        else {
+            continue;
+        };
+        let names = match words[0] {
+            "Add" => &mut summary.new,
+            "Move" | "Force" => &mut summary.updated,
+            "Delete" => &mut summary.deleted,
+            _ => continue,
+        };
+        names.push((*name).to_owned());
+    }
+    summary
+}
+
 impl Commander {
     /// Create a new change after revision. Maps to `jj new <revision>`
     #[instrument(level = "trace", skip(self))]
@@ -18,6 +101,14 @@ impl Commander {
             .context("Failed executing jj new")
     }
 
+    /// Create a new change after revision, leaving the working copy where it is.
+    /// Maps to `jj new --no-edit <revision>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn run_new_no_edit(&self, revision: &str) -> Result<()> {
+        self.execute_void_jj_command(vec!["new", "--no-edit", revision])
+            .context("Failed executing jj new")
+    }
+
     /// Edit change. Maps to `jj edit <commit>`
     #[instrument(level = "trace", skip(self))]
     pub fn run_edit(&self, revision: &str, ignore_immutable: bool) -> Result<()> {
@@ -30,13 +121,139 @@ impl Commander {
             .context("Failed executing jj edit")
     }
 
-    /// Abandon change. Maps to `jj abandon <revision>`
+    /// Abandon change, optionally moving its bookmarks to its parent.
+    /// Maps to `jj abandon [--retain-bookmarks] <revision>`
     #[instrument(level = "trace", skip(self))]
-    pub fn run_abandon(&self, commit_id: &CommitId) -> Result<()> {
-        self.execute_void_jj_command(vec!["abandon", commit_id.as_str()])
+    pub fn run_abandon(&self, commit_id: &CommitId, retain_bookmarks: bool) -> Result<()> {
+        let mut args = vec!["abandon", commit_id.as_str()];
+        if retain_bookmarks {
+            args.push("--retain-bookmarks");
+        }
+
+        self.execute_void_jj_command(args)
+            .context("Failed executing jj abandon")
+    }
+
+    /// Abandon several changes in a single operation. Nothing is abandoned if any of them can't
+    /// be, e.g. because it is immutable.
+    /// Maps to `jj abandon <revision>...`
+    #[instrument(level = "trace", skip(self))]
+    pub fn run_abandon_many(&self, commit_ids: &[CommitId]) -> Result<()> {
+        if commit_ids.is_empty() {
+            bail!("No changes to abandon");
+        }
+
+        let mut args = vec!["abandon"];
+        args.extend(commit_ids.iter().map(CommitId::as_str));
+        self.execute_void_jj_command(args)
             .context("Failed executing jj abandon")
     }
 
+    /// Duplicate a commit onto destinations, or onto the parents of the commit, and return
+    /// the duplicate. It is the newest child of the destinations which did not exist before.
+    /// Maps to `jj duplicate <revision> [--destination <destination>]...`
+    #[instrument(level = "trace", skip(self))]
+    pub fn run_duplicate(&self, commit_id: &CommitId, destinations: &[CommitId]) -> Result<Head> {
+        let operation = self
+            .get_operations(1)?
+            .pop()
+            .context("Failed getting current operation")?;
+
+        let mut args = vec!["duplicate", commit_id.as_str()];
+        for destination in destinations {
+            args.push("--destination");
+            args.push(destination.as_str());
+        }
+        self.execute_void_jj_command(args)
+            .context("Failed executing jj duplicate")?;
+
+        let parents = if destinations.is_empty() {
+            format!("parents({commit_id})")
+        } else {
+            destinations.iter().join("|")
+        };
+        let Some((head, _)) = self
+            .get_revset_commits(&format!(
+                "latest(children({parents}) ~ at_operation({}, children({parents})))",
+                operation.id
+            ))?
+            .pop()
+        else {
+            bail!("Failed finding the duplicate of {commit_id}");
+        };
+        Ok(head)
+    }
+
+    /// Create a commit undoing the changes of a commit on top of a destination, returning it.
+    /// jj replaced `jj backout` with `jj revert`, which takes the same arguments.
+    /// Maps to `jj revert -r <revision> -d <destination>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn run_backout(&self, commit_id: &CommitId, destination: &str) -> Result<Head> {
+        let operation = self
+            .get_operations(1)?
+            .pop()
+            .context("Failed getting current operation")?;
+
+        self.execute_void_jj_command(vec!["revert", "-r", commit_id.as_str(), "-d", destination])
+            .context("Failed executing jj revert")?;
+
+        let Some((head, _)) = self
+            .get_revset_commits(&format!(
+                "latest(children({destination}) ~ at_operation({}, children({destination})))",
+                operation.id
+            ))?
+            .pop()
+        else {
+            bail!("Failed finding the backout of {commit_id}");
+        };
+        Ok(head)
+    }
+
+    /// Sign a commit with the configured signing backend.
+    /// Maps to `jj sign -r <revision>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn run_sign(&self, commit_id: &CommitId) -> Result<()> {
+        self.execute_void_jj_command(vec!["sign", "-r", commit_id.as_str()])
+            .context("Failed executing jj sign")
+    }
+
+    /// Drop the signature of a commit.
+    /// Maps to `jj unsign -r <revision>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn run_unsign(&self, commit_id: &CommitId) -> Result<()> {
+        self.execute_void_jj_command(vec!["unsign", "-r", commit_id.as_str()])
+            .context("Failed executing jj unsign")
+    }
+
+    /// Create a new change with several parents, e.g. a merge.
+    /// Maps to `jj new <revision>...`
+    #[instrument(level = "trace", skip(self))]
+    pub fn run_new_merge(&self, parents: &[CommitId]) -> Result<()> {
+        if parents.is_empty() {
+            bail!("A new change needs at least one parent");
+        }
+
+        let mut args = vec!["new"];
+        args.extend(parents.iter().map(CommitId::as_str));
+        self.execute_void_jj_command(args)
+            .context("Failed executing jj new")
+    }
+
+    /// Turn a chain of commits into siblings, keeping the parents of the chain's root and the
+    /// children of its head.
+    /// Maps to `jj parallelize <revision>...`
+    #[instrument(level = "trace", skip(self))]
+    pub fn run_parallelize(&self, revisions: &[CommitId]) -> Result<()> {
+        if revisions.len() < 2 {
+            bail!("Parallelizing needs at least two changes");
+        }
+
+        let mut args = vec!["parallelize"];
+        args.extend(revisions.iter().map(CommitId::as_str));
+        self.execute_void_jj_command(args)
+            .context("Failed executing jj parallelize")
+    }
+
     /// Describe change. Maps to `jj describe <revision> -m <message>`
     #[instrument(level = "trace", skip(self))]
     pub fn run_describe(&self, revision: &str, message: &str) -> Result<()> {
@@ -44,18 +261,212 @@ impl Commander {
             .context("Failed executing jj describe")
     }
 
+    /// Get the editor jj opens for descriptions, as the program followed by its arguments.
+    /// jj fills `ui.editor` from `$JJ_EDITOR`, `$VISUAL` or `$EDITOR` when they are set.
+    /// Maps to `jj config list --include-defaults ui.editor`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_editor(&self) -> Result<Vec<String>> {
+        let output = self
+            .execute_jj_command(
+                vec!["config", "list", "--include-defaults", "ui.editor"],
+                false,
+                true,
+            )
+            .context("Failed getting editor")?;
+        let config = toml::from_str::<toml::Table>(&output).context("Failed parsing editor")?;
+
+        // The editor is either a command line or an array of arguments
+        let editor = match config.get("ui").and_then(|ui| ui.get("editor")).cloned() {
+            Some(toml::Value::String(editor)) => shell_words::split(&editor)?,
+            Some(toml::Value::Array(editor)) => editor
+                .into_iter()
+                .filter_map(|arg| arg.as_str().map(str::to_owned))
+                .collect(),
+            _ => vec![],
+        };
+        if editor.is_empty() {
+            bail!("No editor configured, set ui.editor in the jj config");
+        }
+        Ok(editor)
+    }
+
     /// Squash changes. Maps to `jj squash -u --into <revision>`
     #[instrument(level = "trace", skip(self))]
-    pub fn run_squash(&mut self, revision: &str, ignore_immutable: bool) -> Result<()> {
+    pub fn run_squash(
+        &mut self,
+        revision: &str,
+        ignore_immutable: bool,
+        keep_emptied: bool,
+    ) -> Result<()> {
         let mut args = vec!["squash", "-u", "--into", revision];
         if ignore_immutable {
             args.push("--ignore-immutable");
         }
+        if keep_emptied {
+            args.push("--keep-emptied");
+        }
+
+        self.execute_void_jj_command(args)
+            .context("Failed executing jj squash")
+    }
+
+    /// Edit the diff of a commit in the diff editor configured for jj. The diff editor uses the
+    /// terminal, so the UI must be suspended meanwhile.
+    /// Maps to `jj diffedit -r <revision> [--ignore-immutable]`
+    #[instrument(level = "trace", skip(self))]
+    pub fn run_diffedit(&self, commit_id: &CommitId, ignore_immutable: bool) -> Result<()> {
+        let mut args = vec!["diffedit", "-r", commit_id.as_str()];
+        if ignore_immutable {
+            args.push("--ignore-immutable");
+        }
+
+        self.execute_interactive_jj_command(args)
+            .context("Failed executing jj diffedit")
+    }
+
+    /// Move the changes to paths from one commit into another, keeping the description of the
+    /// destination. Maps to `jj squash -u --from <from> --into <into> <paths>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn run_squash_paths(&self, from: &CommitId, into: &str, paths: &[&str]) -> Result<()> {
+        let mut args = vec!["squash", "-u", "--from", from.as_str(), "--into", into];
+        args.extend_from_slice(paths);
+
+        self.execute_void_jj_command(args)
+            .context("Failed executing jj squash")
+    }
+
+    /// Move paths out of a commit into a new commit before it, with the given description.
+    /// Maps to `jj split -r <revision> -m <message> <paths>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn run_split_paths(
+        &self,
+        commit_id: &CommitId,
+        paths: &[&str],
+        message: &str,
+    ) -> Result<()> {
+        let mut args = vec!["split", "-r", commit_id.as_str(), "-m", message];
+        args.extend_from_slice(paths);
 
         self.execute_void_jj_command(args)
+            .context("Failed executing jj split")
+    }
+
+    /// Move paths out of a commit into a new commit before it, both keeping the description.
+    /// `jj split --interactive` needs a terminal for the diff editor, so paths are passed instead.
+    /// Maps to `jj split -r <revision> -m <description> <paths>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn run_split(&self, commit_id: &CommitId, paths: &[&str]) -> Result<()> {
+        let description = self.get_commit_description(commit_id)?;
+        self.run_split_paths(commit_id, paths, &description)
+    }
+
+    /// Move changes of a commit into the mutable ancestors which last modified the same lines,
+    /// returning the summary jj prints of the commits changed.
+    /// Maps to `jj absorb [--from <revision>] [--into <revset>]`
+    #[instrument(level = "trace", skip(self))]
+    pub fn run_absorb(
+        &self,
+        from: Option<&CommitId>,
+        into: Option<&str>,
+    ) -> Result<String, CommandError> {
+        let mut args = vec!["absorb"];
+        if let Some(from) = from {
+            args.push("--from");
+            args.push(from.as_str());
+        }
+        if let Some(into) = into {
+            args.push("--into");
+            args.push(into);
+        }
+
+        self.execute_jj_command_messages(args)
+    }
+
+    /// Run the configured code formatters on the files of a commit and its descendants,
+    /// returning the summary jj prints of the commits fixed.
+    /// Maps to `jj fix [-s <revision>]`
+    #[instrument(level = "trace", skip(self))]
+    pub fn run_fix(&self, source: Option<&str>) -> Result<String, CommandError> {
+        let mut args = vec!["fix"];
+        if let Some(source) = source {
+            args.push("-s");
+            args.push(source);
+        }
+
+        self.execute_jj_command_messages(args)
+    }
+
+    /// Rebase all commits in a revset onto a destination.
+    /// Maps to `jj rebase -r <revset> -d <destination>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn run_rebase(&self, revset: &str, destination: &CommitId) -> Result<()> {
+        self.execute_void_jj_command(vec!["rebase", "-r", revset, "-d", destination.as_str()])
+            .context("Failed executing jj rebase")
+    }
+
+    /// Rebase a revset onto a destination, moving the commits selected by the mode.
+    /// Returns jj's messages, which mention new conflicts.
+    /// Maps to `jj rebase <-r|-s|-b> <revset> -d <destination>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn run_rebase_mode(
+        &self,
+        mode: RebaseMode,
+        revset: &str,
+        destination: &CommitId,
+    ) -> Result<String, CommandError> {
+        self.execute_jj_command_messages(vec![
+            "rebase",
+            mode.flag(),
+            revset,
+            "-d",
+            destination.as_str(),
+        ])
+    }
+
+    /// Rebase a commit and its descendants onto a destination.
+    /// Maps to `jj rebase -s <source> -d <destination>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn run_rebase_source(&self, source: &CommitId, destination: &CommitId) -> Result<()> {
+        self.execute_void_jj_command(vec![
+            "rebase",
+            "-s",
+            source.as_str(),
+            "-d",
+            destination.as_str(),
+        ])
+        .context("Failed executing jj rebase")
+    }
+
+    /// Squash a commit into its parent, keeping the parent's description.
+    /// Maps to `jj squash -u -r <revision>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn run_squash_into_parent(&self, commit_id: &CommitId) -> Result<()> {
+        self.execute_void_jj_command(vec!["squash", "-u", "-r", commit_id.as_str()])
             .context("Failed executing jj squash")
     }
 
+    /// Restore paths in the working copy from a commit, or all paths if none are given.
+    /// Maps to `jj restore --from <revision> [paths]`
+    #[instrument(level = "trace", skip(self))]
+    pub fn run_restore(&self, from: &CommitId, paths: &[&str]) -> Result<()> {
+        let mut args = vec!["restore", "--from", from.as_str()];
+        args.extend_from_slice(paths);
+
+        self.execute_void_jj_command(args)
+            .context("Failed executing jj restore")
+    }
+
+    /// Restore paths of a commit from its parents, discarding the commit's changes to them.
+    /// Maps to `jj restore --changes-in <revision> <paths>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn run_restore_changes(&self, commit_id: &CommitId, paths: &[&str]) -> Result<()> {
+        let mut args = vec!["restore", "--changes-in", commit_id.as_str()];
+        args.extend_from_slice(paths);
+
+        self.execute_void_jj_command(args)
+            .context("Failed executing jj restore")
+    }
+
     /// Create bookmark. Maps to `jj bookmark create <name>`
     #[instrument(level = "trace", skip(self))]
     pub fn create_bookmark(&self, name: &str) -> Result<Bookmark, CommandError> {
@@ -142,16 +553,39 @@ impl Commander {
         allow_new: bool,
         commit_id: &CommitId,
     ) -> Result<String, CommandError> {
-        let mut args = vec!["git", "push"];
+        self.execute_jj_command(
+            git_push_args(all_bookmarks, allow_new, commit_id),
+            true,
+            true,
+        )
+    }
+
+    /// Get which bookmarks `jj git push` would create, move or delete on the remotes, without
+    /// pushing. Unlike [Commander::get_push_summary], jj runs its usual checks, e.g. for
+    /// commits without a description.
+    /// Maps to `jj git push --dry-run`
+    #[instrument(level = "trace", skip(self))]
+    pub fn git_push_dry_run(
+        &self,
+        all_bookmarks: bool,
+        allow_new: bool,
+        commit_id: &CommitId,
+    ) -> Result<PushSummary, CommandError> {
+        let mut args = git_push_args(all_bookmarks, allow_new, commit_id);
+        args.push("--dry-run");
+
+        // The planned changes are only printed to stderr
+        let output = self.execute_command_output(&mut self.jj_command(args, false, false)?)?;
+        Ok(parse_push_dry_run(&String::from_utf8(output.stderr)?))
+    }
+
+    /// Git push a single bookmark. Maps to `jj git push --bookmark <name>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn git_push_bookmark(&self, name: &str, allow_new: bool) -> Result<String, CommandError> {
+        let mut args = vec!["git", "push", "--bookmark", name];
         if allow_new {
             args.push("--allow-new");
         }
-        if all_bookmarks {
-            args.push("--all");
-        } else {
-            args.push("-r");
-            args.push(commit_id.as_str());
-        }
 
         self.execute_jj_command(args, true, true)
     }
@@ -166,6 +600,12 @@ impl Commander {
 
         self.execute_jj_command(args, true, true)
     }
+
+    /// Git fetch from a single remote. Maps to `jj git fetch --remote <name>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn git_fetch_remote(&self, remote: &str) -> Result<String, CommandError> {
+        self.execute_jj_command(vec!["git", "fetch", "--remote", remote], true, true)
+    }
 }
 
 #[cfg(test)]
@@ -197,6 +637,39 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn run_new_no_edit() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        let head = test_repo.commander.get_current_head()?;
+        test_repo
+            .commander
+            .run_new_no_edit(head.commit_id.as_str())?;
+        assert_eq!(
+            test_repo
+                .commander
+                .command_history
+                .lock()
+                .unwrap()
+                .last()
+                .unwrap()
+                .args
+                .first()
+                .unwrap(),
+            "new"
+        );
+        assert_eq!(head, test_repo.commander.get_current_head()?);
+        assert_eq!(
+            test_repo
+                .commander
+                .get_revset_commits(&format!("children({})", head.commit_id.as_str()))?
+                .len(),
+            1
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn run_edit() -> Result<()> {
         let test_repo = TestRepo::new()?;
@@ -230,7 +703,7 @@ mod tests {
         let test_repo = TestRepo::new()?;
 
         let head = test_repo.commander.get_current_head()?;
-        test_repo.commander.run_abandon(&head.commit_id)?;
+        test_repo.commander.run_abandon(&head.commit_id, false)?;
         assert_eq!(
             test_repo
                 .commander
@@ -249,6 +722,91 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn run_abandon_retain_bookmarks() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        let parent = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new(parent.commit_id.as_str())?;
+        let head = test_repo.commander.get_current_head()?;
+        test_repo
+            .commander
+            .create_bookmark_commit("retained", &head.commit_id)?;
+
+        test_repo.commander.run_abandon(&head.commit_id, true)?;
+        assert_eq!(
+            test_repo
+                .commander
+                .get_commit_bookmarks(&parent.commit_id)?,
+            ["retained"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn run_abandon_many() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        let base = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new(base.commit_id.as_str())?;
+        let first = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new(first.commit_id.as_str())?;
+        let second = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new(base.commit_id.as_str())?;
+        let children = format!("children({})", base.commit_id.as_str());
+        assert_eq!(test_repo.commander.get_revset_commits(&children)?.len(), 2);
+
+        // The immutable root commit can't be abandoned, so neither is the other change
+        let (root, _) = test_repo.commander.get_revset_commits("root()")?.remove(0);
+        assert!(
+            test_repo
+                .commander
+                .run_abandon_many(&[second.commit_id.clone(), root.commit_id])
+                .is_err()
+        );
+        assert_eq!(
+            test_repo
+                .commander
+                .get_revset_commits(&format!("children({})", first.commit_id.as_str()))?
+                .len(),
+            1
+        );
+
+        test_repo
+            .commander
+            .run_abandon_many(&[first.commit_id, second.commit_id])?;
+        assert_eq!(test_repo.commander.get_revset_commits(&children)?.len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_editor() -> Result<()> {
+        let mut test_repo = TestRepo::new()?;
+
+        let jj_config_toml = test_repo.commander.jj_config_toml.clone().unwrap();
+        test_repo.commander.jj_config_toml = Some(
+            [
+                jj_config_toml.clone(),
+                vec![r#"ui.editor="vim -f""#.to_owned()],
+            ]
+            .concat(),
+        );
+        assert_eq!(test_repo.commander.get_editor()?, ["vim", "-f"]);
+
+        test_repo.commander.jj_config_toml = Some(
+            [
+                jj_config_toml,
+                vec![r#"ui.editor=["code", "--wait"]"#.to_owned()],
+            ]
+            .concat(),
+        );
+        assert_eq!(test_repo.commander.get_editor()?, ["code", "--wait"]);
+
+        Ok(())
+    }
+
     #[test]
     fn run_describe() -> Result<()> {
         let test_repo = TestRepo::new()?;
@@ -278,50 +836,569 @@ mod tests {
     }
 
     #[test]
-    fn create_bookmark() -> Result<()> {
+    fn run_describe_multi_line() -> Result<()> {
         let test_repo = TestRepo::new()?;
 
-        let bookmark = test_repo.commander.create_bookmark("test")?;
-        let bookmarks = test_repo.commander.get_bookmarks_list(false)?;
+        let message = "Subject\n\nBody with \"quotes\"\n  and indentation";
+        test_repo.commander.run_describe("@", message)?;
 
-        assert_eq!(
-            bookmarks,
-            [Bookmark {
-                name: bookmark.name,
-                remote: bookmark.remote,
-                present: bookmark.present,
-                timestamp: bookmarks[0].timestamp,
-            }]
-        );
+        let head = test_repo.commander.get_current_head()?.commit_id;
+        assert_eq!(test_repo.commander.get_commit_description(&head)?, message);
 
         Ok(())
     }
 
     #[test]
-    fn create_bookmark_commit() -> Result<()> {
-        let test_repo = TestRepo::new()?;
+    fn run_squash() -> Result<()> {
+        let mut test_repo = TestRepo::new()?;
 
-        // Create new change, since by default `jj bookmark create` uses current change
+        let parent = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new(parent.commit_id.as_str())?;
+        std::fs::write(test_repo.directory.path().join("README"), b"AAA")?;
         let head = test_repo.commander.get_current_head()?;
-        test_repo.commander.run_new(head.commit_id.as_str())?;
-        assert_ne!(head, test_repo.commander.get_current_head()?);
 
-        let bookmark = test_repo
+        test_repo
             .commander
-            .create_bookmark_commit("test", &head.commit_id)?;
+            .run_squash(parent.change_id.as_str(), false, true)?;
+        assert_eq!(
+            test_repo.commander.get_current_head()?.change_id,
+            head.change_id
+        );
 
-        let log = test_repo.commander.execute_jj_command(
-            [
-                "log",
-                "--limit",
-                "1",
-                "--no-graph",
-                "-T",
-                "commit_id",
-                "-r",
-                &bookmark.name,
-            ],
-            false,
+        test_repo.commander.run_new("@")?;
+        std::fs::write(test_repo.directory.path().join("README"), b"BBB")?;
+        let head = test_repo.commander.get_current_head()?;
+
+        test_repo
+            .commander
+            .run_squash(parent.change_id.as_str(), false, false)?;
+        assert_ne!(
+            test_repo.commander.get_current_head()?.change_id,
+            head.change_id
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn run_duplicate() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        let base = test_repo.commander.get_current_head()?;
+        test_repo
+            .commander
+            .run_describe(base.commit_id.as_str(), "base")?;
+        let base = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new(base.commit_id.as_str())?;
+        let destination = test_repo.commander.get_current_head()?;
+
+        let duplicate = test_repo.commander.run_duplicate(&base.commit_id, &[])?;
+        assert_ne!(duplicate.change_id, base.change_id);
+        assert_eq!(
+            test_repo
+                .commander
+                .get_commit_description(&duplicate.commit_id)?,
+            "base"
+        );
+        assert_eq!(
+            test_repo
+                .commander
+                .get_commit_parents(&duplicate.commit_id)?,
+            test_repo.commander.get_commit_parents(&base.commit_id)?
+        );
+
+        let duplicate = test_repo
+            .commander
+            .run_duplicate(&base.commit_id, &[destination.commit_id.clone()])?;
+        assert_eq!(
+            test_repo
+                .commander
+                .get_commit_parents(&duplicate.commit_id)?,
+            vec![destination.commit_id]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn run_backout() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        std::fs::write(test_repo.directory.path().join("README"), b"AAA")?;
+        test_repo.commander.run_describe("@", "add readme")?;
+        let base = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new(base.commit_id.as_str())?;
+        let current = test_repo.commander.get_current_head()?;
+
+        let backout = test_repo.commander.run_backout(&base.commit_id, "@-")?;
+        assert_ne!(backout.change_id, current.change_id);
+        assert_eq!(
+            test_repo.commander.get_commit_parents(&backout.commit_id)?,
+            vec![base.commit_id]
+        );
+        assert!(
+            test_repo
+                .commander
+                .get_commit_description(&backout.commit_id)?
+                .starts_with("Revert")
+        );
+        assert!(
+            test_repo
+                .commander
+                .get_conflicts(&backout.commit_id)?
+                .is_empty()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn run_new_merge() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        let base = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new(base.commit_id.as_str())?;
+        test_repo.commander.run_describe("@", "first")?;
+        let first = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new(base.commit_id.as_str())?;
+        test_repo.commander.run_describe("@", "second")?;
+        let second = test_repo.commander.get_current_head()?;
+
+        assert!(test_repo.commander.run_new_merge(&[]).is_err());
+
+        test_repo
+            .commander
+            .run_new_merge(&[first.commit_id.clone(), second.commit_id.clone()])?;
+        let merge = test_repo.commander.get_current_head()?;
+        assert_eq!(
+            test_repo.commander.get_commit_parents(&merge.commit_id)?,
+            [first.commit_id, second.commit_id]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn run_parallelize() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        let base = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new(base.commit_id.as_str())?;
+        test_repo.commander.run_describe("@", "first")?;
+        let first = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new(first.commit_id.as_str())?;
+        test_repo.commander.run_describe("@", "second")?;
+        let second = test_repo.commander.get_current_head()?;
+
+        assert!(
+            test_repo
+                .commander
+                .run_parallelize(std::slice::from_ref(&first.commit_id))
+                .is_err()
+        );
+
+        test_repo
+            .commander
+            .run_parallelize(&[first.commit_id.clone(), second.commit_id.clone()])?;
+        let second = test_repo.commander.get_head_latest(&second)?;
+        assert_eq!(
+            test_repo.commander.get_commit_parents(&second.commit_id)?,
+            [base.commit_id]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn run_diffedit() -> Result<()> {
+        let mut test_repo = TestRepo::new()?;
+
+        std::fs::write(test_repo.directory.path().join("README"), b"AAA")?;
+        let head = test_repo.commander.get_current_head()?;
+
+        // A diff editor leaving the diff as it is keeps the change
+        let jj_config_toml = test_repo.commander.jj_config_toml.clone().unwrap();
+        test_repo.commander.jj_config_toml = Some(
+            [
+                jj_config_toml.clone(),
+                vec![r#"ui.diff-editor=["true", "$left", "$right"]"#.to_owned()],
+            ]
+            .concat(),
+        );
+        test_repo.commander.run_diffedit(&head.commit_id, false)?;
+        let head = test_repo.commander.get_head_latest(&head)?;
+        assert_eq!(test_repo.commander.get_files(&head)?.len(), 1);
+
+        // jj's error is returned when the diff editor fails
+        test_repo.commander.jj_config_toml = Some(
+            [
+                jj_config_toml,
+                vec![r#"ui.diff-editor=["false", "$left", "$right"]"#.to_owned()],
+            ]
+            .concat(),
+        );
+        assert!(
+            test_repo
+                .commander
+                .run_diffedit(&head.commit_id, false)
+                .is_err()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn run_squash_paths() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        test_repo.commander.run_new("@")?;
+        std::fs::write(test_repo.directory.path().join("README"), b"AAA")?;
+        std::fs::write(test_repo.directory.path().join("LICENSE"), b"BBB")?;
+        let head = test_repo.commander.get_current_head()?;
+
+        test_repo
+            .commander
+            .run_squash_paths(&head.commit_id, "@-", &["README"])?;
+
+        let head = test_repo.commander.get_current_head()?;
+        let files = test_repo.commander.get_files(&head)?;
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].target_path(), Some("LICENSE"));
+        let parent = test_repo.commander.get_commit_parent(&head.commit_id)?;
+        let files = test_repo.commander.get_files(&parent)?;
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].target_path(), Some("README"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn run_split_paths() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        std::fs::write(test_repo.directory.path().join("README"), b"AAA")?;
+        std::fs::write(test_repo.directory.path().join("LICENSE"), b"BBB")?;
+        let head = test_repo.commander.get_current_head()?;
+
+        test_repo
+            .commander
+            .run_split_paths(&head.commit_id, &["LICENSE"], "split out")?;
+
+        let [(parent, description)] = test_repo
+            .commander
+            .get_revset_commits("@-")?
+            .try_into()
+            .unwrap();
+        assert_eq!(description, "split out");
+        let parent_files = test_repo.commander.get_files(&parent)?;
+        assert_eq!(parent_files.len(), 1);
+        assert_eq!(parent_files[0].path.as_deref(), Some("LICENSE"));
+
+        let head = test_repo.commander.get_current_head()?;
+        let head_files = test_repo.commander.get_files(&head)?;
+        assert_eq!(head_files.len(), 1);
+        assert_eq!(head_files[0].path.as_deref(), Some("README"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn run_split() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        std::fs::write(test_repo.directory.path().join("README"), b"AAA")?;
+        std::fs::write(test_repo.directory.path().join("LICENSE"), b"BBB")?;
+        let head = test_repo.commander.get_current_head()?;
+        test_repo
+            .commander
+            .run_describe(head.commit_id.as_str(), "both files")?;
+        let head = test_repo.commander.get_current_head()?;
+
+        test_repo
+            .commander
+            .run_split(&head.commit_id, &["LICENSE"])?;
+
+        let [(parent, description)] = test_repo
+            .commander
+            .get_revset_commits("@-")?
+            .try_into()
+            .unwrap();
+        assert_eq!(description, "both files");
+        let parent_files = test_repo.commander.get_files(&parent)?;
+        assert_eq!(parent_files.len(), 1);
+        assert_eq!(parent_files[0].path.as_deref(), Some("LICENSE"));
+
+        let head = test_repo.commander.get_current_head()?;
+        assert_eq!(
+            test_repo
+                .commander
+                .get_commit_description(&head.commit_id)?,
+            "both files"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn run_absorb() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        std::fs::write(test_repo.directory.path().join("README"), b"AAA\n")?;
+        test_repo.commander.run_new("@")?;
+        std::fs::write(test_repo.directory.path().join("README"), b"BBB\n")?;
+
+        test_repo.commander.run_absorb(None, None)?;
+
+        let head = test_repo.commander.get_current_head()?;
+        assert!(test_repo.commander.get_files(&head)?.is_empty());
+        let parent = test_repo.commander.get_commit_parent(&head.commit_id)?;
+        assert_eq!(test_repo.commander.get_files(&parent)?.len(), 1);
+
+        Ok(())
+    }
+
+    #[test]
+    fn run_fix() -> Result<()> {
+        let mut test_repo = TestRepo::new()?;
+
+        // Fails without fix tools
+        assert!(test_repo.commander.run_fix(Some("@")).is_err());
+
+        if let Some(jj_config_toml) = test_repo.commander.jj_config_toml.as_mut() {
+            jj_config_toml.push(
+                r#"fix.tools.upper={ command = ["tr", "a-z", "A-Z"], patterns = ["glob:README"] }"#
+                    .to_owned(),
+            );
+        }
+        std::fs::write(test_repo.directory.path().join("README"), b"aaa\n")?;
+        test_repo.commander.run_fix(Some("@"))?;
+        assert_eq!(
+            std::fs::read_to_string(test_repo.directory.path().join("README"))?,
+            "AAA\n"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn run_rebase() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        let base = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new(base.commit_id.as_str())?;
+        test_repo.commander.run_describe("@", "first")?;
+        let first = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new(base.commit_id.as_str())?;
+        test_repo.commander.run_describe("@", "second")?;
+        let second = test_repo.commander.get_current_head()?;
+
+        test_repo
+            .commander
+            .run_rebase(first.change_id.as_str(), &second.commit_id)?;
+
+        let first = test_repo.commander.get_head_latest(&first)?;
+        assert_eq!(
+            test_repo.commander.get_commit_parents(&first.commit_id)?,
+            [second.commit_id]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn run_rebase_mode_branch() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        let base = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new(base.commit_id.as_str())?;
+        test_repo.commander.run_describe("@", "first")?;
+        let first = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new(first.commit_id.as_str())?;
+        test_repo.commander.run_describe("@", "first child")?;
+        let first_child = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new(base.commit_id.as_str())?;
+        test_repo.commander.run_describe("@", "second")?;
+        let second = test_repo.commander.get_current_head()?;
+
+        let moved =
+            RebaseMode::Branch.moved_revset(first_child.change_id.as_str(), &second.commit_id);
+        assert_eq!(test_repo.commander.get_revset_commits(&moved)?.len(), 2);
+
+        test_repo.commander.run_rebase_mode(
+            RebaseMode::Branch,
+            first_child.change_id.as_str(),
+            &second.commit_id,
+        )?;
+
+        let first = test_repo.commander.get_head_latest(&first)?;
+        assert_eq!(
+            test_repo.commander.get_commit_parents(&first.commit_id)?,
+            [second.commit_id]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn run_rebase_source() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        let base = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new(base.commit_id.as_str())?;
+        test_repo.commander.run_describe("@", "first")?;
+        let first = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new(first.commit_id.as_str())?;
+        test_repo.commander.run_describe("@", "first child")?;
+        let first_child = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new(base.commit_id.as_str())?;
+        test_repo.commander.run_describe("@", "second")?;
+        let second = test_repo.commander.get_current_head()?;
+
+        test_repo
+            .commander
+            .run_rebase_source(&first.commit_id, &second.commit_id)?;
+
+        let first = test_repo.commander.get_head_latest(&first)?;
+        let first_child = test_repo.commander.get_head_latest(&first_child)?;
+        assert_eq!(
+            test_repo.commander.get_commit_parents(&first.commit_id)?,
+            [second.commit_id]
+        );
+        assert_eq!(
+            test_repo
+                .commander
+                .get_commit_parents(&first_child.commit_id)?,
+            [first.commit_id]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn run_squash_into_parent() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        test_repo.commander.run_describe("@", "parent")?;
+        let parent = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new(parent.commit_id.as_str())?;
+        std::fs::write(test_repo.directory.path().join("README"), b"AAA")?;
+        let head = test_repo.commander.get_current_head()?;
+
+        test_repo
+            .commander
+            .run_squash_into_parent(&head.commit_id)?;
+
+        let parent = test_repo.commander.get_head_latest(&parent)?;
+        assert_eq!(
+            test_repo
+                .commander
+                .get_commit_description(&parent.commit_id)?,
+            "parent"
+        );
+        assert_eq!(
+            test_repo
+                .commander
+                .get_commit_diff_stat(&parent.commit_id, None, None, false)?
+                .files,
+            1
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn run_restore() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        let head = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new(head.commit_id.as_str())?;
+        std::fs::write(test_repo.directory.path().join("README"), b"AAA")?;
+
+        test_repo.commander.run_restore(&head.commit_id, &[])?;
+        assert_eq!(
+            test_repo
+                .commander
+                .command_history
+                .lock()
+                .unwrap()
+                .last()
+                .unwrap()
+                .args
+                .first()
+                .unwrap(),
+            "restore"
+        );
+        assert!(!test_repo.directory.path().join("README").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn run_restore_changes() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        std::fs::write(test_repo.directory.path().join("README"), b"AAA")?;
+        std::fs::write(test_repo.directory.path().join("LICENSE"), b"BBB")?;
+        let head = test_repo.commander.get_current_head()?;
+
+        test_repo
+            .commander
+            .run_restore_changes(&head.commit_id, &["LICENSE"])?;
+
+        let head = test_repo.commander.get_current_head()?;
+        let files = test_repo.commander.get_files(&head)?;
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0].path.as_deref(), Some("README"));
+        assert!(!test_repo.directory.path().join("LICENSE").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn create_bookmark() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        let bookmark = test_repo.commander.create_bookmark("test")?;
+        let bookmarks = test_repo.commander.get_bookmarks_list(false)?;
+
+        assert_eq!(
+            bookmarks,
+            [Bookmark {
+                name: bookmark.name,
+                remote: bookmark.remote,
+                present: bookmark.present,
+                timestamp: bookmarks[0].timestamp,
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn create_bookmark_commit() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        // Create new change, since by default `jj bookmark create` uses current change
+        let head = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new(head.commit_id.as_str())?;
+        assert_ne!(head, test_repo.commander.get_current_head()?);
+
+        let bookmark = test_repo
+            .commander
+            .create_bookmark_commit("test", &head.commit_id)?;
+
+        let log = test_repo.commander.execute_jj_command(
+            [
+                "log",
+                "--limit",
+                "1",
+                "--no-graph",
+                "-T",
+                "commit_id",
+                "-r",
+                &bookmark.name,
+            ],
+            false,
             true,
         )?;
 
@@ -467,4 +1544,56 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn parse_push_dry_run_output() {
+        let output = "Changes to push to origin:
+  Add bookmark feature to 0123abcd
+  Move forward bookmark main from 0123abcd to 4567cdef
+  Move sideways bookmark fix from 0123abcd to 89abcdef
+  Delete bookmark old from 0123abcd
+Dry-run requested, not pushing.
+";
+        assert_eq!(
+            parse_push_dry_run(output),
+            PushSummary {
+                new: vec!["feature".to_owned()],
+                updated: vec!["main".to_owned(), "fix".to_owned()],
+                deleted: vec!["old".to_owned()],
+            }
+        );
+        assert_eq!(
+            parse_push_dry_run("Nothing changed.\n"),
+            PushSummary::default()
+        );
+    }
+
+    #[test]
+    fn git_push_dry_run() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+        let remote_repo = TestRepo::new()?;
+        test_repo
+            .commander
+            .add_git_remote("origin", &remote_repo.directory.path().to_string_lossy())?;
+
+        test_repo
+            .commander
+            .execute_void_jj_command(vec!["describe", "-m", "test"])?;
+        test_repo.commander.create_bookmark("test")?;
+        let head = test_repo.commander.get_current_head()?;
+
+        assert_eq!(
+            test_repo
+                .commander
+                .git_push_dry_run(false, true, &head.commit_id)?,
+            PushSummary {
+                new: vec!["test".to_owned()],
+                ..Default::default()
+            }
+        );
+        // Nothing was pushed
+        assert_eq!(remote_repo.commander.get_bookmarks_list(true)?, []);
+
+        Ok(())
+    }
 }
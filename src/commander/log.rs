@@ -11,13 +11,17 @@ use crate::{
         bookmarks::Bookmark,
         ids::{ChangeId, CommitId},
     },
-    env::DiffFormat,
+    env::{DiffFormat, PrimaryId},
 };
 
 use anyhow::{Context, Result, anyhow, bail};
 use itertools::Itertools;
 use regex::Regex;
-use std::{fmt::Display, sync::LazyLock};
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    sync::LazyLock,
+};
 use thiserror::Error;
 use tracing::instrument;
 
@@ -35,11 +39,67 @@ pub struct LogOutput {
     // Maps graph line -> heads
     pub graph_heads: Vec<Option<Head>>,
     pub heads: Vec<Head>,
+    /// Commits with conflicted files, from the `conflict` template keyword
+    pub conflicted: HashSet<CommitId>,
+    /// The working copy commit, if the log shows it
+    pub working_copy: Option<CommitId>,
+    /// Commits with tags, from the `tags` template keyword
+    pub tagged: HashSet<CommitId>,
+}
+
+/// A commit with conflicted files, as listed by the `conflicts()` revset
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct ConflictedCommit {
+    pub head: Head,
+    /// First line of the description
+    pub description: String,
+    /// Number of conflicted files
+    pub conflicts: usize,
+}
+
+/// A commit whose change id or commit id starts with a prefix
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct PrefixMatch {
+    pub head: Head,
+    /// First line of the description
+    pub description: String,
 }
 
 #[derive(Error, Debug)]
 pub struct HeadParseError(String);
 
+/// The workspace has no usable working copy, so @ can't be resolved
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum WorkingCopyError {
+    #[error(
+        "The working copy is stale, it was changed by another process or workspace.\nRecover it with `jj workspace update-stale`, then restart lazyjj."
+    )]
+    Stale,
+    #[error(
+        "This workspace has no working-copy commit.\nCreate one with `jj new <revision>` or `jj edit <revision>`, then restart lazyjj."
+    )]
+    Missing,
+}
+
+impl WorkingCopyError {
+    /// Recognize a failed command caused by the working copy state
+    pub fn detect(err: &CommandError) -> Option<Self> {
+        let CommandError::Status(output, _) = err else {
+            return None;
+        };
+        if output.contains("working copy is stale") {
+            Some(WorkingCopyError::Stale)
+        } else if output.contains("doesn't have a working-copy commit")
+            || output.contains("Revision `@` doesn't exist")
+            || output.contains(r#"Revision "@" doesn't exist"#)
+        {
+            Some(WorkingCopyError::Missing)
+        } else {
+            None
+        }
+    }
+}
+
 impl Display for HeadParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         write!(f, "Head parse error: {}", self.0)
@@ -50,10 +110,135 @@ impl Display for HeadParseError {
 // commands which supports templating.
 const HEAD_TEMPLATE: &str =
     r#""[" ++ change_id ++ "|" ++ commit_id ++ "|" ++ divergent ++ "|" ++ immutable ++ "]""#;
+/// Template of each change in the log, unless `lazyjj.log-template` sets another one
+pub const DEFAULT_LOG_TEMPLATE: &str = "builtin_log_compact";
+
+// builtin_log_compact with the commit id first and the change id in its place, used when
+// `lazyjj.primary-id` is `commit`
+const COMMIT_FIRST_LOG_TEMPLATE: &str = r#"
+if(root,
+  format_root_commit(self),
+  label(if(current_working_copy, "working_copy"),
+    concat(
+      separate(" ",
+        format_short_commit_id(commit_id),
+        if(author.email(), author.email().local(), email_placeholder),
+        format_timestamp(commit_timestamp(self)),
+        bookmarks,
+        tags,
+        working_copy,
+        format_short_change_id_with_hidden_and_divergent_info(self),
+        if(conflict, label("conflict", "conflict")),
+      ) ++ "\n",
+      separate(" ",
+        if(empty, label("empty", "(empty)")),
+        if(description,
+          description.first_line(),
+          label(if(empty, "empty"), description_placeholder),
+        ),
+      ) ++ "\n",
+    ),
+  )
+)"#;
+
+// builtin_log_oneline with the commit id first and the change id in its place, used when
+// `lazyjj.primary-id` is `commit`
+const COMMIT_FIRST_LOG_ONELINE_TEMPLATE: &str = r#"
+if(root,
+  format_root_commit(self),
+  label(if(current_working_copy, "working_copy"),
+    concat(
+      separate(" ",
+        format_short_commit_id(commit_id),
+        if(author.email(), author.email().local(), email_placeholder),
+        format_timestamp(commit_timestamp(self)),
+        bookmarks,
+        tags,
+        working_copy,
+        format_short_change_id_with_hidden_and_divergent_info(self),
+        if(conflict, label("conflict", "conflict")),
+        if(empty, label("empty", "(empty)")),
+        if(description,
+          description.first_line(),
+          label(if(empty, "empty"), description_placeholder),
+        ),
+      ) ++ "\n",
+    ),
+  )
+)"#;
+
+// Template which outputs flags of a commit in the log before HEAD_TEMPLATE, `{@!t}` for the
+// tagged working copy commit with conflicts. Read by head_flags
+const HEAD_FLAGS_TEMPLATE: &str =
+    r#""{" ++ if(current_working_copy, "@") ++ if(conflict, "!") ++ if(tags, "t") ++ "}""#;
+
 // Regex to parse HEAD_TEMPLATE
 static HEAD_TEMPLATE_REGEX: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"\[(.*)\|(.*)\|(.*)\|(.*)\]").unwrap());
 
+/// Size of a diff, as summarized by `jj diff --stat`
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub struct DiffStat {
+    pub files: usize,
+    pub insertions: usize,
+    pub deletions: usize,
+}
+
+// Regex to parse the summary line of `jj diff --stat`
+static DIFF_STAT_REGEX: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(\d+) files? changed, (\d+) insertions?\(\+\), (\d+) deletions?\(-\)").unwrap()
+});
+
+/// Signature verification status of a commit
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SignatureStatus {
+    Good,
+    Bad,
+    Unknown,
+    Unsigned,
+}
+
+impl SignatureStatus {
+    /// Parse the output of [signature_template]
+    pub fn parse(text: &str) -> Self {
+        match text.trim() {
+            "good" => SignatureStatus::Good,
+            "bad" | "invalid" => SignatureStatus::Bad,
+            "unsigned" => SignatureStatus::Unsigned,
+            _ => SignatureStatus::Unknown,
+        }
+    }
+}
+
+/// Template which outputs the signature status of `commit`, or `unsigned`.
+/// Parsed by [SignatureStatus::parse].
+pub fn signature_template(commit: &str) -> String {
+    format!(r#"if({commit}.signature(), {commit}.signature().status(), "unsigned")"#)
+}
+
+/// Flags output by HEAD_FLAGS_TEMPLATE, between the graph and the head
+fn head_flags(line: &str) -> &str {
+    line.split_once("}[")
+        .and_then(|(before_head, _)| before_head.rsplit_once('{'))
+        .map_or("", |(_, flags)| flags)
+}
+
+/// Move the line of `jj show` with the primary id first, as jj shows the commit id first
+fn order_show_ids(show: String, primary_id: PrimaryId) -> String {
+    if primary_id == PrimaryId::Commit {
+        return show;
+    }
+    let mut lines: Vec<&str> = show.split_inclusive('\n').collect();
+    if let Some(index) = lines.iter().position(|line| line.contains("Commit ID: "))
+        && lines
+            .get(index + 1)
+            .is_some_and(|line| line.contains("Change ID: "))
+    {
+        lines.swap(index, index + 1);
+    }
+    lines.concat()
+}
+
 // Parse a head with HEAD_TEMPLATE.
 fn parse_head(text: &str) -> Result<Head> {
     let captured = HEAD_TEMPLATE_REGEX.captures(text);
@@ -80,51 +265,108 @@ fn parse_head(text: &str) -> Result<Head> {
 
 impl Commander {
     /// Get log. Returns human readable log and mapping to log line to head.
+    /// In compact mode, each change takes a single line. Without graph, changes are listed
+    /// flat, without the graph lines and elided revisions. With a limit, only the first changes
+    /// are listed.
     /// Maps to `jj log`
     #[instrument(level = "trace", skip(self))]
-    pub fn get_log(&self, revset: &Option<String>) -> Result<LogOutput, CommandError> {
+    pub fn get_log(
+        &self,
+        revset: &Option<String>,
+        compact: bool,
+        graph: bool,
+        limit: Option<usize>,
+    ) -> Result<LogOutput, CommandError> {
         let mut args = vec![];
 
+        if !graph {
+            args.push("--no-graph");
+        }
+
         if let Some(revset) = revset {
             args.push("-r");
             args.push(revset);
         }
 
-        // Force builtin_log_compact which uses 2 lines per change
+        let limit = limit.map(|limit| limit.to_string());
+        if let Some(limit) = limit.as_ref() {
+            args.push("--limit");
+            args.push(limit);
+        }
+
+        // Force builtin_log_compact which uses 2 lines per change,
+        // or builtin_log_oneline which uses 1 line per change.
+        // A custom template can use any number of lines, so output the head once for each of
+        // its lines
+        // Each head is preceded by flags of the commit
+        // Head templates end with a newline, as jj only adds one after each change with the graph
+        // The builtin templates are replaced to show the commit id first when it is the primary id
+        let log_template = self.env.config.log_template();
+        let commit_first = self.env.config.primary_id() == PrimaryId::Commit;
+        let head = format!("{HEAD_FLAGS_TEMPLATE} ++ {HEAD_TEMPLATE}");
+        let (template, head_template) = if compact {
+            (
+                if commit_first {
+                    COMMIT_FIRST_LOG_ONELINE_TEMPLATE
+                } else {
+                    "builtin_log_oneline"
+                },
+                format!(r#"{head} ++ "\n""#),
+            )
+        } else if log_template == DEFAULT_LOG_TEMPLATE {
+            (
+                if commit_first {
+                    COMMIT_FIRST_LOG_TEMPLATE
+                } else {
+                    DEFAULT_LOG_TEMPLATE
+                },
+                format!(r#"{head} ++ " " ++ bookmarks ++"\n" ++ {head} ++ "\n""#),
+            )
+        } else {
+            (
+                log_template,
+                format!(
+                    r#"stringify({log_template}).lines().map(|line| {head}).join("\n") ++ "\n""#
+                ),
+            )
+        };
         let graph = self.execute_jj_command(
-            [
-                vec!["log", "--template", "builtin_log_compact"],
-                args.clone(),
-            ]
-            .concat(),
+            [vec!["log", "--template", template], args.clone()].concat(),
             true,
             true,
         )?;
 
         // Extract the log one more time, but this time use a template
         // where each line begins with Head information. Since jj has
-        // 2 lines per change, there will also be two lines with head info.
+        // 1 or 2 lines per change, there will also be as many lines with head info.
         // The number of lines in graph and the number of items in graph_heads
         // should be identical.
+        let mut conflicted = HashSet::new();
+        let mut working_copy = None;
+        let mut tagged = HashSet::new();
         let graph_heads: Vec<Option<Head>> = self
             .execute_jj_command(
-                [
-                    vec![
-                        "log",
-                        "--template",
-                        // Match builtin_log_compact with 2 lines per change
-                        &format!(
-                            r#"{HEAD_TEMPLATE} ++ " " ++ bookmarks ++"\n" ++ {HEAD_TEMPLATE}"#
-                        ),
-                    ],
-                    args,
-                ]
-                .concat(),
+                [vec!["log", "--template", &head_template], args].concat(),
                 false,
                 true,
             )?
             .lines()
-            .map(|line| parse_head(line).ok())
+            .map(|line| {
+                let head = parse_head(line).ok();
+                if let Some(head) = head.as_ref() {
+                    let flags = head_flags(line);
+                    if flags.contains('!') {
+                        conflicted.insert(head.commit_id.clone());
+                    }
+                    if flags.contains('@') {
+                        working_copy = Some(head.commit_id.clone());
+                    }
+                    if flags.contains('t') {
+                        tagged.insert(head.commit_id.clone());
+                    }
+                }
+                head
+            })
             .collect();
 
         let heads = graph_heads.clone().into_iter().flatten().unique().collect();
@@ -133,6 +375,9 @@ impl Commander {
             graph,
             graph_heads,
             heads,
+            conflicted,
+            working_copy,
+            tagged,
         })
     }
 
@@ -151,9 +396,150 @@ impl Commander {
             args.push("--ignore-working-copy");
         }
 
+        let show = self.execute_jj_command(args, true, true)?.remove_end_line();
+        Ok(order_show_ids(show, self.env.config.primary_id()))
+    }
+
+    /// Get commit details, with the diff optionally taken against a specific parent
+    /// and limited to a path.
+    /// Maps to `jj show --no-patch <commit>` and `jj diff`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_commit_show_with_diff(
+        &self,
+        commit_id: &CommitId,
+        parent_id: Option<&CommitId>,
+        path: Option<&str>,
+        diff_format: &DiffFormat,
+        ignore_working_copy: bool,
+    ) -> Result<String, CommandError> {
+        let show = self.get_commit_metadata(commit_id, ignore_working_copy)?;
+        let diff =
+            self.get_commit_diff(commit_id, parent_id, path, diff_format, ignore_working_copy)?;
+        Ok(format!("{show}\n{diff}"))
+    }
+
+    /// Get commit details without the diff.
+    /// Maps to `jj show --no-patch <commit>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_commit_metadata(
+        &self,
+        commit_id: &CommitId,
+        ignore_working_copy: bool,
+    ) -> Result<String, CommandError> {
+        let mut args = vec!["show", "--no-patch", commit_id.as_str()];
+        if ignore_working_copy {
+            args.push("--ignore-working-copy");
+        }
+
+        let show = self.execute_jj_command(args, true, true)?.remove_end_line();
+        Ok(order_show_ids(show, self.env.config.primary_id()))
+    }
+
+    /// Get the diff of a commit, optionally taken against a specific parent and limited to a path.
+    /// Maps to `jj diff -r <commit> [path]` or `jj diff --from <parent> --to <commit> [path]`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_commit_diff(
+        &self,
+        commit_id: &CommitId,
+        parent_id: Option<&CommitId>,
+        path: Option<&str>,
+        diff_format: &DiffFormat,
+        ignore_working_copy: bool,
+    ) -> Result<String, CommandError> {
+        if let Some(parent_id) = parent_id {
+            return self.get_diff_range(
+                parent_id,
+                commit_id,
+                path,
+                diff_format,
+                ignore_working_copy,
+            );
+        }
+
+        let mut args = vec!["diff", "-r", commit_id.as_str()];
+        args.append(&mut diff_format.get_args());
+        if let Some(path) = path {
+            args.push(path);
+        }
+        if ignore_working_copy {
+            args.push("--ignore-working-copy");
+        }
+
+        Ok(self.execute_jj_command(args, true, true)?.remove_end_line())
+    }
+
+    /// Get the diff between two arbitrary commits, optionally limited to a path.
+    /// Maps to `jj diff --from <from> --to <to> [path]`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_diff_range(
+        &self,
+        from: &CommitId,
+        to: &CommitId,
+        path: Option<&str>,
+        diff_format: &DiffFormat,
+        ignore_working_copy: bool,
+    ) -> Result<String, CommandError> {
+        let mut args = vec!["diff", "--from", from.as_str(), "--to", to.as_str()];
+        args.append(&mut diff_format.get_args());
+        if let Some(path) = path {
+            args.push(path);
+        }
+        if ignore_working_copy {
+            args.push("--ignore-working-copy");
+        }
+
         Ok(self.execute_jj_command(args, true, true)?.remove_end_line())
     }
 
+    /// Get the size of a commit's diff, optionally taken against a specific parent
+    /// and limited to a path.
+    /// Maps to `jj diff --stat -r <commit>` or `jj diff --stat --from <parent> --to <commit>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_commit_diff_stat(
+        &self,
+        commit_id: &CommitId,
+        parent_id: Option<&CommitId>,
+        path: Option<&str>,
+        ignore_working_copy: bool,
+    ) -> Result<DiffStat, CommandError> {
+        let mut args = vec!["diff", "--stat"];
+        match parent_id {
+            Some(parent_id) => args.append(&mut vec![
+                "--from",
+                parent_id.as_str(),
+                "--to",
+                commit_id.as_str(),
+            ]),
+            None => args.append(&mut vec!["-r", commit_id.as_str()]),
+        }
+        if let Some(path) = path {
+            args.push(path);
+        }
+        if ignore_working_copy {
+            args.push("--ignore-working-copy");
+        }
+
+        let output = self.execute_jj_command(args, false, true)?;
+        Ok(output
+            .lines()
+            .rev()
+            .find_map(|line| DIFF_STAT_REGEX.captures(line))
+            .map(|captured| {
+                let number = |index| {
+                    captured
+                        .get(index)
+                        .and_then(|number| number.as_str().parse().ok())
+                        .unwrap_or(0)
+                };
+                DiffStat {
+                    files: number(1),
+                    insertions: number(2),
+                    deletions: number(3),
+                }
+            })
+            .unwrap_or_default())
+    }
+
     /// Get the current head.
     /// Maps to `jj log -r @`
     #[instrument(level = "trace", skip(self))]
@@ -174,11 +560,110 @@ impl Commander {
                     false,
                     true,
                 )
-                .context("Failed getting current head")?
+                .map_err(|err| match WorkingCopyError::detect(&err) {
+                    Some(working_copy_error) => anyhow!(working_copy_error),
+                    None => anyhow!(err).context("Failed getting current head"),
+                })?
+                .remove_end_line(),
+        )
+    }
+
+    /// Get the head of a single revision.
+    /// Maps to `jj log -r <revision>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_revision_head(&self, revision: &str) -> Result<Head> {
+        parse_head(
+            &self
+                .execute_jj_command(
+                    vec![
+                        "log",
+                        "--no-graph",
+                        "--template",
+                        &format!(r#"{HEAD_TEMPLATE} ++ "\n""#),
+                        "-r",
+                        revision,
+                        "--limit",
+                        "1",
+                    ],
+                    false,
+                    true,
+                )
+                .with_context(|| format!("Failed getting revision: {revision}"))?
                 .remove_end_line(),
         )
     }
 
+    /// Find the commits whose change id or commit id starts with `prefix`. An ambiguous prefix
+    /// returns every matching commit, an unknown one none.
+    /// Maps to `jj log -r 'change_id(<prefix>)'` or `jj log -r 'commit_id(<prefix>)'`
+    #[instrument(level = "trace", skip(self))]
+    pub fn find_by_id_prefix(&self, prefix: &str) -> Result<Vec<PrefixMatch>> {
+        let prefix = prefix.trim().to_lowercase();
+        // Change ids use the letters k-z, commit ids are hexadecimal
+        let id = if prefix.is_empty() {
+            return Ok(vec![]);
+        } else if prefix.chars().all(|c| ('k'..='z').contains(&c)) {
+            "change_id"
+        } else if prefix.chars().all(|c| c.is_ascii_hexdigit()) {
+            "commit_id"
+        } else {
+            return Ok(vec![]);
+        };
+
+        let template = format!(r#"{HEAD_TEMPLATE} ++ "\t" ++ description.first_line() ++ "\n""#);
+        let output = match self.execute_jj_command(
+            vec![
+                "log",
+                "--no-graph",
+                "--template",
+                &template,
+                "-r",
+                &format!("{id}({prefix})"),
+            ],
+            false,
+            true,
+        ) {
+            Ok(output) => output,
+            // jj refuses ambiguous prefixes, so look through all commits for the candidates
+            Err(_) => self
+                .execute_jj_command(
+                    vec![
+                        "log",
+                        "--no-graph",
+                        "--template",
+                        &format!(r#"if(stringify({id}).starts_with("{prefix}"), {template})"#),
+                        "-r",
+                        "all()",
+                    ],
+                    false,
+                    true,
+                )
+                .with_context(|| format!("Failed finding id prefix: {prefix}"))?,
+        };
+
+        output
+            .lines()
+            .map(|line| {
+                let (head, description) = line.split_once('\t').unwrap_or((line, ""));
+                Ok(PrefixMatch {
+                    head: parse_head(head)?,
+                    description: description.to_owned(),
+                })
+            })
+            .collect()
+    }
+
+    /// Get the revset the log shows by default.
+    /// Maps to `jj config get revsets.log`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_default_log_revset(&self) -> Result<String> {
+        Ok(self
+            .execute_jj_command(vec!["config", "get", "revsets.log"], false, true)
+            .context("Failed getting default log revset")?
+            .trim()
+            .to_owned())
+    }
+
     /// Get the latest version of a head. Can detect evolution of divergent head.
     #[instrument(level = "trace", skip(self))]
     pub fn get_head_latest(&self, head: &Head) -> Result<Head> {
@@ -270,17 +755,80 @@ impl Commander {
         )
     }
 
-    /// Get commit's description.
-    /// Maps to `jj log -r <revision> -T description`
+    /// Get the signature status of a commit.
+    /// Maps to `jj log -r <revision> -T 'signature.status()'`
     #[instrument(level = "trace", skip(self))]
-    pub fn get_commit_description(&self, commit_id: &CommitId) -> Result<String> {
+    pub fn get_commit_signature(&self, commit_id: &CommitId) -> Result<SignatureStatus> {
+        Ok(SignatureStatus::parse(
+            &self
+                .execute_jj_command(
+                    vec![
+                        "log",
+                        "--no-graph",
+                        "--template",
+                        &signature_template("self"),
+                        "-r",
+                        commit_id.as_str(),
+                        "--limit",
+                        "1",
+                    ],
+                    false,
+                    true,
+                )
+                .with_context(|| format!("Failed getting commit signature: {commit_id}"))?,
+        ))
+    }
+
+    /// Get the signature status of the signed commits in a revset, optionally only among its
+    /// first commits like [Commander::get_log]. Unsigned commits are left out.
+    /// Maps to `jj log -r <revset> -T 'signature.status()'`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_signed_commits(
+        &self,
+        revset: &Option<String>,
+        limit: Option<usize>,
+    ) -> Result<HashMap<CommitId, SignatureStatus>> {
+        let template = format!(
+            r#"if(self.signature(), commit_id ++ "|" ++ {} ++ "\n")"#,
+            signature_template("self")
+        );
+        let mut args = vec!["log", "--no-graph", "--template", &template];
+        if let Some(revset) = revset {
+            args.push("-r");
+            args.push(revset);
+        }
+        let limit = limit.map(|limit| limit.to_string());
+        if let Some(limit) = limit.as_ref() {
+            args.push("--limit");
+            args.push(limit);
+        }
+
+        Ok(self
+            .execute_jj_command(args, false, true)
+            .context("Failed getting commit signatures")?
+            .lines()
+            .filter_map(|line| line.split_once('|'))
+            .map(|(commit_id, signature)| {
+                (
+                    CommitId(commit_id.to_owned()),
+                    SignatureStatus::parse(signature),
+                )
+            })
+            .filter(|(_, signature)| *signature != SignatureStatus::Unsigned)
+            .collect())
+    }
+
+    /// Get all parents of a commit, in order.
+    /// Maps to `jj log -r <revision> -T parents`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_commit_parents(&self, commit_id: &CommitId) -> Result<Vec<CommitId>> {
         Ok(self
             .execute_jj_command(
                 vec![
                     "log",
                     "--no-graph",
                     "--template",
-                    "description",
+                    r#"parents.map(|parent| parent.commit_id() ++ "\n").join("")"#,
                     "-r",
                     commit_id.as_str(),
                     "--limit",
@@ -289,63 +837,261 @@ impl Commander {
                 false,
                 true,
             )
-            .with_context(|| format!("Failed getting commit description: {commit_id}"))?
-            .remove_end_line())
+            .with_context(|| format!("Failed getting commit parents: {commit_id}"))?
+            .lines()
+            .map(|line| CommitId(line.to_owned()))
+            .collect())
     }
 
-    /// Check if a revision is immutable
-    /// Maps to `jj log -r <revision> -T immutable`
+    /// Check if a commit is an ancestor of another one, or the same commit.
+    /// Maps to `jj log -r '<ancestor> & ::<descendant>'`
     #[instrument(level = "trace", skip(self))]
-    pub fn check_revision_immutable(&self, revision: &str) -> Result<bool> {
-        Ok(self
+    pub fn is_ancestor(&self, ancestor: &CommitId, descendant: &CommitId) -> Result<bool> {
+        Ok(!self
             .execute_jj_command(
                 vec![
                     "log",
                     "--no-graph",
                     "--template",
-                    "immutable",
+                    "commit_id",
                     "-r",
-                    revision,
-                    "--limit",
-                    "1",
+                    &format!("{ancestor} & ::{descendant}"),
                 ],
                 false,
                 true,
             )
-            .with_context(|| format!("Failed checking if revision is immutable: {revision}"))?
-            .remove_end_line()
-            == "true")
+            .with_context(|| format!("Failed checking ancestry: {ancestor} {descendant}"))?
+            .is_empty())
     }
 
-    /// Get bookmark head
-    /// Maps to `jj log -r <bookmark>[@<remote>]`
+    /// Check if a commit is in a revset.
+    /// Maps to `jj log -r '<commit> & (<revset>)'`
     #[instrument(level = "trace", skip(self))]
-    pub fn get_bookmark_head(&self, bookmark: &Bookmark) -> Result<Head> {
-        parse_head(
-            &self
-                .execute_jj_command(
-                    vec![
-                        "log",
-                        "--no-graph",
-                        "--template",
-                        &format!(r#"{HEAD_TEMPLATE} ++ "\n""#),
-                        "-r",
-                        &bookmark.to_string(),
-                        "--limit",
-                        "1",
-                    ],
-                    false,
-                    true,
-                )
-                .context("Failed getting bookmark head")?
-                .remove_end_line(),
+    pub fn is_in_revset(&self, commit_id: &CommitId, revset: &str) -> Result<bool> {
+        Ok(!self
+            .execute_jj_command(
+                vec![
+                    "log",
+                    "--no-graph",
+                    "--template",
+                    "commit_id",
+                    "-r",
+                    &format!("{commit_id} & ({revset})"),
+                ],
+                false,
+                true,
+            )
+            .with_context(|| format!("Failed checking revset: {revset}"))?
+            .is_empty())
+    }
+
+    /// Get the commits in a revset along with the first line of their description.
+    /// Maps to `jj log -r <revset>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_revset_commits(&self, revset: &str) -> Result<Vec<(Head, String)>> {
+        self.execute_jj_command(
+            vec![
+                "log",
+                "--no-graph",
+                "--template",
+                &format!(r#"{HEAD_TEMPLATE} ++ "\t" ++ description.first_line() ++ "\n""#),
+                "-r",
+                revset,
+            ],
+            false,
+            true,
         )
+        .with_context(|| format!("Failed getting commits of revset: {revset}"))?
+        .lines()
+        .map(|line| {
+            let (head, description) = line.split_once('\t').unwrap_or((line, ""));
+            Ok((parse_head(head)?, description.to_owned()))
+        })
+        .collect()
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use std::fs;
+    /// Get mutable commits without a description in a revset, oldest first.
+    /// An empty working copy commit is left out.
+    /// Maps to `jj log -r 'description(exact:"") & mutable() & <revset>'`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_undescribed_heads(&self, revset: &Option<String>) -> Result<Vec<Head>> {
+        let mut undescribed = r#"description(exact:"") & mutable() ~ (@ & empty())"#.to_owned();
+        if let Some(revset) = revset {
+            undescribed = format!("({undescribed}) & ({revset})");
+        }
+
+        self.execute_jj_command(
+            vec![
+                "log",
+                "--no-graph",
+                "--reversed",
+                "--template",
+                &format!(r#"{HEAD_TEMPLATE} ++ "\n""#),
+                "-r",
+                &undescribed,
+            ],
+            false,
+            true,
+        )
+        .context("Failed getting undescribed commits")?
+        .lines()
+        .map(parse_head)
+        .collect()
+    }
+
+    /// Get all commits with conflicts, along with their number of conflicted files.
+    /// Maps to `jj log -r 'conflicts()'` and `jj resolve --list -r <revision>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_conflicted_commits(&self) -> Result<Vec<ConflictedCommit>> {
+        self.execute_jj_command(
+            vec![
+                "log",
+                "--no-graph",
+                "--template",
+                &format!(r#"{HEAD_TEMPLATE} ++ "\t" ++ description.first_line() ++ "\n""#),
+                "-r",
+                "conflicts()",
+            ],
+            false,
+            true,
+        )
+        .context("Failed getting conflicted commits")?
+        .lines()
+        .map(|line| {
+            let (head, description) = line.split_once('\t').unwrap_or((line, ""));
+            let head = parse_head(head)?;
+            let conflicts = self
+                .execute_jj_command(
+                    vec!["resolve", "--list", "-r", head.commit_id.as_str()],
+                    false,
+                    true,
+                )
+                .map(|output| output.lines().count())
+                .unwrap_or(0);
+            Ok(ConflictedCommit {
+                head,
+                description: description.to_owned(),
+                conflicts,
+            })
+        })
+        .collect()
+    }
+
+    /// Get commit's description.
+    /// Maps to `jj log -r <revision> -T description`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_commit_description(&self, commit_id: &CommitId) -> Result<String> {
+        Ok(self
+            .execute_jj_command(
+                vec![
+                    "log",
+                    "--no-graph",
+                    "--template",
+                    "description",
+                    "-r",
+                    commit_id.as_str(),
+                    "--limit",
+                    "1",
+                ],
+                false,
+                true,
+            )
+            .with_context(|| format!("Failed getting commit description: {commit_id}"))?
+            .remove_end_line())
+    }
+
+    /// Check if a revision is immutable
+    /// Maps to `jj log -r <revision> -T immutable`
+    #[instrument(level = "trace", skip(self))]
+    pub fn check_revision_immutable(&self, revision: &str) -> Result<bool> {
+        Ok(self
+            .execute_jj_command(
+                vec![
+                    "log",
+                    "--no-graph",
+                    "--template",
+                    "immutable",
+                    "-r",
+                    revision,
+                    "--limit",
+                    "1",
+                ],
+                false,
+                true,
+            )
+            .with_context(|| format!("Failed checking if revision is immutable: {revision}"))?
+            .remove_end_line()
+            == "true")
+    }
+
+    /// Get how many commits @ is ahead of and behind `trunk()`.
+    /// Returns `None` if there is no trunk, i.e. `trunk()` resolves to the root commit.
+    /// Maps to `jj log -r 'trunk()..@'` and `jj log -r '@..trunk()'`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_trunk_ahead_behind(&self) -> Result<Option<(usize, usize)>> {
+        let Ok(trunk) = self.execute_jj_command(
+            vec![
+                "log",
+                "--no-graph",
+                "--template",
+                "commit_id",
+                "-r",
+                "trunk()",
+                "--limit",
+                "1",
+            ],
+            false,
+            true,
+        ) else {
+            return Ok(None);
+        };
+        if trunk.trim().chars().all(|c| c == '0') {
+            return Ok(None);
+        }
+
+        let count = |revset: &str| -> Result<usize> {
+            Ok(self
+                .execute_jj_command(
+                    vec!["log", "--no-graph", "--template", r#""x""#, "-r", revset],
+                    false,
+                    true,
+                )
+                .with_context(|| format!("Failed counting commits in {revset}"))?
+                .len())
+        };
+
+        Ok(Some((count("trunk()..@")?, count("@..trunk()")?)))
+    }
+
+    /// Get bookmark head
+    /// Maps to `jj log -r <bookmark>[@<remote>]`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_bookmark_head(&self, bookmark: &Bookmark) -> Result<Head> {
+        parse_head(
+            &self
+                .execute_jj_command(
+                    vec![
+                        "log",
+                        "--no-graph",
+                        "--template",
+                        &format!(r#"{HEAD_TEMPLATE} ++ "\n""#),
+                        "-r",
+                        &bookmark.to_string(),
+                        "--limit",
+                        "1",
+                    ],
+                    false,
+                    true,
+                )
+                .context("Failed getting bookmark head")?
+                .remove_end_line(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{fs, process::Command};
 
     use super::*;
     use crate::commander::tests::TestRepo;
@@ -355,7 +1101,7 @@ mod tests {
     fn get_log() -> Result<()> {
         let test_repo = TestRepo::new()?;
 
-        let log = test_repo.commander.get_log(&None)?;
+        let log = test_repo.commander.get_log(&None, false, true, None)?;
 
         let mut settings = insta::Settings::clone_current();
         settings.add_filter(r"[k-z]{8} .*? [0-9a-fA-F]{8}", "[LINE]");
@@ -372,6 +1118,106 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn get_log_compact() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        let log = test_repo.commander.get_log(&None, true, true, None)?;
+
+        assert_eq!(log.graph.lines().count(), log.graph_heads.len());
+        assert_eq!(log.heads.len(), 2);
+        assert!(log.graph_heads.iter().all(Option::is_some));
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_log_no_graph() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        let log = test_repo.commander.get_log(&None, false, false, None)?;
+
+        assert_eq!(log.graph.lines().count(), log.graph_heads.len());
+        assert_eq!(log.heads.len(), 2);
+        assert!(log.graph_heads.iter().all(Option::is_some));
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_log_compact_no_graph() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        let log = test_repo.commander.get_log(&None, true, false, None)?;
+
+        assert_eq!(log.graph.lines().count(), log.graph_heads.len());
+        assert_eq!(log.heads.len(), 2);
+        assert!(log.graph_heads.iter().all(Option::is_some));
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_log_limit() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        let log = test_repo.commander.get_log(&None, true, true, Some(1))?;
+
+        assert_eq!(log.graph.lines().count(), log.graph_heads.len());
+        // Only @, not the root commit
+        assert_eq!(log.heads, [test_repo.commander.get_current_head()?]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_log_custom_template() -> Result<()> {
+        let mut test_repo = TestRepo::new()?;
+        test_repo.commander.env.config = toml::from_str(
+            r#""lazyjj.log-template" = 'change_id.short() ++ "\n" ++ author.email() ++ "\n" ++ description ++ "\n"'"#,
+        )?;
+
+        let log = test_repo.commander.get_log(&None, false, false, None)?;
+
+        assert_eq!(log.graph.lines().count(), log.graph_heads.len());
+        assert_eq!(log.graph_heads.len(), 6);
+        assert_eq!(log.heads.len(), 2);
+        assert!(log.graph.contains("lazyjj@example.com"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_log_flags() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        fs::write(test_repo.directory.path().join("README"), b"AAA")?;
+        let first = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new("root()")?;
+        fs::write(test_repo.directory.path().join("README"), b"BBB")?;
+        let second = test_repo.commander.get_current_head()?;
+        test_repo.commander.execute_void_jj_command(vec![
+            "new",
+            first.commit_id.as_str(),
+            second.commit_id.as_str(),
+        ])?;
+        let merge = test_repo.commander.get_current_head()?;
+        Command::new("git")
+            .args(["tag", "v1.0", first.commit_id.as_str()])
+            .current_dir(test_repo.directory.path())
+            .output()?;
+
+        for compact in [false, true] {
+            let log = test_repo.commander.get_log(&None, compact, true, None)?;
+            assert_eq!(log.conflicted, HashSet::from([merge.commit_id.clone()]));
+            assert_eq!(log.working_copy, Some(merge.commit_id.clone()));
+            assert_eq!(log.tagged, HashSet::from([first.commit_id.clone()]));
+            assert_eq!(log.graph.lines().count(), log.graph_heads.len());
+        }
+
+        Ok(())
+    }
+
     #[test]
     fn get_commit_show() -> Result<()> {
         let test_repo = TestRepo::new()?;
@@ -395,6 +1241,44 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn order_show_ids_puts_primary_first() {
+        let show = "Commit ID: abc\nChange ID: xyz\nAuthor   : lazyjj\n".to_owned();
+
+        assert_eq!(
+            order_show_ids(show.clone(), PrimaryId::Change),
+            "Change ID: xyz\nCommit ID: abc\nAuthor   : lazyjj\n"
+        );
+        assert_eq!(order_show_ids(show.clone(), PrimaryId::Commit), show);
+    }
+
+    #[test]
+    fn get_commit_metadata_and_diff() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        fs::write(test_repo.directory.path().join("README"), b"AAA")?;
+        test_repo.commander.run_describe("@", "Add readme")?;
+
+        let head = test_repo.commander.get_current_head()?;
+        let metadata = test_repo
+            .commander
+            .get_commit_metadata(&head.commit_id, false)?;
+        assert!(metadata.contains("Add readme"));
+        assert!(!metadata.contains("AAA"));
+
+        let diff = test_repo.commander.get_commit_diff(
+            &head.commit_id,
+            None,
+            None,
+            &DiffFormat::ColorWords,
+            false,
+        )?;
+        assert!(diff.contains("AAA"));
+        assert!(!diff.contains("Add readme"));
+
+        Ok(())
+    }
+
     #[test]
     fn get_commit_parent() -> Result<()> {
         let test_repo = TestRepo::new()?;
@@ -414,6 +1298,281 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn get_commit_diff_stat() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        let head = test_repo.commander.get_current_head()?;
+        assert_eq!(
+            test_repo
+                .commander
+                .get_commit_diff_stat(&head.commit_id, None, None, false)?,
+            DiffStat::default()
+        );
+
+        fs::write(test_repo.directory.path().join("README"), b"AAA\nBBB\n")?;
+        fs::write(test_repo.directory.path().join("LICENSE"), b"CCC\n")?;
+        let head = test_repo.commander.get_current_head()?;
+        assert_eq!(
+            test_repo
+                .commander
+                .get_commit_diff_stat(&head.commit_id, None, None, false)?,
+            DiffStat {
+                files: 2,
+                insertions: 3,
+                deletions: 0,
+            }
+        );
+        assert_eq!(
+            test_repo.commander.get_commit_diff_stat(
+                &head.commit_id,
+                None,
+                Some("LICENSE"),
+                false
+            )?,
+            DiffStat {
+                files: 1,
+                insertions: 1,
+                deletions: 0,
+            }
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_diff_range() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        fs::write(test_repo.directory.path().join("README"), b"AAA\n")?;
+        let first = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new("@")?;
+        fs::write(test_repo.directory.path().join("README"), b"BBB\n")?;
+        fs::write(test_repo.directory.path().join("LICENSE"), b"CCC\n")?;
+        let second = test_repo.commander.get_current_head()?;
+
+        let diff = test_repo.commander.get_diff_range(
+            &first.commit_id,
+            &second.commit_id,
+            None,
+            &DiffFormat::Git,
+            false,
+        )?;
+        assert!(diff.contains("-AAA"));
+        assert!(diff.contains("+BBB"));
+        assert!(diff.contains("+CCC"));
+
+        let diff = test_repo.commander.get_diff_range(
+            &second.commit_id,
+            &first.commit_id,
+            Some("LICENSE"),
+            &DiffFormat::Git,
+            false,
+        )?;
+        assert!(diff.contains("-CCC"));
+        assert!(!diff.contains("AAA"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_commit_parents() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        let first = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new("root()")?;
+        let second = test_repo.commander.get_current_head()?;
+        test_repo.commander.execute_void_jj_command(vec![
+            "new",
+            first.commit_id.as_str(),
+            second.commit_id.as_str(),
+        ])?;
+        let merge = test_repo.commander.get_current_head()?;
+
+        assert_eq!(
+            test_repo.commander.get_commit_parents(&merge.commit_id)?,
+            [first.commit_id, second.commit_id]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn is_ancestor() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        let first = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_describe("@", "First")?;
+        test_repo.commander.run_new("@")?;
+        let second = test_repo.commander.get_current_head()?;
+        let first = test_repo.commander.get_head_latest(&first)?;
+
+        assert!(
+            test_repo
+                .commander
+                .is_ancestor(&first.commit_id, &second.commit_id)?
+        );
+        assert!(
+            !test_repo
+                .commander
+                .is_ancestor(&second.commit_id, &first.commit_id)?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn is_in_revset() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        let head = test_repo.commander.get_current_head()?;
+
+        assert!(test_repo.commander.is_in_revset(&head.commit_id, "@")?);
+        assert!(
+            !test_repo
+                .commander
+                .is_in_revset(&head.commit_id, "root()")?
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_conflicted_commits() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        assert_eq!(test_repo.commander.get_conflicted_commits()?, []);
+
+        fs::write(test_repo.directory.path().join("README"), b"AAA")?;
+        let first = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new("root()")?;
+        fs::write(test_repo.directory.path().join("README"), b"BBB")?;
+        let second = test_repo.commander.get_current_head()?;
+        test_repo.commander.execute_void_jj_command(vec![
+            "new",
+            first.commit_id.as_str(),
+            second.commit_id.as_str(),
+        ])?;
+        let merge = test_repo.commander.get_current_head()?;
+
+        assert_eq!(
+            test_repo.commander.get_conflicted_commits()?,
+            [ConflictedCommit {
+                head: merge,
+                description: "".to_owned(),
+                conflicts: 1,
+            }]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_commit_signature() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        let head = test_repo.commander.get_current_head()?;
+        assert_eq!(
+            test_repo.commander.get_commit_signature(&head.commit_id)?,
+            SignatureStatus::Unsigned
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_signed_commits() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        test_repo.commander.run_new("@")?;
+        assert!(
+            test_repo
+                .commander
+                .get_signed_commits(&None, None)?
+                .is_empty()
+        );
+        assert!(
+            test_repo
+                .commander
+                .get_signed_commits(&Some("all()".to_owned()), None)?
+                .is_empty()
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_revset_commits() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        test_repo
+            .commander
+            .run_describe("@", "first line\nsecond line")?;
+        let head = test_repo.commander.get_current_head()?;
+
+        assert_eq!(
+            test_repo.commander.get_revset_commits("@")?,
+            [(head, "first line".to_owned())]
+        );
+        assert_eq!(test_repo.commander.get_revset_commits("none()")?, []);
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_undescribed_heads() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        assert_eq!(test_repo.commander.get_undescribed_heads(&None)?, []);
+
+        fs::write(test_repo.directory.path().join("README"), b"AAA")?;
+        let first = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new("@")?;
+        fs::write(test_repo.directory.path().join("README"), b"BBB")?;
+        let second = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new("@")?;
+        test_repo.commander.run_describe("@", "described")?;
+
+        assert_eq!(
+            test_repo.commander.get_undescribed_heads(&None)?,
+            [first.clone(), second]
+        );
+        assert_eq!(
+            test_repo
+                .commander
+                .get_undescribed_heads(&Some("@--".to_owned()))?,
+            [first]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn working_copy_error_detect() {
+        let status = |output: &str| CommandError::Status(output.to_owned(), Some(1));
+
+        assert_eq!(
+            WorkingCopyError::detect(&status(
+                "Error: The working copy is stale (not updated since operation abc)."
+            )),
+            Some(WorkingCopyError::Stale)
+        );
+        assert_eq!(
+            WorkingCopyError::detect(&status(
+                r#"Error: Workspace "default" doesn't have a working-copy commit"#
+            )),
+            Some(WorkingCopyError::Missing)
+        );
+        assert_eq!(
+            WorkingCopyError::detect(&status("Error: Revision `@` doesn't exist")),
+            Some(WorkingCopyError::Missing)
+        );
+        assert_eq!(
+            WorkingCopyError::detect(&status("Error: Revision `abc` doesn't exist")),
+            None
+        );
+    }
+
     #[test]
     fn get_head_latest() -> Result<()> {
         let test_repo = TestRepo::new()?;
@@ -440,6 +1599,97 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn get_trunk_ahead_behind() -> Result<()> {
+        let mut test_repo = TestRepo::new()?;
+
+        // Without remotes, trunk() is the root commit
+        assert_eq!(test_repo.commander.get_trunk_ahead_behind()?, None);
+
+        if let Some(jj_config_toml) = test_repo.commander.jj_config_toml.as_mut() {
+            jj_config_toml.push(r#"revset-aliases."trunk()"="main""#.to_owned());
+        }
+        test_repo.commander.create_bookmark("main")?;
+        test_repo.commander.run_new("@")?;
+        test_repo.commander.run_new("@")?;
+
+        assert_eq!(test_repo.commander.get_trunk_ahead_behind()?, Some((2, 0)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_revision_head() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        let head = test_repo.commander.get_current_head()?;
+        assert_eq!(
+            test_repo
+                .commander
+                .get_revision_head(head.commit_id.as_str())?,
+            head
+        );
+        assert!(test_repo.commander.get_revision_head("missing").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn find_by_id_prefix() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        // More changes than letters used by change ids, so some share their first letter
+        for index in 0..16 {
+            test_repo.commander.run_new("@")?;
+            test_repo
+                .commander
+                .run_describe("@", &format!("change {index}"))?;
+        }
+        let head = test_repo.commander.get_current_head()?;
+
+        let found = test_repo
+            .commander
+            .find_by_id_prefix(&head.change_id.as_str()[..8])?;
+        assert_eq!(
+            found,
+            [PrefixMatch {
+                head: head.clone(),
+                description: "change 15".to_owned(),
+            }]
+        );
+        let found = test_repo
+            .commander
+            .find_by_id_prefix(&head.commit_id.as_str()[..8].to_uppercase())?;
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].head, head);
+
+        assert_eq!(test_repo.commander.find_by_id_prefix("not-an-id")?, []);
+        assert_eq!(test_repo.commander.find_by_id_prefix("")?, []);
+
+        let mut ambiguous = false;
+        for letter in 'k'..='z' {
+            let found = test_repo.commander.find_by_id_prefix(&letter.to_string())?;
+            assert!(
+                found
+                    .iter()
+                    .all(|found| found.head.change_id.as_str().starts_with(letter))
+            );
+            ambiguous |= found.len() > 1;
+        }
+        assert!(ambiguous);
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_default_log_revset() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        assert!(!test_repo.commander.get_default_log_revset()?.is_empty());
+
+        Ok(())
+    }
+
     #[test]
     fn get_bookmark_head() -> Result<()> {
         let test_repo = TestRepo::new()?;
@@ -21,10 +21,16 @@ command implementation functions:
 */
 
 pub mod bookmarks;
+pub mod config;
 pub mod files;
 pub mod ids;
 pub mod jj;
 pub mod log;
+pub mod operations;
+pub mod remotes;
+pub mod sparse;
+pub mod tags;
+pub mod workspaces;
 
 use crate::env::DiffFormat;
 use crate::env::Env;
@@ -40,7 +46,7 @@ use std::sync::Mutex;
 use std::{
     ffi::OsStr,
     io,
-    process::{Command, Output},
+    process::{Command, Output, Stdio},
     string::FromUtf8Error,
     sync::Arc,
 };
@@ -74,6 +80,10 @@ pub enum CommandError {
     Status(String, Option<i32>),
     #[error("Error parsing UTF-8 output: {0}")]
     FromUtf8(#[from] FromUtf8Error),
+    #[error(
+        "Cannot run `jj {0}` while viewing operation {1}. Return to the latest operation first."
+    )]
+    ReadOnly(String, String),
 }
 
 impl CommandError {
@@ -88,6 +98,26 @@ impl CommandError {
 
         Ok(Text::from(lines))
     }
+
+    /// Whether the command failed because of jj commands running at the same time, e.g. in
+    /// another terminal. Running it again later usually succeeds
+    pub fn is_concurrent_operation(&self) -> bool {
+        matches!(
+            self,
+            CommandError::Status(output, _)
+                if output.contains("Predecessors cycle detected")
+                    || output.contains("Concurrent modification detected")
+        )
+    }
+}
+
+/// Whether an error was caused by jj commands running at the same time, see
+/// [CommandError::is_concurrent_operation]
+pub fn is_concurrent_operation_error(err: &anyhow::Error) -> bool {
+    err.chain().any(|err| {
+        err.downcast_ref::<CommandError>()
+            .is_some_and(CommandError::is_concurrent_operation)
+    })
 }
 
 #[derive(Clone, Debug)]
@@ -102,14 +132,19 @@ pub struct CommandLogItem {
 /// Struct used to interact with the jj cli using commanders.
 ///
 /// Handles arguments and recording of history.
-#[derive(Debug)]
+#[derive(Clone, Debug)]
 pub struct Commander {
     pub env: Env,
     pub command_history: Arc<Mutex<Vec<CommandLogItem>>>,
 
+    /// Operation to view the repo at. While set, only read-only commands are allowed
+    pub at_operation: Option<String>,
+
+    /// Pass `--color=never` to jj, set by `NO_COLOR` and `lazyjj.color`, and for testing
+    pub force_no_color: bool,
+
     // Used for testing
     pub jj_config_toml: Option<Vec<String>>,
-    pub force_no_color: bool,
 }
 
 impl Commander {
@@ -117,13 +152,21 @@ impl Commander {
         Self {
             env: env.clone(),
             command_history: Arc::new(Mutex::new(Vec::new())),
+            at_operation: None,
+            force_no_color: !env.config.color(),
             jj_config_toml: None,
-            force_no_color: false,
         }
     }
 
     /// Execute a command and record to history.
     fn execute_command(&self, command: &mut Command) -> Result<String, CommandError> {
+        Ok(String::from_utf8(
+            self.execute_command_output(command)?.stdout,
+        )?)
+    }
+
+    /// Execute a command and record to history, returning both stdout and stderr.
+    fn execute_command_output(&self, command: &mut Command) -> Result<Output, CommandError> {
         // Set current directory to root
         command.current_dir(&self.env.root);
 
@@ -163,7 +206,7 @@ impl Commander {
             ));
         }
 
-        Ok(String::from_utf8(output.stdout)?)
+        Ok(output)
     }
 
     /// Execute a jj command with color/quiet arguments.
@@ -177,17 +220,66 @@ impl Commander {
         I: IntoIterator<Item = S>,
         S: AsRef<OsStr>,
     {
+        self.execute_command(&mut self.jj_command(args, color, quiet)?)
+    }
+
+    /// Execute a jj command, returning the messages jj prints to stderr, e.g. summaries of
+    /// what the command changed.
+    pub fn execute_jj_command_messages<I, S>(&self, args: I) -> Result<String, CommandError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let output = self.execute_command_output(&mut self.jj_command(args, true, false)?)?;
+        Ok(String::from_utf8(output.stderr)?)
+    }
+
+    /// Build a jj command with color/quiet arguments
+    fn jj_command<I, S>(&self, args: I, color: bool, quiet: bool) -> Result<Command, CommandError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let args: Vec<S> = args.into_iter().collect();
+
         let mut command = Command::new(&self.env.jj_bin);
-        command.args(args);
+        command.args(&args);
         command.args(get_output_args(!self.force_no_color && color, quiet));
 
+        if let Some(at_operation) = self.at_operation.as_ref() {
+            let args: Vec<String> = args
+                .iter()
+                .map(|arg| arg.as_ref().to_string_lossy().to_string())
+                .collect();
+            if !is_read_only_command(&args) {
+                return Err(CommandError::ReadOnly(
+                    args.join(" "),
+                    at_operation.to_owned(),
+                ));
+            }
+            command.args(["--at-operation", at_operation]);
+        }
+
         if let Some(jj_config_toml) = &self.jj_config_toml {
             for cfg in jj_config_toml {
                 command.args(["--config", cfg]);
             }
         }
 
-        self.execute_command(&mut command)
+        Ok(command)
+    }
+
+    /// Execute a jj command which uses the terminal, e.g. to run a diff editor. Only its
+    /// error messages are captured. The UI must be suspended meanwhile.
+    pub fn execute_interactive_jj_command<I, S>(&self, args: I) -> Result<(), CommandError>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<OsStr>,
+    {
+        let mut command = self.jj_command(args, true, false)?;
+        command.stdin(Stdio::inherit()).stdout(Stdio::inherit());
+        self.execute_command_output(&mut command)?;
+        Ok(())
     }
 
     /// Execute a jj command without using the output.
@@ -254,6 +346,28 @@ impl RemoveEndLine for String {
     }
 }
 
+/// Check if a jj command only reads from the repo, and so can run at an older operation
+pub fn is_read_only_command(args: &[String]) -> bool {
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    match args.as_slice() {
+        [
+            "log" | "show" | "diff" | "status" | "st" | "evolog" | "interdiff" | "version" | "root"
+            | "help",
+            ..,
+        ] => true,
+        ["file", "list" | "show" | "annotate", ..]
+        | ["bookmark" | "b", "list" | "l", ..]
+        | ["tag", "list" | "l", ..]
+        | ["op" | "operation", "log" | "show" | "diff", ..]
+        | ["git", "remote", "list", ..]
+        | ["config", "list" | "get" | "path", ..]
+        | ["sparse", "list", ..]
+        | ["workspace", "list" | "root", ..] => true,
+        ["resolve", rest @ ..] => rest.iter().any(|arg| *arg == "--list" || *arg == "-l"),
+        _ => false,
+    }
+}
+
 pub fn get_output_args(color: bool, quiet: bool) -> Vec<String> {
     vec![
         "--no-pager",
@@ -320,6 +434,60 @@ pub mod tests {
         }
     }
 
+    #[test]
+    fn is_concurrent_operation() {
+        let err = CommandError::Status(
+            "Internal error: Predecessors cycle detected around commit abc".to_owned(),
+            Some(255),
+        );
+        assert!(err.is_concurrent_operation());
+        assert!(is_concurrent_operation_error(
+            &anyhow::Error::new(err).context("Failed getting current head")
+        ));
+
+        let err = CommandError::Status("Error: Revision `xyz` doesn't exist".to_owned(), Some(1));
+        assert!(!err.is_concurrent_operation());
+        assert!(!is_concurrent_operation_error(&anyhow::Error::new(err)));
+    }
+
+    #[test]
+    fn is_read_only_command() {
+        let args = |args: &str| -> Vec<String> { args.split(' ').map(String::from).collect() };
+
+        assert!(super::is_read_only_command(&args("log -r @")));
+        assert!(super::is_read_only_command(&args("bookmark list --all")));
+        assert!(super::is_read_only_command(&args("resolve --list -r @")));
+        assert!(!super::is_read_only_command(&args("new @")));
+        assert!(!super::is_read_only_command(&args("bookmark set main")));
+        assert!(!super::is_read_only_command(&args("resolve -r @")));
+    }
+
+    #[test]
+    fn at_operation() -> Result<()> {
+        let mut test_repo = TestRepo::new()?;
+
+        let head = test_repo.commander.get_current_head()?;
+        let operation = test_repo
+            .commander
+            .execute_jj_command(
+                vec!["op", "log", "--no-graph", "-T", "id", "--limit", "1"],
+                false,
+                true,
+            )?
+            .remove_end_line();
+        test_repo.commander.run_new("@")?;
+
+        test_repo.commander.at_operation = Some(operation);
+        assert_eq!(test_repo.commander.get_current_head()?, head);
+        let err = test_repo.commander.run_new("@").unwrap_err();
+        assert!(
+            err.chain()
+                .any(|err| matches!(err.downcast_ref(), Some(CommandError::ReadOnly(..))))
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_repo() -> Result<()> {
         apply_common_filters!();
@@ -0,0 +1,203 @@
+/*!
+[Commander] member functions related to jj operations.
+
+This module parses the `jj op log` output. It is used to pick an
+operation to view the repo at, and by the [op log tab][crate::ui::op_log_tab].
+*/
+
+use crate::commander::{CommandError, Commander};
+
+use anyhow::Context;
+use chrono::{DateTime, Local};
+use tracing::instrument;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct Operation {
+    pub id: String,
+    pub time: String,
+    /// User and host which ran the operation, as `username@hostname`
+    pub user: String,
+    pub description: String,
+}
+
+// Template which outputs `id\ttime\tuser\tdescription`. Used to parse the op log
+const OPERATION_TEMPLATE: &str = r#"id.short() ++ "\t" ++ time.start().ago() ++ "\t" ++ user ++ "\t" ++ description.first_line() ++ "\n""#;
+
+// Template which outputs the end time of fetch operations as a unix timestamp
+const FETCH_TIME_TEMPLATE: &str =
+    r#"if(description.starts_with("fetch from git remote"), time.end().format("%s") ++ "\n")"#;
+
+// Number of operations searched for the last fetch
+const FETCH_SEARCH_LIMIT: usize = 1000;
+
+fn parse_operation(text: &str) -> Option<Operation> {
+    let mut parts = text.splitn(4, '\t');
+    Some(Operation {
+        id: parts.next()?.to_owned(),
+        time: parts.next()?.to_owned(),
+        user: parts.next()?.to_owned(),
+        description: parts.next()?.to_owned(),
+    })
+}
+
+impl Commander {
+    /// Get the latest operations, newest first.
+    /// Maps to `jj op log`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_operations(&self, limit: usize) -> Result<Vec<Operation>, CommandError> {
+        Ok(self
+            .execute_jj_command(
+                vec![
+                    "op",
+                    "log",
+                    "--no-graph",
+                    "--template",
+                    OPERATION_TEMPLATE,
+                    "--limit",
+                    &limit.to_string(),
+                ],
+                false,
+                true,
+            )?
+            .lines()
+            .filter_map(parse_operation)
+            .collect())
+    }
+
+    /// Get the time of the last `jj git fetch`, if it is among the latest operations.
+    /// Maps to `jj op log`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_last_fetch_time(&self) -> Result<Option<DateTime<Local>>, CommandError> {
+        Ok(self
+            .execute_jj_command(
+                vec![
+                    "op",
+                    "log",
+                    "--no-graph",
+                    "--template",
+                    FETCH_TIME_TEMPLATE,
+                    "--limit",
+                    &FETCH_SEARCH_LIMIT.to_string(),
+                ],
+                false,
+                true,
+            )?
+            .lines()
+            .find_map(|line| line.trim().parse().ok())
+            .and_then(|timestamp| DateTime::from_timestamp(timestamp, 0))
+            .map(|time| time.with_timezone(&Local)))
+    }
+
+    /// Get the changes made by an operation.
+    /// Maps to `jj op show <operation>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_operation_show(&self, operation_id: &str) -> Result<String, CommandError> {
+        self.execute_jj_command(vec!["op", "show", operation_id], true, true)
+    }
+
+    /// Restore the repo to the state at an operation.
+    /// Maps to `jj op restore <operation>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn run_op_restore(&self, operation_id: &str) -> anyhow::Result<()> {
+        self.execute_void_jj_command(vec!["op", "restore", operation_id])
+            .context("Failed executing jj op restore")
+    }
+
+    /// Undo the last operation. Maps to `jj undo`
+    #[instrument(level = "trace", skip(self))]
+    pub fn run_undo(&self) -> anyhow::Result<()> {
+        self.execute_void_jj_command(vec!["undo"])
+            .context("Failed executing jj undo")
+    }
+
+    /// Redo the last undone operation. Maps to `jj redo`
+    #[instrument(level = "trace", skip(self))]
+    pub fn run_redo(&self) -> anyhow::Result<()> {
+        self.execute_void_jj_command(vec!["redo"])
+            .context("Failed executing jj redo")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commander::tests::TestRepo;
+    use anyhow::Result;
+
+    #[test]
+    fn get_operations() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        test_repo.commander.run_new("@")?;
+        let operations = test_repo.commander.get_operations(2)?;
+
+        assert_eq!(operations.len(), 2);
+        assert!(operations[0].description.starts_with("new empty commit"));
+        assert!(operations[0].user.contains('@'));
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_operation_show() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        test_repo.commander.run_new("@")?;
+        let operations = test_repo.commander.get_operations(1)?;
+        let show = test_repo.commander.get_operation_show(&operations[0].id)?;
+
+        assert!(show.contains("new empty commit"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn run_undo_redo() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        let head = test_repo.commander.get_current_head()?;
+        test_repo.commander.run_new(head.commit_id.as_str())?;
+        let new_head = test_repo.commander.get_current_head()?;
+
+        test_repo.commander.run_undo()?;
+        assert_eq!(
+            test_repo.commander.get_current_head()?.change_id,
+            head.change_id
+        );
+
+        test_repo.commander.run_redo()?;
+        assert_eq!(
+            test_repo.commander.get_current_head()?.change_id,
+            new_head.change_id
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn run_op_restore() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        let head = test_repo.commander.get_current_head()?;
+        let operations = test_repo.commander.get_operations(1)?;
+        test_repo.commander.run_new(head.commit_id.as_str())?;
+        test_repo.commander.run_new("@")?;
+
+        test_repo.commander.run_op_restore(&operations[0].id)?;
+        assert_eq!(
+            test_repo.commander.get_current_head()?.change_id,
+            head.change_id
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn get_last_fetch_time_without_fetch() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        assert_eq!(test_repo.commander.get_last_fetch_time()?, None);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,204 @@
+/*!
+[Commander] member functions related to git remotes.
+
+Besides listing and editing remotes, this module knows how to turn a remote URL
+into the URL of the hosting service's web UI.
+*/
+
+use crate::commander::{CommandError, Commander, ids::CommitId};
+
+use tracing::instrument;
+
+#[derive(Clone, PartialEq, Eq, Debug)]
+pub struct GitRemote {
+    pub name: String,
+    pub url: String,
+}
+
+impl GitRemote {
+    /// URL of the remote's web UI, derived from SSH (`git@host:owner/repo.git`,
+    /// `ssh://git@host/owner/repo.git`) and HTTP(S) remote URLs
+    pub fn web_url(&self) -> Option<String> {
+        let url = self.url.trim().trim_end_matches('/');
+        let url = url.strip_suffix(".git").unwrap_or(url);
+
+        let (host, path) = if let Some(rest) = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))
+            .or_else(|| url.strip_prefix("ssh://"))
+            .or_else(|| url.strip_prefix("git://"))
+        {
+            rest.split_once('/')?
+        } else {
+            // scp-like syntax: [user@]host:path
+            let (host, path) = url.split_once(':')?;
+            if host.contains('/') {
+                return None;
+            }
+            (host, path)
+        };
+
+        // Drop user and port, which are not part of the web URL
+        let host = host.rsplit_once('@').map_or(host, |(_, host)| host);
+        let host = host.split_once(':').map_or(host, |(host, _)| host);
+        let path = path.trim_start_matches('/');
+        if host.is_empty() || path.is_empty() {
+            return None;
+        }
+
+        Some(format!("https://{host}/{path}"))
+    }
+
+    /// URL of a commit in the remote's web UI. A custom template can use
+    /// `{remote}` for the remote's web URL and `{commit_id}` for the commit id.
+    pub fn commit_web_url(&self, template: Option<&str>, commit_id: &CommitId) -> Option<String> {
+        let web_url = self.web_url();
+        if let Some(template) = template {
+            return Some(
+                template
+                    .replace("{remote}", web_url.as_deref().unwrap_or(&self.url))
+                    .replace("{commit_id}", commit_id.as_str()),
+            );
+        }
+
+        let web_url = web_url?;
+        if web_url.starts_with("https://gitlab.") {
+            Some(format!("{web_url}/-/commit/{commit_id}"))
+        } else {
+            Some(format!("{web_url}/commit/{commit_id}"))
+        }
+    }
+}
+
+impl Commander {
+    /// Get the list of git remotes.
+    /// Maps to `jj git remote list`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_git_remotes(&self) -> Result<Vec<GitRemote>, CommandError> {
+        Ok(self
+            .execute_jj_command(vec!["git", "remote", "list"], false, true)?
+            .lines()
+            .filter_map(|line| {
+                let (name, url) = line.split_once(' ')?;
+                Some(GitRemote {
+                    name: name.to_owned(),
+                    url: url.trim().to_owned(),
+                })
+            })
+            .collect())
+    }
+
+    /// Add a git remote.
+    /// Maps to `jj git remote add <name> <url>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn add_git_remote(&self, name: &str, url: &str) -> Result<(), CommandError> {
+        self.execute_void_jj_command(vec!["git", "remote", "add", name, url])
+    }
+
+    /// Remove a git remote and its remote-tracking bookmarks.
+    /// Maps to `jj git remote remove <name>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn remove_git_remote(&self, name: &str) -> Result<(), CommandError> {
+        self.execute_void_jj_command(vec!["git", "remote", "remove", name])
+    }
+
+    /// Change the URL of a git remote.
+    /// Maps to `jj git remote set-url <name> <url>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn set_git_remote_url(&self, name: &str, url: &str) -> Result<(), CommandError> {
+        self.execute_void_jj_command(vec!["git", "remote", "set-url", name, url])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commander::tests::TestRepo;
+    use anyhow::Result;
+
+    fn remote(url: &str) -> GitRemote {
+        GitRemote {
+            name: "origin".to_owned(),
+            url: url.to_owned(),
+        }
+    }
+
+    #[test]
+    fn web_url() {
+        for url in [
+            "git@github.com:peso/lazyjj.git",
+            "ssh://git@github.com/peso/lazyjj.git",
+            "ssh://git@github.com:22/peso/lazyjj",
+            "https://github.com/peso/lazyjj.git",
+            "https://user@github.com/peso/lazyjj/",
+        ] {
+            assert_eq!(
+                remote(url).web_url(),
+                Some("https://github.com/peso/lazyjj".to_owned()),
+                "{url}"
+            );
+        }
+        assert_eq!(remote("/path/to/repo").web_url(), None);
+    }
+
+    #[test]
+    fn commit_web_url() {
+        let commit_id = CommitId("abc".to_owned());
+
+        assert_eq!(
+            remote("git@github.com:peso/lazyjj.git").commit_web_url(None, &commit_id),
+            Some("https://github.com/peso/lazyjj/commit/abc".to_owned())
+        );
+        assert_eq!(
+            remote("git@gitlab.com:peso/lazyjj.git").commit_web_url(None, &commit_id),
+            Some("https://gitlab.com/peso/lazyjj/-/commit/abc".to_owned())
+        );
+        assert_eq!(
+            remote("git@example.com:peso/lazyjj.git")
+                .commit_web_url(Some("{remote}/commits/{commit_id}"), &commit_id),
+            Some("https://example.com/peso/lazyjj/commits/abc".to_owned())
+        );
+    }
+
+    #[test]
+    fn get_git_remotes() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        assert_eq!(test_repo.commander.get_git_remotes()?, []);
+
+        test_repo.commander.execute_void_jj_command(vec![
+            "git",
+            "remote",
+            "add",
+            "origin",
+            "https://github.com/peso/lazyjj.git",
+        ])?;
+        assert_eq!(
+            test_repo.commander.get_git_remotes()?,
+            [remote("https://github.com/peso/lazyjj.git")]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn edit_git_remotes() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        test_repo
+            .commander
+            .add_git_remote("origin", "https://example.com/old.git")?;
+        test_repo
+            .commander
+            .set_git_remote_url("origin", "https://github.com/peso/lazyjj.git")?;
+        assert_eq!(
+            test_repo.commander.get_git_remotes()?,
+            [remote("https://github.com/peso/lazyjj.git")]
+        );
+
+        test_repo.commander.remove_git_remote("origin")?;
+        assert_eq!(test_repo.commander.get_git_remotes()?, []);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,69 @@
+/*!
+[Commander] member functions related to sparse working copies.
+
+A sparse working copy only materializes the files matching its patterns. It is
+edited in the [sparse_popup][crate::ui::sparse_popup] module.
+*/
+
+use crate::commander::{CommandError, Commander};
+
+use tracing::instrument;
+
+/// Whether the patterns materialize the whole repo, which is the default
+pub fn is_full_checkout(patterns: &[String]) -> bool {
+    patterns == ["."]
+}
+
+impl Commander {
+    /// Get the patterns of the files materialized in the working copy.
+    /// Maps to `jj sparse list`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_sparse_patterns(&self) -> Result<Vec<String>, CommandError> {
+        Ok(self
+            .execute_jj_command(vec!["sparse", "list"], false, true)?
+            .lines()
+            .map(str::to_owned)
+            .collect())
+    }
+
+    /// Replace the patterns of the files materialized in the working copy.
+    /// Maps to `jj sparse set --clear --add <pattern>...`
+    #[instrument(level = "trace", skip(self))]
+    pub fn set_sparse_patterns(&self, patterns: &[String]) -> Result<(), CommandError> {
+        let mut args = vec!["sparse", "set", "--clear"];
+        for pattern in patterns {
+            args.extend(["--add", pattern.as_str()]);
+        }
+        self.execute_void_jj_command(args)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commander::tests::TestRepo;
+    use anyhow::Result;
+    use std::fs;
+
+    #[test]
+    fn set_sparse_patterns() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+        fs::create_dir(test_repo.directory.path().join("src"))?;
+        fs::write(test_repo.directory.path().join("src/main.rs"), "")?;
+        fs::write(test_repo.directory.path().join("README.md"), "")?;
+        test_repo.commander.execute_void_jj_command(vec!["new"])?;
+
+        assert!(is_full_checkout(
+            &test_repo.commander.get_sparse_patterns()?
+        ));
+
+        test_repo
+            .commander
+            .set_sparse_patterns(&["src".to_owned()])?;
+        assert_eq!(test_repo.commander.get_sparse_patterns()?, ["src"]);
+        assert!(test_repo.directory.path().join("src/main.rs").exists());
+        assert!(!test_repo.directory.path().join("README.md").exists());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,112 @@
+/*!
+[Commander] member functions related to tags.
+
+Tags are listed read-only, they are created and moved with git. The log marks
+the changes they point to with [LogOutput::tagged][crate::commander::log::LogOutput].
+*/
+
+use crate::commander::{CommandError, Commander, ids::CommitId};
+
+use tracing::instrument;
+
+/// Tag and the change it points to
+#[derive(Clone, Debug, PartialEq)]
+pub struct Tag {
+    pub name: String,
+    /// Target commit, `None` when the tag is conflicted
+    pub commit_id: Option<CommitId>,
+    /// Shortest change ID and first description line of the target
+    pub target: String,
+}
+
+// Template which outputs `name|commit_id|change_id|description`
+const TAG_TEMPLATE: &str = r#"name ++ "|" ++ if(normal_target, normal_target.commit_id() ++ "|" ++ normal_target.change_id().shortest(8) ++ "|" ++ normal_target.description().first_line()) ++ "\n""#;
+
+fn parse_tag(text: &str) -> Option<Tag> {
+    let mut parts = text.splitn(4, '|');
+    let name = parts.next().filter(|name| !name.is_empty())?;
+    let commit_id = parts.next().filter(|commit_id| !commit_id.is_empty());
+    let change_id = parts.next().unwrap_or_default();
+    let description = parts.next().unwrap_or_default();
+    Some(Tag {
+        name: name.to_owned(),
+        commit_id: commit_id.map(|commit_id| CommitId(commit_id.to_owned())),
+        target: match (commit_id, description.is_empty()) {
+            (None, _) => "(conflicted)".to_owned(),
+            (Some(_), true) => format!("{change_id} (no description set)"),
+            (Some(_), false) => format!("{change_id} {description}"),
+        },
+    })
+}
+
+/// Whether the command failed because jj has no `tag` command or doesn't support its arguments
+pub fn is_tags_unsupported(err: &CommandError) -> bool {
+    let CommandError::Status(output, _) = err else {
+        return false;
+    };
+    output.contains("unrecognized subcommand") || output.contains("unexpected argument")
+}
+
+impl Commander {
+    /// Get tags with the change they point to.
+    /// Maps to `jj tag list`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_tags(&self) -> Result<Vec<Tag>, CommandError> {
+        Ok(self
+            .execute_jj_command(vec!["tag", "list", "-T", TAG_TEMPLATE], false, true)?
+            .lines()
+            .filter_map(parse_tag)
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commander::tests::TestRepo;
+    use anyhow::Result;
+    use std::process::Command;
+
+    #[test]
+    fn parse_tag_line() {
+        assert_eq!(
+            parse_tag("v1.0|abc|qpvuntsm|Release 1.0"),
+            Some(Tag {
+                name: "v1.0".to_owned(),
+                commit_id: Some(CommitId("abc".to_owned())),
+                target: "qpvuntsm Release 1.0".to_owned(),
+            })
+        );
+        assert_eq!(
+            parse_tag("v2.0|"),
+            Some(Tag {
+                name: "v2.0".to_owned(),
+                commit_id: None,
+                target: "(conflicted)".to_owned(),
+            })
+        );
+    }
+
+    #[test]
+    fn get_tags() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+
+        assert_eq!(test_repo.commander.get_tags()?, []);
+
+        test_repo.commander.run_describe("@", "Release")?;
+        let head = test_repo.commander.get_current_head()?;
+        // Tags are created with git, and imported by jj in colocated repos
+        Command::new("git")
+            .args(["tag", "v1.0", head.commit_id.as_str()])
+            .current_dir(test_repo.directory.path())
+            .output()?;
+
+        let tags = test_repo.commander.get_tags()?;
+        assert_eq!(tags.len(), 1);
+        assert_eq!(tags[0].name, "v1.0");
+        assert_eq!(tags[0].commit_id, Some(head.commit_id));
+        assert!(tags[0].target.ends_with(" Release"));
+
+        Ok(())
+    }
+}
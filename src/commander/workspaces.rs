@@ -0,0 +1,107 @@
+/*!
+[Commander] member functions related to jj workspaces.
+
+Several workspaces can share a repo, each with its own working copy. lazyjj
+operates on the workspace it was started in, and can switch to another one.
+*/
+
+use std::path::Path;
+
+use crate::commander::{CommandError, Commander, RemoveEndLine};
+
+use tracing::instrument;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Workspace {
+    pub name: String,
+    /// Root directory of the workspace
+    pub path: String,
+    /// Whether lazyjj operates on this workspace
+    pub current: bool,
+}
+
+/// Parse the output of `jj workspace list`, where each line is `<name>: <working copy>`
+fn parse_workspace_names(output: &str) -> Vec<String> {
+    output
+        .lines()
+        .filter_map(|line| line.split_once(": "))
+        .map(|(name, _)| name.to_owned())
+        .collect()
+}
+
+/// Whether two paths point to the same directory, following symlinks
+fn same_path(a: &str, b: &str) -> bool {
+    match (Path::new(a).canonicalize(), Path::new(b).canonicalize()) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => a == b,
+    }
+}
+
+impl Commander {
+    /// Get the workspaces of the repo, with their root directory.
+    /// Maps to `jj workspace list` and `jj workspace root --name <name>`
+    #[instrument(level = "trace", skip(self))]
+    pub fn get_workspaces(&self) -> Result<Vec<Workspace>, CommandError> {
+        let output = self.execute_jj_command(vec!["workspace", "list"], false, true)?;
+        parse_workspace_names(&output)
+            .into_iter()
+            .map(|name| -> Result<Workspace, CommandError> {
+                let path = self
+                    .execute_jj_command(
+                        vec!["workspace", "root", "--name", name.as_str()],
+                        false,
+                        true,
+                    )?
+                    .remove_end_line();
+                Ok(Workspace {
+                    current: same_path(&path, &self.env.root),
+                    name,
+                    path,
+                })
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commander::tests::TestRepo;
+    use anyhow::Result;
+    use tempdir::TempDir;
+
+    #[test]
+    fn parse_workspace_list_output() {
+        let output = "default: qpvuntsm 230dd059 (empty) (no description set)\nsecond: rlvkpnrz 8d3a9c1f (empty) feat: add: colons\n";
+
+        assert_eq!(parse_workspace_names(output), ["default", "second"]);
+    }
+
+    #[test]
+    fn get_workspaces() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+        let directory = TempDir::new("lazyjj")?;
+        let path = directory.path().join("second");
+        let path = path.to_string_lossy().to_string();
+
+        test_repo.commander.execute_void_jj_command(vec![
+            "workspace",
+            "add",
+            "--name",
+            "second",
+            path.as_str(),
+        ])?;
+
+        let workspaces = test_repo.commander.get_workspaces()?;
+        assert_eq!(
+            workspaces
+                .iter()
+                .map(|workspace| (workspace.name.as_str(), workspace.current))
+                .collect::<Vec<_>>(),
+            [("default", true), ("second", false)]
+        );
+        assert!(same_path(&workspaces[1].path, &path));
+
+        Ok(())
+    }
+}
@@ -1,12 +1,19 @@
-use std::{path::PathBuf, process::Command};
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
 
 use anyhow::{Context, Result, bail};
-use ratatui::style::Color;
+use ratatui::{
+    crossterm::event::{KeyCode, KeyModifiers},
+    style::Color,
+};
 use serde::Deserialize;
+use thiserror::Error;
 
 use crate::{
-    commander::{RemoveEndLine, get_output_args},
-    keybinds::KeybindsConfig,
+    commander::{RemoveEndLine, get_output_args, log::DEFAULT_LOG_TEMPLATE},
+    keybinds::{Keybind, KeybindsConfig, Shortcut},
 };
 
 // TODO: After 0.18, remove Config and replace with JjConfig
@@ -14,6 +21,12 @@ use crate::{
 pub struct Config {
     #[serde(rename = "lazyjj.highlight-color")]
     lazyjj_highlight_color: Option<Color>,
+    #[serde(rename = "lazyjj.color")]
+    lazyjj_color: Option<bool>,
+    #[serde(rename = "lazyjj.theme")]
+    lazyjj_theme: Option<Theme>,
+    #[serde(rename = "lazyjj.colors")]
+    lazyjj_colors: Option<ThemeColors>,
     #[serde(rename = "lazyjj.diff-format")]
     lazyjj_diff_format: Option<DiffFormat>,
     #[serde(rename = "lazyjj.diff-tool")]
@@ -24,6 +37,46 @@ pub struct Config {
     lazyjj_layout: Option<JJLayout>,
     #[serde(rename = "lazyjj.layout-percent")]
     lazyjj_layout_percent: Option<u16>,
+    #[serde(rename = "lazyjj.log-compact")]
+    lazyjj_log_compact: Option<bool>,
+    #[serde(rename = "lazyjj.log-graph")]
+    lazyjj_log_graph: Option<bool>,
+    #[serde(rename = "lazyjj.log-template")]
+    lazyjj_log_template: Option<String>,
+    #[serde(rename = "lazyjj.log-limit")]
+    lazyjj_log_limit: Option<usize>,
+    #[serde(rename = "lazyjj.log-load-more")]
+    lazyjj_log_load_more: Option<usize>,
+    #[serde(rename = "lazyjj.files-tree")]
+    lazyjj_files_tree: Option<bool>,
+    #[serde(rename = "lazyjj.details-wrap")]
+    lazyjj_details_wrap: Option<bool>,
+    #[serde(rename = "lazyjj.fold-details")]
+    lazyjj_fold_details: Option<bool>,
+    #[serde(rename = "lazyjj.primary-id")]
+    lazyjj_primary_id: Option<PrimaryId>,
+    #[serde(rename = "lazyjj.mouse-scroll")]
+    lazyjj_mouse_scroll: Option<MouseScroll>,
+    #[serde(rename = "lazyjj.remote-url-template")]
+    lazyjj_remote_url_template: Option<String>,
+    #[serde(rename = "lazyjj.tab-width")]
+    lazyjj_tab_width: Option<usize>,
+    #[serde(rename = "lazyjj.syntax-highlighting")]
+    lazyjj_syntax_highlighting: Option<bool>,
+    #[serde(rename = "lazyjj.refresh-on-focus")]
+    lazyjj_refresh_on_focus: Option<bool>,
+    #[serde(rename = "lazyjj.auto-refresh-ms")]
+    lazyjj_auto_refresh_ms: Option<u64>,
+    #[serde(rename = "lazyjj.tab-order")]
+    lazyjj_tab_order: Option<Vec<String>>,
+    #[serde(rename = "lazyjj.quit-key")]
+    lazyjj_quit_key: Option<Keybind>,
+    #[serde(rename = "lazyjj.quit-double-press")]
+    lazyjj_quit_double_press: Option<bool>,
+    #[serde(rename = "lazyjj.confirm-destructive")]
+    lazyjj_confirm_destructive: Option<bool>,
+    #[serde(rename = "lazyjj.push-dry-run")]
+    lazyjj_push_dry_run: Option<bool>,
     #[serde(rename = "lazyjj.keybinds")]
     lazyjj_keybinds: Option<KeybindsConfig>,
     #[serde(rename = "ui.diff.format")]
@@ -46,11 +99,34 @@ pub struct JjConfig {
 #[serde(rename_all = "kebab-case")]
 pub struct JjConfigLazyjj {
     highlight_color: Option<Color>,
+    color: Option<bool>,
+    theme: Option<Theme>,
+    colors: Option<ThemeColors>,
     diff_format: Option<DiffFormat>,
     diff_tool: Option<String>,
     bookmark_prefix: Option<String>,
     layout: Option<JJLayout>,
     layout_percent: Option<u16>,
+    log_compact: Option<bool>,
+    log_graph: Option<bool>,
+    log_template: Option<String>,
+    log_limit: Option<usize>,
+    log_load_more: Option<usize>,
+    files_tree: Option<bool>,
+    details_wrap: Option<bool>,
+    fold_details: Option<bool>,
+    primary_id: Option<PrimaryId>,
+    mouse_scroll: Option<MouseScroll>,
+    remote_url_template: Option<String>,
+    tab_width: Option<usize>,
+    syntax_highlighting: Option<bool>,
+    refresh_on_focus: Option<bool>,
+    auto_refresh_ms: Option<u64>,
+    tab_order: Option<Vec<String>>,
+    quit_key: Option<Keybind>,
+    quit_double_press: Option<bool>,
+    confirm_destructive: Option<bool>,
+    push_dry_run: Option<bool>,
     keybinds: Option<KeybindsConfig>,
 }
 
@@ -98,10 +174,28 @@ impl Config {
     }
 
     pub fn highlight_color(&self) -> Color {
-        self.lazyjj_highlight_color
+        self.theme_colors()
+            .selected_bg
+            .or(self.lazyjj_highlight_color)
             .unwrap_or(Color::Rgb(50, 50, 150))
     }
 
+    /// Whether to use colors, in the UI and jj output.
+    /// Defaults to `false` when the `NO_COLOR` environment variable is set
+    pub fn color(&self) -> bool {
+        self.lazyjj_color.unwrap_or_else(|| {
+            std::env::var_os("NO_COLOR").is_none_or(|no_color| no_color.is_empty())
+        })
+    }
+
+    pub fn theme(&self) -> Theme {
+        self.lazyjj_theme.unwrap_or_default()
+    }
+
+    pub fn theme_colors(&self) -> ThemeColors {
+        self.lazyjj_colors.unwrap_or_default()
+    }
+
     pub fn bookmark_prefix(&self) -> String {
         self.lazyjj_bookmark_prefix.clone().unwrap_or(
             self.git_push_bookmark_prefix
@@ -118,6 +212,104 @@ impl Config {
         self.lazyjj_layout_percent.unwrap_or(50)
     }
 
+    pub fn log_compact(&self) -> bool {
+        self.lazyjj_log_compact.unwrap_or(false)
+    }
+
+    pub fn log_graph(&self) -> bool {
+        self.lazyjj_log_graph.unwrap_or(true)
+    }
+
+    /// jj template of each change in the log, outside of compact mode
+    pub fn log_template(&self) -> &str {
+        self.lazyjj_log_template
+            .as_deref()
+            .unwrap_or(DEFAULT_LOG_TEMPLATE)
+    }
+
+    /// Number of changes the log loads at first. `None` when set to 0, loading all of them
+    pub fn log_limit(&self) -> Option<usize> {
+        Some(self.lazyjj_log_limit.unwrap_or(500)).filter(|limit| *limit > 0)
+    }
+
+    /// Number of changes loaded when scrolling to the end of a log cut off by the limit
+    pub fn log_load_more(&self) -> usize {
+        self.lazyjj_log_load_more.unwrap_or(500).max(1)
+    }
+
+    pub fn files_tree(&self) -> bool {
+        self.lazyjj_files_tree.unwrap_or(true)
+    }
+
+    pub fn details_wrap(&self) -> bool {
+        self.lazyjj_details_wrap.unwrap_or(true)
+    }
+
+    pub fn fold_details(&self) -> bool {
+        self.lazyjj_fold_details.unwrap_or(false)
+    }
+
+    pub fn primary_id(&self) -> PrimaryId {
+        self.lazyjj_primary_id.unwrap_or_default()
+    }
+
+    pub fn mouse_scroll(&self) -> MouseScroll {
+        self.lazyjj_mouse_scroll.unwrap_or_default()
+    }
+
+    pub fn tab_width(&self) -> usize {
+        self.lazyjj_tab_width.unwrap_or(4).max(1)
+    }
+
+    /// Whether git diffs get syntax highlighting. Never when colors are disabled
+    pub fn syntax_highlighting(&self) -> bool {
+        self.lazyjj_syntax_highlighting.unwrap_or(true) && self.color()
+    }
+
+    pub fn remote_url_template(&self) -> Option<&str> {
+        self.lazyjj_remote_url_template.as_deref()
+    }
+
+    pub fn refresh_on_focus(&self) -> bool {
+        self.lazyjj_refresh_on_focus.unwrap_or(true)
+    }
+
+    /// Interval between automatic refreshes in milliseconds, 0 when disabled
+    pub fn auto_refresh_ms(&self) -> u64 {
+        self.lazyjj_auto_refresh_ms.unwrap_or(0)
+    }
+
+    /// Names of the tabs in the order they are shown. Omitted tabs follow in the default order
+    pub fn tab_order(&self) -> &[String] {
+        self.lazyjj_tab_order.as_deref().unwrap_or_default()
+    }
+
+    /// Shortcuts which quit lazyjj. Ctrl+c always quits
+    pub fn quit_shortcuts(&self) -> Vec<Shortcut> {
+        match self.lazyjj_quit_key.as_ref() {
+            Some(Keybind::Single(shortcut)) => vec![*shortcut],
+            Some(Keybind::Multiple(shortcuts)) => shortcuts.clone(),
+            Some(Keybind::Enable(false)) => vec![],
+            None | Some(Keybind::Enable(true)) => vec![
+                Shortcut::new_mod_key(KeyModifiers::NONE, KeyCode::Char('q')),
+                Shortcut::new_mod_key(KeyModifiers::NONE, KeyCode::Esc),
+            ],
+        }
+    }
+
+    pub fn quit_double_press(&self) -> bool {
+        self.lazyjj_quit_double_press.unwrap_or(false)
+    }
+
+    pub fn confirm_destructive(&self) -> bool {
+        self.lazyjj_confirm_destructive.unwrap_or(true)
+    }
+
+    /// Preview pushes from the log tab with `jj git push --dry-run` before pushing
+    pub fn push_dry_run(&self) -> bool {
+        self.lazyjj_push_dry_run.unwrap_or(false)
+    }
+
     pub fn keybinds(&self) -> Option<&KeybindsConfig> {
         self.lazyjj_keybinds.as_ref()
     }
@@ -131,6 +323,27 @@ pub struct Env {
     pub jj_bin: String,
 }
 
+/// lazyjj was started in a directory which is not in a jj repo
+#[derive(Error, Debug, PartialEq, Eq)]
+#[error("No jj repository found in {path}")]
+pub struct NotARepoError {
+    pub path: String,
+    /// Whether the directory is in a git repo, which jj can be initialized in
+    pub git_repo: bool,
+}
+
+impl NotARepoError {
+    /// Recognize a failed `jj root` caused by the path not being in a jj repo
+    pub fn detect(path: &Path, stderr: &str) -> Option<Self> {
+        stderr
+            .contains("There is no jj repo")
+            .then(|| NotARepoError {
+                path: path.to_string_lossy().to_string(),
+                git_repo: path.ancestors().any(|dir| dir.join(".git").exists()),
+            })
+    }
+}
+
 impl Env {
     pub fn new(path: PathBuf, default_revset: Option<String>, jj_bin: String) -> Result<Env> {
         // Get jj repository root
@@ -140,7 +353,15 @@ impl Env {
             .current_dir(&path)
             .output()?;
         if !root_output.status.success() {
-            bail!("No jj repository found in {}", path.to_str().unwrap_or(""))
+            let stderr = String::from_utf8_lossy(&root_output.stderr);
+            if let Some(err) = NotARepoError::detect(&path, &stderr) {
+                bail!(err)
+            }
+            bail!(
+                "Failed to find the jj repository of {}: {}",
+                path.to_string_lossy(),
+                stderr.trim()
+            )
         }
         let root = String::from_utf8(root_output.stdout)?.remove_end_line();
 
@@ -178,6 +399,9 @@ impl Env {
                             .lazyjj
                             .as_ref()
                             .and_then(|lazyjj| lazyjj.highlight_color),
+                        lazyjj_color: config.lazyjj.as_ref().and_then(|lazyjj| lazyjj.color),
+                        lazyjj_theme: config.lazyjj.as_ref().and_then(|lazyjj| lazyjj.theme),
+                        lazyjj_colors: config.lazyjj.as_ref().and_then(|lazyjj| lazyjj.colors),
                         lazyjj_diff_format: config
                             .lazyjj
                             .as_ref()
@@ -195,6 +419,86 @@ impl Env {
                             .lazyjj
                             .as_ref()
                             .and_then(|lazyjj| lazyjj.layout_percent),
+                        lazyjj_log_compact: config
+                            .lazyjj
+                            .as_ref()
+                            .and_then(|lazyjj| lazyjj.log_compact),
+                        lazyjj_log_graph: config
+                            .lazyjj
+                            .as_ref()
+                            .and_then(|lazyjj| lazyjj.log_graph),
+                        lazyjj_log_template: config
+                            .lazyjj
+                            .as_ref()
+                            .and_then(|lazyjj| lazyjj.log_template.clone()),
+                        lazyjj_log_limit: config
+                            .lazyjj
+                            .as_ref()
+                            .and_then(|lazyjj| lazyjj.log_limit),
+                        lazyjj_log_load_more: config
+                            .lazyjj
+                            .as_ref()
+                            .and_then(|lazyjj| lazyjj.log_load_more),
+                        lazyjj_files_tree: config
+                            .lazyjj
+                            .as_ref()
+                            .and_then(|lazyjj| lazyjj.files_tree),
+                        lazyjj_details_wrap: config
+                            .lazyjj
+                            .as_ref()
+                            .and_then(|lazyjj| lazyjj.details_wrap),
+                        lazyjj_fold_details: config
+                            .lazyjj
+                            .as_ref()
+                            .and_then(|lazyjj| lazyjj.fold_details),
+                        lazyjj_primary_id: config
+                            .lazyjj
+                            .as_ref()
+                            .and_then(|lazyjj| lazyjj.primary_id),
+                        lazyjj_mouse_scroll: config
+                            .lazyjj
+                            .as_ref()
+                            .and_then(|lazyjj| lazyjj.mouse_scroll),
+                        lazyjj_remote_url_template: config
+                            .lazyjj
+                            .as_ref()
+                            .and_then(|lazyjj| lazyjj.remote_url_template.clone()),
+                        lazyjj_tab_width: config
+                            .lazyjj
+                            .as_ref()
+                            .and_then(|lazyjj| lazyjj.tab_width),
+                        lazyjj_syntax_highlighting: config
+                            .lazyjj
+                            .as_ref()
+                            .and_then(|lazyjj| lazyjj.syntax_highlighting),
+                        lazyjj_refresh_on_focus: config
+                            .lazyjj
+                            .as_ref()
+                            .and_then(|lazyjj| lazyjj.refresh_on_focus),
+                        lazyjj_auto_refresh_ms: config
+                            .lazyjj
+                            .as_ref()
+                            .and_then(|lazyjj| lazyjj.auto_refresh_ms),
+                        lazyjj_tab_order: config
+                            .lazyjj
+                            .as_ref()
+                            .and_then(|lazyjj| lazyjj.tab_order.clone()),
+                        lazyjj_quit_key: config
+                            .lazyjj
+                            .as_ref()
+                            .and_then(|lazyjj| lazyjj.quit_key.clone()),
+                        lazyjj_quit_double_press: config
+                            .lazyjj
+                            .as_ref()
+                            .and_then(|lazyjj| lazyjj.quit_double_press),
+                        lazyjj_confirm_destructive: config
+                            .lazyjj
+                            .as_ref()
+                            .and_then(|lazyjj| lazyjj.confirm_destructive),
+                        lazyjj_push_dry_run: config
+                            .lazyjj
+                            .as_ref()
+                            .and_then(|lazyjj| lazyjj.push_dry_run),
                         lazyjj_keybinds: config
                             .lazyjj
                             .as_ref()
@@ -252,6 +556,55 @@ impl DiffFormat {
     }
 }
 
+/// Which id to show first when both change id and commit id are displayed
+#[derive(Clone, Debug, Deserialize, Default, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum PrimaryId {
+    #[default]
+    Change,
+    Commit,
+}
+
+/// What the mouse wheel does over the log
+#[derive(Clone, Debug, Deserialize, Default, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum MouseScroll {
+    /// Move the selection
+    #[default]
+    Selection,
+    /// Scroll the list, leaving the selection where it is
+    List,
+}
+
+/// Built-in color scheme
+#[derive(Clone, Debug, Deserialize, Default, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub enum Theme {
+    #[default]
+    Default,
+    /// Bright colors on the terminal background, and black on white for highlighted items
+    HighContrast,
+}
+
+/// Colors replacing parts of the default appearance. Unset colors keep the default appearance
+#[derive(Clone, Debug, Deserialize, Default, Copy, PartialEq)]
+#[serde(rename_all = "kebab-case")]
+pub struct ThemeColors {
+    /// Lines drawn with box-drawing characters: borders and the log graph
+    pub border_fg: Option<Color>,
+    pub border_bg: Option<Color>,
+    /// Dimmed text, like key hints and help lines
+    pub help_fg: Option<Color>,
+    pub help_bg: Option<Color>,
+    /// Added and removed text in diffs
+    pub diff_added: Option<Color>,
+    pub diff_removed: Option<Color>,
+    /// Selected change and other highlighted items. The background defaults to
+    /// `lazyjj.highlight-color`
+    pub selected_fg: Option<Color>,
+    pub selected_bg: Option<Color>,
+}
+
 #[derive(Clone, Debug, Deserialize, Default, Copy, PartialEq)]
 #[serde(rename_all = "kebab-case")]
 pub enum JJLayout {
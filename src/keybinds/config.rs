@@ -26,21 +26,58 @@ pub struct LogTabKeybindsConfig {
     pub scroll_down_half: Option<Keybind>,
     pub scroll_up_half: Option<Keybind>,
 
+    pub toggle_compact: Option<Keybind>,
+    pub toggle_graph: Option<Keybind>,
+    pub toggle_details: Option<Keybind>,
+
     pub focus_current: Option<Keybind>,
     pub toggle_diff_format: Option<Keybind>,
+    pub cycle_diff_parent: Option<Keybind>,
+    pub toggle_interdiff: Option<Keybind>,
+    pub cycle_details_view: Option<Keybind>,
 
     pub refresh: Option<Keybind>,
     pub create_new: Option<Keybind>,
     pub create_new_describe: Option<Keybind>,
+    pub create_new_no_edit: Option<Keybind>,
     pub squash: Option<Keybind>,
     pub squash_ignore_immutable: Option<Keybind>,
+    pub toggle_squash_keep_emptied: Option<Keybind>,
     pub edit_change: Option<Keybind>,
     pub edit_change_ignore_immutable: Option<Keybind>,
+    pub diff_edit: Option<Keybind>,
     pub abandon: Option<Keybind>,
+    pub restore_working_copy: Option<Keybind>,
     pub describe: Option<Keybind>,
+    pub describe_in_editor: Option<Keybind>,
+    pub bulk_describe: Option<Keybind>,
     pub edit_revset: Option<Keybind>,
+    pub edit_path_filter: Option<Keybind>,
     pub set_bookmark: Option<Keybind>,
+    pub set_bookmark_current: Option<Keybind>,
+    pub create_bookmark: Option<Keybind>,
+    pub move_bookmark: Option<Keybind>,
     pub open_files: Option<Keybind>,
+    pub open_conflicts: Option<Keybind>,
+    pub rebase_revset: Option<Keybind>,
+    pub interactive_rebase: Option<Keybind>,
+    pub split: Option<Keybind>,
+    pub toggle_mark: Option<Keybind>,
+    pub duplicate: Option<Keybind>,
+    pub backout: Option<Keybind>,
+    pub sign: Option<Keybind>,
+    pub unsign: Option<Keybind>,
+    pub create_merge: Option<Keybind>,
+    pub parallelize: Option<Keybind>,
+    pub absorb: Option<Keybind>,
+    pub fix: Option<Keybind>,
+    pub copy_change_id: Option<Keybind>,
+    pub copy_commit_id: Option<Keybind>,
+    pub search: Option<Keybind>,
+    pub search_next: Option<Keybind>,
+    pub search_previous: Option<Keybind>,
+    pub jump_to_change: Option<Keybind>,
+    pub open_in_browser: Option<Keybind>,
 
     pub push: Option<Keybind>,
     pub push_new: Option<Keybind>,
@@ -24,24 +24,61 @@ pub enum LogTabEvent {
     ScrollDownHalf,
     ScrollUpHalf,
 
+    ToggleCompact,
+    ToggleGraph,
+    ToggleDetails,
+
     FocusCurrent,
     ToggleDiffFormat,
+    CycleDiffParent,
+    ToggleInterdiff,
+    CycleDetailsView,
 
     Refresh,
     CreateNew {
         describe: bool,
     },
+    CreateNewNoEdit,
     Squash {
         ignore_immutable: bool,
     },
+    ToggleSquashKeepEmptied,
     EditChange {
         ignore_immutable: bool,
     },
+    DiffEdit,
     Abandon,
+    RestoreWorkingCopy,
     Describe,
+    DescribeInEditor,
+    BulkDescribe,
     EditRevset,
+    EditPathFilter,
     SetBookmark,
+    SetBookmarkAtCurrent,
+    CreateBookmark,
+    MoveBookmark,
     OpenFiles,
+    OpenConflicts,
+    RebaseRevset,
+    InteractiveRebase,
+    Split,
+    ToggleMark,
+    Duplicate,
+    Backout,
+    Sign,
+    Unsign,
+    CreateMerge,
+    Parallelize,
+    Absorb,
+    Fix,
+    CopyChangeId,
+    CopyCommitId,
+    Search,
+    SearchNext,
+    SearchPrevious,
+    JumpToChange,
+    OpenInBrowser,
 
     Push {
         all_bookmarks: bool,
@@ -70,22 +107,58 @@ impl Default for LogTabKeybinds {
             LogTabEvent::ScrollUp => "up",
             LogTabEvent::ScrollDownHalf => "shift+j",
             LogTabEvent::ScrollUpHalf => "shift+k",
+            LogTabEvent::ToggleCompact => "v",
+            LogTabEvent::ToggleGraph => "shift+l",
+            LogTabEvent::ToggleDetails => "tab",
             LogTabEvent::FocusCurrent => "@",
             // todo: move to DetailsKeybindings
             LogTabEvent::ToggleDiffFormat => "w",
+            LogTabEvent::CycleDiffParent => "m",
+            LogTabEvent::ToggleInterdiff => "=",
+            LogTabEvent::CycleDetailsView => "shift+m",
             LogTabEvent::Refresh => "shift+r",
             LogTabEvent::Refresh => "f5",
             LogTabEvent::CreateNew { describe: false } => "n",
             LogTabEvent::CreateNew { describe: true } => "shift+n",
+            LogTabEvent::CreateNewNoEdit => "alt+n",
             LogTabEvent::Squash { ignore_immutable: false } => "s",
             LogTabEvent::Squash { ignore_immutable: true } => "shift+s",
+            LogTabEvent::ToggleSquashKeepEmptied => "ctrl+k",
             LogTabEvent::EditChange { ignore_immutable: false } => "e",
             LogTabEvent::EditChange { ignore_immutable: true } => "shift+e",
+            LogTabEvent::DiffEdit => "z",
             LogTabEvent::Abandon => "a",
+            LogTabEvent::RestoreWorkingCopy => "shift+x",
             LogTabEvent::Describe => "d",
+            LogTabEvent::DescribeInEditor => "ctrl+x",
+            LogTabEvent::BulkDescribe => "shift+d",
             LogTabEvent::EditRevset => "r",
+            LogTabEvent::EditPathFilter => "shift+h",
             LogTabEvent::SetBookmark => "b",
+            LogTabEvent::SetBookmarkAtCurrent => "shift+b",
+            LogTabEvent::CreateBookmark => "alt+b",
+            LogTabEvent::MoveBookmark => "g",
             LogTabEvent::OpenFiles => "enter",
+            LogTabEvent::OpenConflicts => "c",
+            LogTabEvent::RebaseRevset => "ctrl+r",
+            LogTabEvent::InteractiveRebase => "shift+i",
+            LogTabEvent::Split => "x",
+            LogTabEvent::ToggleMark => "t",
+            LogTabEvent::Duplicate => "y",
+            LogTabEvent::Backout => "ctrl+v",
+            LogTabEvent::Sign => "ctrl+l",
+            LogTabEvent::Unsign => "alt+l",
+            LogTabEvent::CreateMerge => "ctrl+n",
+            LogTabEvent::Parallelize => "shift+t",
+            LogTabEvent::Absorb => "shift+a",
+            LogTabEvent::Fix => "ctrl+t",
+            LogTabEvent::CopyChangeId => "shift+c",
+            LogTabEvent::CopyCommitId => "ctrl+o",
+            LogTabEvent::Search => "/",
+            LogTabEvent::SearchNext => "]",
+            LogTabEvent::SearchPrevious => "[",
+            LogTabEvent::JumpToChange => "ctrl+g",
+            LogTabEvent::OpenInBrowser => "o",
             event_push(false, false) => "p",
             event_push(false, true) => "ctrl+p",
             event_push(true, false) => "shift+p",
@@ -117,20 +190,56 @@ impl LogTabKeybinds {
             LogTabEvent::ScrollUp => config.scroll_up,
             LogTabEvent::ScrollDownHalf => config.scroll_down_half,
             LogTabEvent::ScrollUpHalf => config.scroll_up_half,
+            LogTabEvent::ToggleCompact => config.toggle_compact,
+            LogTabEvent::ToggleGraph => config.toggle_graph,
+            LogTabEvent::ToggleDetails => config.toggle_details,
             LogTabEvent::FocusCurrent => config.focus_current,
             LogTabEvent::ToggleDiffFormat => config.toggle_diff_format,
+            LogTabEvent::CycleDiffParent => config.cycle_diff_parent,
+            LogTabEvent::ToggleInterdiff => config.toggle_interdiff,
+            LogTabEvent::CycleDetailsView => config.cycle_details_view,
             LogTabEvent::Refresh => config.refresh,
             LogTabEvent::CreateNew { describe: false } => config.create_new,
             LogTabEvent::CreateNew { describe: true } => config.create_new_describe,
+            LogTabEvent::CreateNewNoEdit => config.create_new_no_edit,
             LogTabEvent::Squash { ignore_immutable: false } => config.squash,
             LogTabEvent::Squash { ignore_immutable: true } => config.squash_ignore_immutable,
+            LogTabEvent::ToggleSquashKeepEmptied => config.toggle_squash_keep_emptied,
             LogTabEvent::EditChange { ignore_immutable: false } => config.edit_change,
             LogTabEvent::EditChange { ignore_immutable: true } => config.edit_change_ignore_immutable,
+            LogTabEvent::DiffEdit => config.diff_edit,
             LogTabEvent::Abandon => config.abandon,
+            LogTabEvent::RestoreWorkingCopy => config.restore_working_copy,
             LogTabEvent::Describe => config.describe,
+            LogTabEvent::DescribeInEditor => config.describe_in_editor,
+            LogTabEvent::BulkDescribe => config.bulk_describe,
             LogTabEvent::EditRevset => config.edit_revset,
+            LogTabEvent::EditPathFilter => config.edit_path_filter,
             LogTabEvent::SetBookmark => config.set_bookmark,
+            LogTabEvent::SetBookmarkAtCurrent => config.set_bookmark_current,
+            LogTabEvent::CreateBookmark => config.create_bookmark,
+            LogTabEvent::MoveBookmark => config.move_bookmark,
             LogTabEvent::OpenFiles => config.open_files,
+            LogTabEvent::OpenConflicts => config.open_conflicts,
+            LogTabEvent::RebaseRevset => config.rebase_revset,
+            LogTabEvent::InteractiveRebase => config.interactive_rebase,
+            LogTabEvent::Split => config.split,
+            LogTabEvent::ToggleMark => config.toggle_mark,
+            LogTabEvent::Duplicate => config.duplicate,
+            LogTabEvent::Backout => config.backout,
+            LogTabEvent::Sign => config.sign,
+            LogTabEvent::Unsign => config.unsign,
+            LogTabEvent::CreateMerge => config.create_merge,
+            LogTabEvent::Parallelize => config.parallelize,
+            LogTabEvent::Absorb => config.absorb,
+            LogTabEvent::Fix => config.fix,
+            LogTabEvent::CopyChangeId => config.copy_change_id,
+            LogTabEvent::CopyCommitId => config.copy_commit_id,
+            LogTabEvent::Search => config.search,
+            LogTabEvent::SearchNext => config.search_next,
+            LogTabEvent::SearchPrevious => config.search_previous,
+            LogTabEvent::JumpToChange => config.jump_to_change,
+            LogTabEvent::OpenInBrowser => config.open_in_browser,
             event_push(false, false) => config.push,
             event_push(false, true) => config.push_new,
             event_push(true, false) => config.push_all,
@@ -147,18 +256,54 @@ impl LogTabKeybinds {
             LogTabEvent::ScrollUp => "scroll up",
             LogTabEvent::ScrollDownHalf => "scroll down by ½ page",
             LogTabEvent::ScrollUpHalf => "scroll up by ½ page",
+            LogTabEvent::ToggleCompact => "toggle compact log",
+            LogTabEvent::ToggleGraph => "toggle log graph",
+            LogTabEvent::ToggleDetails => "show/hide details panel",
             LogTabEvent::OpenFiles => "see files",
+            LogTabEvent::OpenConflicts => "see conflicts in the repo",
+            LogTabEvent::RebaseRevset => "rebase revset onto change",
+            LogTabEvent::InteractiveRebase => "reorder, squash or drop the stack below the change",
+            LogTabEvent::Split => "split change, picking files",
+            LogTabEvent::ToggleMark => "mark change as merge parent or duplicate destination",
+            LogTabEvent::Duplicate => "duplicate change onto marked changes or in place",
+            LogTabEvent::Backout => "back out the change on top of @-",
+            LogTabEvent::Sign => "sign change",
+            LogTabEvent::Unsign => "drop the change's signature",
+            LogTabEvent::CreateMerge => "new merge of the marked changes",
+            LogTabEvent::Parallelize => "parallelize the marked changes, or the range from the marked one",
+            LogTabEvent::Absorb => "absorb @ into the changes which last touched its lines",
+            LogTabEvent::Fix => "run formatters on the change and its descendants",
+            LogTabEvent::CopyChangeId => "copy change id",
+            LogTabEvent::CopyCommitId => "copy commit id",
+            LogTabEvent::Search => "search the log",
+            LogTabEvent::SearchNext => "next change matching the search",
+            LogTabEvent::SearchPrevious => "previous change matching the search",
+            LogTabEvent::JumpToChange => "jump to a change or commit id",
+            LogTabEvent::OpenInBrowser => "open change on remote web UI",
             LogTabEvent::FocusCurrent => "current change",
+            LogTabEvent::CycleDiffParent => "cycle merge parent to diff against",
+            LogTabEvent::ToggleInterdiff => "toggle diff between the two marked changes",
+            LogTabEvent::CycleDetailsView => "cycle details between full, description and diff",
             LogTabEvent::EditRevset => "set revset",
+            LogTabEvent::EditPathFilter => "show history of a path",
             LogTabEvent::Describe => "describe change",
+            LogTabEvent::DescribeInEditor => "describe change in $EDITOR",
+            LogTabEvent::BulkDescribe => "describe all changes without description",
             LogTabEvent::EditChange { ignore_immutable: false } => "edit change",
             LogTabEvent::EditChange { ignore_immutable: true } => "edit change ignoring immutability",
+            LogTabEvent::DiffEdit => "edit the diff of the change in the diff editor",
             LogTabEvent::CreateNew { describe: false } => "new change",
             LogTabEvent::CreateNew { describe: true } => "new with message",
+            LogTabEvent::CreateNewNoEdit => "new without moving the working copy",
             LogTabEvent::Abandon => "abandon change",
+            LogTabEvent::RestoreWorkingCopy => "restore @ from change, discarding its edits",
             LogTabEvent::Squash { ignore_immutable: false } => "squash @ into the selected change",
             LogTabEvent::Squash { ignore_immutable: true } => "squash @ into the selected change ignoring immutability",
+            LogTabEvent::ToggleSquashKeepEmptied => "toggle keeping the emptied change on squash",
             LogTabEvent::SetBookmark => "set bookmark",
+            LogTabEvent::SetBookmarkAtCurrent => "set bookmark to @",
+            LogTabEvent::CreateBookmark => "create new bookmark",
+            LogTabEvent::MoveBookmark => "move an existing bookmark to the change",
             LogTabEvent::Fetch { all_remotes: false } => "git fetch",
             LogTabEvent::Fetch { all_remotes: true } => "git fetch all remotes",
             event_push(false, false) => "git push",
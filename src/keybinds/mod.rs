@@ -97,8 +97,10 @@ impl FromStr for Shortcut {
             match s {
                 "ctrl" => modifiers |= KeyModifiers::CONTROL,
                 "shift" => modifiers |= KeyModifiers::SHIFT,
+                "alt" => modifiers |= KeyModifiers::ALT,
                 "enter" => key = Some(KeyCode::Enter),
                 "esc" => key = Some(KeyCode::Esc),
+                "tab" => key = Some(KeyCode::Tab),
                 "left" => key = Some(KeyCode::Left),
                 "right" => key = Some(KeyCode::Right),
                 "up" => key = Some(KeyCode::Up),
@@ -128,13 +130,16 @@ impl FromStr for Shortcut {
 
 impl Display for Shortcut {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let mut parts = Vec::with_capacity(3);
+        let mut parts = Vec::with_capacity(4);
         if self.modifiers.contains(KeyModifiers::CONTROL) {
             parts.push("Control".to_string());
         }
         if self.modifiers.contains(KeyModifiers::SHIFT) {
             parts.push("Shift".to_string());
         }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            parts.push("Alt".to_string());
+        }
         let k = match self.key {
             KeyCode::Enter => "Enter".to_string(),
             KeyCode::Left => "Left".to_string(),
@@ -144,6 +149,7 @@ impl Display for Shortcut {
             KeyCode::F(n) => format!("F{n}"),
             KeyCode::Char(c) => c.to_string(),
             KeyCode::Esc => "Esc".to_string(),
+            KeyCode::Tab => "Tab".to_string(),
             _ => "Unknown".to_string(),
         };
         parts.push(k);
@@ -180,6 +186,7 @@ mod tests {
     fn test_shortcut_from_str() {
         let ctrl = KeyModifiers::CONTROL;
         let shift = KeyModifiers::SHIFT;
+        let alt = KeyModifiers::ALT;
         let ctrl_shift = ctrl | shift;
 
         let table = [
@@ -193,6 +200,7 @@ mod tests {
             ("ctrl+Q", Ok(Shortcut::new_mod_char(ctrl, 'q'))),
             ("ctrl+ctrl+q", Ok(Shortcut::new_mod_char(ctrl, 'q'))),
             ("ctrl+shift+q", Ok(Shortcut::new_mod_char(ctrl_shift, 'q'))),
+            ("alt+q", Ok(Shortcut::new_mod_char(alt, 'q'))),
             (
                 "ctrl+shift+f5",
                 Ok(Shortcut::new_mod_key(ctrl_shift, KeyCode::F(5))),
@@ -207,6 +215,7 @@ mod tests {
                 Ok(Shortcut::new_mod_key(ctrl, KeyCode::Enter)),
             ),
             ("esc", Ok(Shortcut::new_key(KeyCode::Esc))),
+            ("tab", Ok(Shortcut::new_key(KeyCode::Tab))),
             ("left", Ok(Shortcut::new_key(KeyCode::Left))),
             ("right", Ok(Shortcut::new_key(KeyCode::Right))),
             ("up", Ok(Shortcut::new_key(KeyCode::Up))),
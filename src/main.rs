@@ -5,7 +5,7 @@ use std::{
     fs::{OpenOptions, canonicalize},
     io::{self, ErrorKind},
     process::Command,
-    time::Instant,
+    time::{Duration, Instant},
 };
 
 use anyhow::{Context, Result, bail};
@@ -16,8 +16,8 @@ use ratatui::{
     crossterm::{
         event::{
             self, DisableFocusChange, DisableMouseCapture, EnableFocusChange, EnableMouseCapture,
-            Event, KeyboardEnhancementFlags, MouseEvent, MouseEventKind,
-            PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
+            Event, KeyCode, KeyEventKind, KeyModifiers, KeyboardEnhancementFlags, MouseEvent,
+            MouseEventKind, PopKeyboardEnhancementFlags, PushKeyboardEnhancementFlags,
         },
         execute,
         terminal::{
@@ -41,8 +41,11 @@ mod ui;
 use crate::{
     app::App,
     commander::Commander,
-    env::Env,
-    ui::{ComponentAction, ui},
+    env::{Env, NotARepoError},
+    ui::{
+        ComponentAction, not_a_repo::draw_not_a_repo, styles::apply_color_config, ui,
+        utils::take_background_done,
+    },
 };
 
 /// Simple program to greet a person
@@ -127,15 +130,26 @@ fn main() -> Result<()> {
     }
 
     // Setup environment
-    let env = Env::new(path, args.revisions, jj_bin)?;
+    let env = match Env::new(path, args.revisions, jj_bin) {
+        Ok(env) => env,
+        Err(err) => match err.downcast::<NotARepoError>() {
+            Ok(err) => return show_not_a_repo(&err),
+            Err(err) => return Err(err),
+        },
+    };
     let mut commander = Commander::new(&env);
 
     if !args.ignore_jj_version {
         commander.check_jj_version()?;
     }
 
+    // Fail early with guidance if the working copy can't be used
+    commander.get_current_head()?;
+
     // Setup app
     let mut app = App::new(env.clone())?;
+    app.refresh_header(&mut commander);
+    app.refresh_workspace(&mut commander);
 
     let mut terminal = setup_terminal()?;
     install_panic_hook();
@@ -155,17 +169,43 @@ fn run_app<B: Backend>(
 ) -> Result<()> {
     let mut start_time = Instant::now();
     loop {
+        // Leave the UI while a program, e.g. an editor, uses the terminal
+        if let Some(task) = app.suspended.take() {
+            restore_terminal()?;
+            let result = task(commander);
+            init_terminal()?;
+            // The program drew over the UI, so draw everything again
+            terminal.clear()?;
+            if let Err(err) = result.and_then(|action| app.handle_action(action, commander)) {
+                app.show_read_only_error(err)?;
+            }
+        }
+
         // Draw
         let mut terminal_draw_res = Ok(());
         terminal.draw(|f| {
-            // Update current tab
+            // Update current tab, then the popup over it
             let update_span = trace_span!("update");
             terminal_draw_res = update_span.in_scope(|| -> Result<()> {
-                if let Some(component_action) =
-                    app.get_or_init_current_tab(commander)?.update(commander)?
-                {
-                    app.handle_action(component_action, commander)?;
+                let update_res = app
+                    .get_or_init_current_tab(commander)?
+                    .update(commander)
+                    .and_then(|component_action| match component_action {
+                        Some(component_action) => app.handle_action(component_action, commander),
+                        None => Ok(()),
+                    })
+                    .and_then(|()| match app.popup.as_mut() {
+                        Some(popup) => popup.update(commander),
+                        None => Ok(None),
+                    })
+                    .and_then(|component_action| match component_action {
+                        Some(component_action) => app.handle_action(component_action, commander),
+                        None => Ok(()),
+                    });
+                if let Err(err) = update_res {
+                    app.show_read_only_error(err)?;
                 }
+                app.sync_header(commander);
 
                 Ok(())
             });
@@ -189,6 +229,8 @@ fn run_app<B: Backend>(
                     };
                     f.render_widget(paragraph, position);
                 }
+
+                apply_color_config(f.buffer_mut(), &app.env.config);
                 Ok(())
             });
         })?;
@@ -197,24 +239,43 @@ fn run_app<B: Backend>(
         // Input
         let input_spawn = trace_span!("input");
         let event = loop {
+            // Wake up to draw the results of background tasks and pending work
+            if !event::poll(Duration::from_millis(50))? {
+                let redraw = match app.tick(commander) {
+                    Ok(redraw) => redraw,
+                    Err(err) => {
+                        app.show_read_only_error(err)?;
+                        true
+                    }
+                };
+                if take_background_done() || redraw {
+                    break None;
+                }
+                continue;
+            }
             match event::read()? {
                 event::Event::FocusLost => continue,
                 Event::Mouse(MouseEvent {
                     kind: MouseEventKind::Moved,
                     ..
                 }) => continue,
-                event => break event,
+                event => break Some(event),
             }
         };
+        let Some(event) = event else {
+            continue;
+        };
 
         start_time = Instant::now();
 
         let should_stop = input_spawn.in_scope(|| -> Result<bool> {
-            if app.input(event, commander)? {
-                return Ok(true);
+            match app.input(event, commander) {
+                Ok(should_stop) => Ok(should_stop),
+                Err(err) => {
+                    app.show_read_only_error(err)?;
+                    Ok(false)
+                }
             }
-
-            Ok(false)
         })?;
 
         if should_stop {
@@ -223,7 +284,40 @@ fn run_app<B: Backend>(
     }
 }
 
+/// Explain that lazyjj was started outside of a jj repo, until quitting
+fn show_not_a_repo(err: &NotARepoError) -> Result<()> {
+    info!("{}", err);
+    let mut terminal = setup_terminal()?;
+    install_panic_hook();
+
+    let res = run_not_a_repo(&mut terminal, err);
+    restore_terminal()?;
+    res
+}
+
+fn run_not_a_repo<B: Backend>(terminal: &mut Terminal<B>, err: &NotARepoError) -> Result<()> {
+    loop {
+        terminal.draw(|f| draw_not_a_repo(f, err))?;
+
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+            && (matches!(key.code, KeyCode::Char('q') | KeyCode::Esc)
+                || (key.code == KeyCode::Char('c')
+                    && key.modifiers.contains(KeyModifiers::CONTROL)))
+        {
+            return Ok(());
+        }
+    }
+}
+
 fn setup_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
+    init_terminal()?;
+    let backend = CrosstermBackend::new(io::stdout());
+    Ok(Terminal::new(backend)?)
+}
+
+/// Enter raw mode and the alternate screen to draw the UI
+fn init_terminal() -> Result<()> {
     enable_raw_mode()?;
     let mut stdout = io::stdout();
     execute!(
@@ -241,8 +335,7 @@ fn setup_terminal() -> Result<Terminal<CrosstermBackend<io::Stdout>>> {
         )?;
     }
 
-    let backend = CrosstermBackend::new(stdout);
-    Ok(Terminal::new(backend)?)
+    Ok(())
 }
 
 fn restore_terminal() -> Result<()> {
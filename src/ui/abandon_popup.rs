@@ -0,0 +1,201 @@
+use ansi_to_tui::IntoText;
+use anyhow::Result;
+use itertools::Itertools;
+use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEventKind},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+};
+
+use crate::{
+    ComponentInputResult,
+    commander::{Commander, ids::CommitId, log::Head},
+    ui::{
+        Component, ComponentAction, message_popup::MessagePopup, styles::create_popup_block,
+        utils::centered_rect_line_height,
+    },
+};
+
+/// Abandon a change, then show the change which takes its place in the log
+pub fn abandon(
+    commander: &mut Commander,
+    head: &Head,
+    retain_bookmarks: bool,
+) -> Result<ComponentAction> {
+    let is_current_head = *head == commander.get_current_head()?;
+    let head_parent = commander.get_commit_parent(&head.commit_id)?;
+    commander.run_abandon(&head.commit_id, retain_bookmarks)?;
+
+    let mut actions = vec![];
+    if is_current_head {
+        let head = commander.get_current_head()?;
+        actions.push(ComponentAction::ViewLog(head.clone()));
+        actions.push(ComponentAction::ChangeHead(head));
+    } else {
+        actions.push(ComponentAction::ViewLog(head_parent));
+    }
+    Ok(ComponentAction::Multiple(actions))
+}
+
+/// Abandon several changes at once, then show the parent of the earliest of them in the log.
+/// When @ is abandoned, the new working copy is shown instead
+pub fn abandon_many(commander: &mut Commander, heads: &[Head]) -> Result<ComponentAction> {
+    let commit_ids: Vec<CommitId> = heads.iter().map(|head| head.commit_id.clone()).collect();
+    let current_head = commander.get_current_head()?;
+    let revset = commit_ids.iter().map(CommitId::as_str).join(" | ");
+    let survivor = commander
+        .get_revset_commits(&format!("roots({revset})-"))?
+        .into_iter()
+        .next()
+        .map(|(head, _)| head);
+    // jj abandons all of them or none, so the log is unchanged after an error
+    if let Err(err) = commander.run_abandon_many(&commit_ids) {
+        return Ok(ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+            title: "Abandon".into(),
+            messages: format!("{err:#}").into_text()?,
+            text_align: None,
+        }))));
+    }
+
+    // The marks would point to abandoned changes
+    let mut actions = vec![ComponentAction::ClearMarks()];
+    if heads
+        .iter()
+        .any(|head| head.change_id == current_head.change_id)
+    {
+        let head = commander.get_current_head()?;
+        actions.push(ComponentAction::ViewLog(head.clone()));
+        actions.push(ComponentAction::ChangeHead(head));
+    } else if let Some(survivor) = survivor {
+        actions.push(ComponentAction::ViewLog(survivor));
+    }
+    Ok(ComponentAction::Multiple(actions))
+}
+
+/// Lines listing the changes to abandon at once, by their ids and descriptions
+pub fn abandon_many_lines(changes: &[(Head, String)]) -> Vec<Line<'static>> {
+    let mut lines = vec![Line::from(format!(
+        "Are you sure you want to abandon these {} changes?",
+        changes.len()
+    ))];
+    for (head, description) in changes {
+        let description = description.lines().next().unwrap_or_default();
+        lines.push(Line::from(vec![
+            Span::raw(head.change_id.as_str().to_owned()).fg(Color::Magenta),
+            Span::raw(" "),
+            if description.is_empty() {
+                Span::raw("(no description set)").fg(Color::Yellow)
+            } else {
+                Span::raw(description.to_owned()).bold()
+            },
+        ]));
+    }
+    lines
+}
+
+/// Lines naming the change to abandon, by its id and description
+pub fn abandon_lines(head: &Head, description: &str) -> Vec<Line<'static>> {
+    let description = description.lines().next().unwrap_or_default();
+    vec![
+        Line::from("Are you sure you want to abandon this change?"),
+        Line::from(format!("Change: {}", head.change_id.as_str())),
+        if description.is_empty() {
+            Line::from("(no description set)").fg(Color::Yellow)
+        } else {
+            Line::from(description.to_owned()).bold()
+        },
+    ]
+}
+
+/// Confirmation to abandon a change with bookmarks, choosing whether the bookmarks
+/// are moved to the parent or deleted.
+pub struct AbandonPopup {
+    head: Head,
+    description: String,
+    bookmarks: Vec<String>,
+}
+
+impl AbandonPopup {
+    pub fn new(head: Head, description: String, bookmarks: Vec<String>) -> Self {
+        Self {
+            head,
+            description,
+            bookmarks,
+        }
+    }
+
+    fn abandon(
+        &self,
+        commander: &mut Commander,
+        retain_bookmarks: bool,
+    ) -> Result<ComponentInputResult> {
+        Ok(ComponentInputResult::HandledAction(
+            ComponentAction::Multiple(vec![
+                ComponentAction::SetPopup(None),
+                abandon(commander, &self.head, retain_bookmarks)?,
+            ]),
+        ))
+    }
+}
+
+impl Component for AbandonPopup {
+    fn draw(&mut self, f: &mut ratatui::prelude::Frame<'_>, area: Rect) -> Result<()> {
+        let block = create_popup_block("Abandon");
+        let area = centered_rect_line_height(area, 50, 11);
+        f.render_widget(Clear, area);
+        f.render_widget(&block, area);
+
+        let popup_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Fill(1), Constraint::Length(2)])
+            .split(block.inner(area));
+
+        let mut lines = abandon_lines(&self.head, &self.description);
+        lines.extend([
+            Line::from(""),
+            Line::from(format!(
+                "Bookmarks pointing to this change: {}",
+                self.bookmarks.join(", ")
+            ))
+            .fg(Color::Yellow),
+        ]);
+        f.render_widget(
+            Paragraph::new(lines)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: false }),
+            popup_chunks[0],
+        );
+
+        let help = Paragraph::new(vec![
+            "r: abandon, moving bookmarks to the parent | d: abandon, deleting bookmarks | Escape: cancel"
+                .into(),
+        ])
+        .fg(Color::DarkGray)
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::TOP)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+        f.render_widget(help, popup_chunks[1]);
+
+        Ok(())
+    }
+
+    fn input(&mut self, commander: &mut Commander, event: Event) -> Result<ComponentInputResult> {
+        if let Event::Key(key) = event
+            && key.kind == KeyEventKind::Press
+        {
+            match key.code {
+                KeyCode::Char('r') => return self.abandon(commander, true),
+                KeyCode::Char('d') => return self.abandon(commander, false),
+                _ => return Ok(ComponentInputResult::NotHandled),
+            }
+        }
+
+        Ok(ComponentInputResult::NotHandled)
+    }
+}
@@ -0,0 +1,185 @@
+use anyhow::Result;
+use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEventKind},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+use crate::{
+    ComponentInputResult,
+    commander::{Commander, files::AnnotatedLine, log::Head},
+    ui::{
+        Component, ComponentAction, panel::DetailsPanel, styles::create_popup_block,
+        utils::centered_rect,
+    },
+};
+
+/// Lines shown for very large files, annotating more would make the popup slow to draw
+const MAX_ANNOTATE_LINES: usize = 20_000;
+
+/// Width of the author column
+const AUTHOR_WIDTH: usize = 16;
+
+/// Popup showing each line of a file with the change and author which last modified it
+pub struct AnnotatePopup {
+    path: String,
+    lines: Vec<AnnotatedLine>,
+    text: Text<'static>,
+    /// Why the file is not annotated, or only partly
+    message: Option<String>,
+    panel: DetailsPanel,
+}
+
+fn annotated_line(line: &AnnotatedLine) -> Line<'static> {
+    let author: String = line.author.chars().take(AUTHOR_WIDTH).collect();
+    Line::from(vec![
+        Span::raw(format!("{:8} ", line.change_id)).fg(Color::Magenta),
+        Span::raw(format!("{author:AUTHOR_WIDTH$} ")).fg(Color::Yellow),
+        Span::raw(line.content.clone()),
+    ])
+}
+
+impl AnnotatePopup {
+    pub fn new(commander: &mut Commander, head: &Head, path: &str) -> Self {
+        let (mut lines, mut message) = match commander.get_file_annotate(head, path) {
+            Ok(lines) => (lines, None),
+            Err(err) => (vec![], Some(err.to_string())),
+        };
+        if lines.iter().any(|line| line.content.contains('\0')) {
+            lines.clear();
+            message = Some("Binary file, nothing to annotate".to_owned());
+        } else if lines.len() > MAX_ANNOTATE_LINES {
+            message = Some(format!(
+                "Large file, showing the first {MAX_ANNOTATE_LINES} of {} lines",
+                lines.len()
+            ));
+            lines.truncate(MAX_ANNOTATE_LINES);
+        }
+
+        let mut panel = DetailsPanel::new()
+            .with_highlight_color(commander.env.config.highlight_color())
+            .with_tab_width(commander.env.config.tab_width())
+            .with_wrap(false);
+        if !lines.is_empty() {
+            panel.select_line(0);
+        }
+
+        Self {
+            path: path.to_owned(),
+            text: Text::from(lines.iter().map(annotated_line).collect::<Vec<_>>()),
+            lines,
+            message,
+            panel,
+        }
+    }
+
+    fn move_cursor(&mut self, lines: isize) {
+        if self.lines.is_empty() {
+            return;
+        }
+        let line = self
+            .panel
+            .cursor()
+            .unwrap_or(0)
+            .saturating_add_signed(lines)
+            .min(self.lines.len() - 1);
+        self.panel.select_line(line);
+    }
+
+    /// Show the change which last modified the line under the cursor in the log
+    fn view_change(&self, commander: &mut Commander) -> Result<ComponentInputResult> {
+        let Some(line) = self
+            .panel
+            .cursor()
+            .and_then(|cursor| self.lines.get(cursor))
+        else {
+            return Ok(ComponentInputResult::Handled);
+        };
+        let head = commander.get_revision_head(line.commit_id.as_str())?;
+        Ok(ComponentInputResult::HandledAction(
+            ComponentAction::Multiple(vec![
+                ComponentAction::SetPopup(None),
+                ComponentAction::ViewLog(head),
+            ]),
+        ))
+    }
+}
+
+impl Component for AnnotatePopup {
+    fn update(&mut self, _commander: &mut Commander) -> Result<Option<ComponentAction>> {
+        self.panel.take_copy_action()
+    }
+
+    fn draw(&mut self, f: &mut ratatui::prelude::Frame<'_>, area: Rect) -> Result<()> {
+        let title = format!("Annotate {}", self.path);
+        let block = create_popup_block(&title);
+        let area = centered_rect(area, 80, 80);
+        f.render_widget(Clear, area);
+        f.render_widget(&block, area);
+
+        let popup_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(if self.message.is_some() { 1 } else { 0 }),
+                Constraint::Fill(1),
+                Constraint::Length(2),
+            ])
+            .split(block.inner(area));
+
+        if let Some(message) = self.message.as_ref() {
+            f.render_widget(
+                Paragraph::new(message.as_str()).fg(Color::Yellow),
+                popup_chunks[0],
+            );
+        }
+
+        self.panel
+            .render_context()
+            .content(self.text.clone())
+            .draw(f, popup_chunks[1]);
+
+        let help = Paragraph::new(vec![
+            "j/k: select line | Enter: view change in log | Escape: close".into(),
+        ])
+        .fg(Color::DarkGray)
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::TOP)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+        f.render_widget(help, popup_chunks[2]);
+
+        Ok(())
+    }
+
+    fn input(&mut self, commander: &mut Commander, event: Event) -> Result<ComponentInputResult> {
+        if let Event::Mouse(mouse) = event {
+            self.panel.input_mouse(mouse);
+            return Ok(ComponentInputResult::Handled);
+        }
+
+        if let Event::Key(key) = event
+            && key.kind == KeyEventKind::Press
+        {
+            if self.panel.input(key) {
+                return Ok(ComponentInputResult::Handled);
+            }
+
+            match key.code {
+                KeyCode::Char('j') | KeyCode::Down => self.move_cursor(1),
+                KeyCode::Char('k') | KeyCode::Up => self.move_cursor(-1),
+                KeyCode::Char('J') => self.move_cursor(20),
+                KeyCode::Char('K') => self.move_cursor(-20),
+                KeyCode::Enter => return self.view_change(commander),
+                KeyCode::Esc | KeyCode::Char('q') => return Ok(ComponentInputResult::NotHandled),
+                _ => {}
+            }
+        }
+
+        Ok(ComponentInputResult::Handled)
+    }
+}
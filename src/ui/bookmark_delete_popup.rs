@@ -0,0 +1,115 @@
+use ansi_to_tui::IntoText;
+use anyhow::Result;
+use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEventKind},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+};
+
+use crate::{
+    ComponentInputResult,
+    app::PushTarget,
+    commander::Commander,
+    ui::{
+        Component, ComponentAction, message_popup::MessagePopup, styles::create_popup_block,
+        utils::centered_rect_line_height,
+    },
+};
+
+/// Confirmation to delete a bookmark tracking remote bookmarks, choosing whether it is only
+/// deleted locally or also on the remotes.
+pub struct BookmarkDeletePopup {
+    name: String,
+    remotes: Vec<String>,
+}
+
+impl BookmarkDeletePopup {
+    pub fn new(name: String, remotes: Vec<String>) -> Self {
+        Self { name, remotes }
+    }
+
+    /// Delete the bookmark locally, then push the deletion to the remotes in the background.
+    /// The push result is shown once it finishes
+    fn delete(&self, commander: &mut Commander, remote: bool) -> Result<ComponentInputResult> {
+        let mut actions = vec![ComponentAction::SetPopup(None)];
+        match commander.delete_bookmark(&self.name) {
+            Ok(()) => {
+                actions.push(ComponentAction::RefreshTab());
+                if remote {
+                    actions.push(ComponentAction::Push(PushTarget::Bookmark {
+                        name: self.name.clone(),
+                        allow_new: false,
+                    }));
+                }
+            }
+            Err(err) => {
+                actions.push(ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                    title: "Delete error".into(),
+                    messages: err.to_string().into_text()?,
+                    text_align: None,
+                }))));
+            }
+        }
+        Ok(ComponentInputResult::HandledAction(
+            ComponentAction::Multiple(actions),
+        ))
+    }
+}
+
+impl Component for BookmarkDeletePopup {
+    fn draw(&mut self, f: &mut ratatui::prelude::Frame<'_>, area: Rect) -> Result<()> {
+        let block = create_popup_block("Delete");
+        let area = centered_rect_line_height(area, 50, 9);
+        f.render_widget(Clear, area);
+        f.render_widget(&block, area);
+
+        let popup_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Fill(1), Constraint::Length(2)])
+            .split(block.inner(area));
+
+        let lines = vec![
+            Line::from("Are you sure you want to delete this bookmark?"),
+            Line::from(self.name.clone()).bold(),
+            Line::from(""),
+            Line::from(format!("Tracked on: {}", self.remotes.join(", "))).fg(Color::Yellow),
+        ];
+        f.render_widget(
+            Paragraph::new(lines)
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: false }),
+            popup_chunks[0],
+        );
+
+        let help = Paragraph::new(vec![
+            "l: delete locally | r: delete locally and on the remotes | Escape: cancel".into(),
+        ])
+        .fg(Color::DarkGray)
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::TOP)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+        f.render_widget(help, popup_chunks[1]);
+
+        Ok(())
+    }
+
+    fn input(&mut self, commander: &mut Commander, event: Event) -> Result<ComponentInputResult> {
+        if let Event::Key(key) = event
+            && key.kind == KeyEventKind::Press
+        {
+            match key.code {
+                KeyCode::Char('l') => return self.delete(commander, false),
+                KeyCode::Char('r') => return self.delete(commander, true),
+                _ => return Ok(ComponentInputResult::NotHandled),
+            }
+        }
+
+        Ok(ComponentInputResult::NotHandled)
+    }
+}
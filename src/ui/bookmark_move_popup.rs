@@ -0,0 +1,211 @@
+use ansi_to_tui::IntoText;
+use anyhow::Result;
+use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEventKind},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListState, Paragraph},
+};
+
+use crate::{
+    ComponentInputResult,
+    commander::{Commander, bookmarks::LocalBookmark, ids::CommitId},
+    env::Config,
+    ui::{
+        Component, ComponentAction, message_popup::MessagePopup, styles::create_popup_block,
+        utils::centered_rect,
+    },
+};
+
+/// Popup to move an existing local bookmark to a commit.
+/// Moving a bookmark backwards or sideways needs to be confirmed, unless disabled with
+/// `lazyjj.confirm-destructive`.
+pub struct BookmarkMovePopup {
+    commit_id: CommitId,
+    bookmarks: Result<Vec<LocalBookmark>, String>,
+    list_state: ListState,
+    list_height: u16,
+    /// Bookmark waiting for confirmation to be moved backwards or sideways
+    confirming: Option<LocalBookmark>,
+    config: Config,
+    tx: std::sync::mpsc::Sender<bool>,
+}
+
+impl BookmarkMovePopup {
+    pub fn new(
+        config: Config,
+        commander: &mut Commander,
+        commit_id: CommitId,
+        tx: std::sync::mpsc::Sender<bool>,
+    ) -> Self {
+        Self {
+            commit_id,
+            bookmarks: commander
+                .get_local_bookmarks()
+                .map_err(|err| err.to_string()),
+            list_state: ListState::default().with_selected(Some(0)),
+            list_height: 0,
+            confirming: None,
+            config,
+            tx,
+        }
+    }
+
+    fn scroll(&mut self, scroll: isize) {
+        let len = self
+            .bookmarks
+            .as_ref()
+            .map_or(0, |bookmarks| bookmarks.len());
+        self.list_state.select(Some(
+            self.list_state
+                .selected()
+                .map(|selected| selected.saturating_add_signed(scroll))
+                .unwrap_or(0)
+                .min(len.saturating_sub(1)),
+        ));
+    }
+
+    fn selected_bookmark(&self) -> Option<&LocalBookmark> {
+        self.bookmarks
+            .as_ref()
+            .ok()
+            .and_then(|bookmarks| bookmarks.get(self.list_state.selected()?))
+    }
+
+    /// Move the selected bookmark, unless it goes backwards or sideways and needs confirmation
+    fn on_select(&mut self, commander: &mut Commander) -> Result<ComponentInputResult> {
+        let Some(bookmark) = self.selected_bookmark().cloned() else {
+            return Ok(ComponentInputResult::Handled);
+        };
+        if bookmark.commit_id.as_ref() == Some(&self.commit_id) {
+            return Ok(ComponentInputResult::Handled);
+        }
+
+        let forwards = match bookmark.commit_id.as_ref() {
+            Some(target) => commander.is_ancestor(target, &self.commit_id)?,
+            None => false,
+        };
+        if forwards || !self.config.confirm_destructive() {
+            self.move_bookmark(commander, &bookmark)
+        } else {
+            self.confirming = Some(bookmark);
+            Ok(ComponentInputResult::Handled)
+        }
+    }
+
+    fn move_bookmark(
+        &mut self,
+        commander: &mut Commander,
+        bookmark: &LocalBookmark,
+    ) -> Result<ComponentInputResult> {
+        let action = match commander.set_bookmark_commit(&bookmark.name, &self.commit_id) {
+            Ok(()) => {
+                self.tx.send(true)?;
+                ComponentAction::SetPopup(None)
+            }
+            Err(err) => ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                title: "Move bookmark".into(),
+                messages: err.into_text("")?,
+                text_align: None,
+            }))),
+        };
+        Ok(ComponentInputResult::HandledAction(action))
+    }
+}
+
+impl Component for BookmarkMovePopup {
+    fn draw(&mut self, f: &mut ratatui::prelude::Frame<'_>, area: Rect) -> Result<()> {
+        let block = create_popup_block("Move bookmark");
+        let area = centered_rect(area, 60, 60);
+        f.render_widget(Clear, area);
+        f.render_widget(&block, area);
+
+        let popup_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Fill(1), Constraint::Length(2)])
+            .split(block.inner(area));
+
+        match self.bookmarks.as_ref() {
+            Ok(bookmarks) if bookmarks.is_empty() => {
+                f.render_widget(
+                    Paragraph::new("No local bookmarks").fg(Color::DarkGray),
+                    popup_chunks[0],
+                );
+            }
+            Ok(bookmarks) => {
+                let lines: Vec<Line> = bookmarks
+                    .iter()
+                    .map(|bookmark| {
+                        let mut spans = vec![
+                            Span::raw(bookmark.name.clone()).fg(Color::Magenta),
+                            Span::raw(" → "),
+                            Span::raw(bookmark.target.clone()),
+                        ];
+                        if bookmark.commit_id.as_ref() == Some(&self.commit_id) {
+                            spans.push(Span::raw(" (here)").fg(Color::DarkGray));
+                        }
+                        Line::from(spans)
+                    })
+                    .collect();
+                let list = List::new(lines)
+                    .scroll_padding(3)
+                    .highlight_style(Style::default().bg(self.config.highlight_color()));
+                f.render_stateful_widget(list, popup_chunks[0], &mut self.list_state);
+            }
+            Err(err) => {
+                f.render_widget(Paragraph::new(err.as_str()).fg(Color::Red), popup_chunks[0]);
+            }
+        }
+        self.list_height = popup_chunks[0].height;
+
+        let help = match self.confirming.as_ref() {
+            Some(bookmark) => Paragraph::new(vec![
+                Line::from(format!(
+                    "Move {} backwards or sideways? y: move | n: cancel",
+                    bookmark.name
+                ))
+                .fg(Color::Yellow),
+            ]),
+            None => Paragraph::new(vec![
+                "j/k: scroll down/up | Enter: move here | Escape: cancel".into(),
+            ])
+            .fg(Color::DarkGray),
+        }
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::TOP)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+        f.render_widget(help, popup_chunks[1]);
+
+        Ok(())
+    }
+
+    fn input(&mut self, commander: &mut Commander, event: Event) -> Result<ComponentInputResult> {
+        if let Event::Key(key) = event
+            && key.kind == KeyEventKind::Press
+        {
+            if let Some(bookmark) = self.confirming.take() {
+                return match key.code {
+                    KeyCode::Char('y') | KeyCode::Enter => self.move_bookmark(commander, &bookmark),
+                    _ => Ok(ComponentInputResult::Handled),
+                };
+            }
+
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => return Ok(ComponentInputResult::NotHandled),
+                KeyCode::Char('j') | KeyCode::Down => self.scroll(1),
+                KeyCode::Char('k') | KeyCode::Up => self.scroll(-1),
+                KeyCode::Char('J') => self.scroll(self.list_height as isize / 2),
+                KeyCode::Char('K') => self.scroll((self.list_height as isize / 2).saturating_neg()),
+                KeyCode::Enter => return self.on_select(commander),
+                _ => {}
+            }
+        }
+
+        Ok(ComponentInputResult::Handled)
+    }
+}
@@ -5,10 +5,10 @@ use ratatui::{
     crossterm::event::{Event, KeyCode, KeyModifiers},
     layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Style, Stylize},
-    text::{Span, Text},
+    text::{Line, Span, Text},
     widgets::{Block, BorderType, Borders, Clear, List, ListState, Paragraph},
 };
-use tui_textarea::TextArea;
+use tui_textarea::{CursorMove, TextArea};
 
 use crate::{
     ComponentInputResult,
@@ -20,11 +20,16 @@ use crate::{
     env::Config,
     ui::{
         Component, ComponentAction,
+        confirm_popup::confirm,
+        message_popup::MessagePopup,
         styles::create_popup_block,
         utils::{centered_rect, centered_rect_line_height},
     },
 };
 
+/// Maximum number of bookmark name completions shown while typing
+const MAX_COMPLETIONS: usize = 5;
+
 enum BookmarkSetOption {
     CreateBookmark,
     // Name, exists
@@ -41,6 +46,10 @@ pub struct BookmarkSetPopup<'a> {
     list_height: u16,
     config: Config,
     creating: Option<TextArea<'a>>,
+    /// Only create a new bookmark, refusing names which are taken instead of moving them
+    create_only: bool,
+    /// Why the typed name was refused
+    error: Option<String>,
     tx: std::sync::mpsc::Sender<bool>,
 }
 
@@ -103,10 +112,38 @@ impl BookmarkSetPopup<'_> {
             config,
             commit_id,
             creating: None,
+            create_only: false,
+            error: None,
             tx,
         }
     }
 
+    /// Create popup which directly prompts for the bookmark name
+    pub fn new_name_prompt(
+        config: Config,
+        commander: &mut Commander,
+        change_id: Option<ChangeId>,
+        commit_id: CommitId,
+        tx: std::sync::mpsc::Sender<bool>,
+    ) -> Self {
+        let mut popup = Self::new(config, commander, change_id, commit_id, tx);
+        popup.on_creating();
+        popup
+    }
+
+    /// Create popup which prompts for the name of a new bookmark. Unlike
+    /// [BookmarkSetPopup::new_name_prompt], an existing bookmark is not moved
+    pub fn new_create_prompt(
+        config: Config,
+        commander: &mut Commander,
+        commit_id: CommitId,
+        tx: std::sync::mpsc::Sender<bool>,
+    ) -> Self {
+        let mut popup = Self::new_name_prompt(config, commander, None, commit_id, tx);
+        popup.create_only = true;
+        popup
+    }
+
     fn scroll(&mut self, scroll: isize) {
         self.list_state.select(Some(
             self.list_state
@@ -121,31 +158,114 @@ impl BookmarkSetPopup<'_> {
         self.creating = Some(TextArea::default());
     }
 
-    fn create_bookmark(&self, commander: &mut Commander, name: &str) -> Result<()> {
+    /// Existing bookmark names starting with the name being typed
+    fn completions(&self) -> Vec<&str> {
+        let Some(creating) = self.creating.as_ref() else {
+            return vec![];
+        };
+        let input = creating.lines().join("\n");
+        if input.is_empty() {
+            return vec![];
+        }
+
+        self.options
+            .iter()
+            .filter_map(|option| match option {
+                BookmarkSetOption::Bookmark(bookmark) => Some(bookmark.name.as_str()),
+                _ => None,
+            })
+            .filter(|name| name.starts_with(&input) && *name != input)
+            .collect()
+    }
+
+    /// Complete the name being typed to the longest prefix shared by all completions
+    fn complete(&mut self) {
+        let completions = self.completions();
+        let Some(first) = completions.first() else {
+            return;
+        };
+        let mut prefix = first.to_string();
+        for completion in completions.iter().skip(1) {
+            while !completion.starts_with(&prefix) {
+                prefix.pop();
+            }
+        }
+
+        let mut textarea = TextArea::new(vec![prefix]);
+        textarea.move_cursor(CursorMove::End);
+        self.creating = Some(textarea);
+    }
+
+    /// Point the bookmark `name` to the commit, creating it if needed. Moving an existing
+    /// bookmark backwards or sideways asks for confirmation first
+    fn set_bookmark(&self, commander: &mut Commander, name: &str) -> Result<ComponentAction> {
+        let Some(bookmark) = commander
+            .get_local_bookmarks()?
+            .into_iter()
+            .find(|bookmark| bookmark.name == name)
+        else {
+            commander.create_bookmark_commit(name, &self.commit_id)?;
+            self.tx.send(true)?;
+            return Ok(ComponentAction::SetPopup(None));
+        };
+
+        let forwards = match bookmark.commit_id.as_ref() {
+            Some(target) => commander.is_ancestor(target, &self.commit_id)?,
+            None => false,
+        };
+        let name = name.to_owned();
+        let commit_id = self.commit_id.clone();
+        let tx = self.tx.clone();
+        let move_bookmark = move |commander: &mut Commander| -> Result<ComponentAction> {
+            Ok(match commander.set_bookmark_commit(&name, &commit_id) {
+                Ok(()) => {
+                    tx.send(true)?;
+                    ComponentAction::SetPopup(None)
+                }
+                Err(err) => ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                    title: "Set bookmark".into(),
+                    messages: err.into_text("")?,
+                    text_align: None,
+                }))),
+            })
+        };
+        if forwards {
+            return move_bookmark(commander);
+        }
+        confirm(
+            &self.config,
+            commander,
+            "Move bookmark",
+            vec![Line::from(format!(
+                "Move {} backwards or sideways?",
+                bookmark.name
+            ))],
+            move_bookmark,
+        )
+    }
+
+    /// Create a new bookmark, returning why not if the name is empty or taken
+    fn create_new_bookmark(&self, commander: &mut Commander, name: &str) -> Result<Option<String>> {
+        if name.trim().is_empty() {
+            return Ok(Some("The bookmark name can't be empty".to_owned()));
+        }
         if commander
             .get_bookmarks_list(false)?
             .iter()
-            .any(|bookmark| bookmark.name == name)
+            .any(|bookmark| bookmark.remote.is_none() && bookmark.name == name)
         {
-            commander.set_bookmark_commit(name, &self.commit_id)?;
-        } else {
-            commander.create_bookmark_commit(name, &self.commit_id)?;
+            return Ok(Some(format!("Bookmark {name} already exists")));
         }
-        Ok(())
+        if let Err(err) = commander.create_bookmark_commit(name, &self.commit_id) {
+            return Ok(Some(err.to_string()));
+        }
+        Ok(None)
     }
-    fn generate_bookmark(&self, commander: &mut Commander) -> Result<()> {
+
+    fn generate_bookmark(&self, commander: &mut Commander) -> Result<ComponentAction> {
         if let Some(change_id) = self.change_id.as_ref() {
             let generated_name = generate_name(&commander.env.config.bookmark_prefix(), change_id);
-            if commander
-                .get_bookmarks_list(false)?
-                .iter()
-                .any(|bookmark| bookmark.name == generated_name)
-            {
-                commander.set_bookmark_commit(&generated_name, &self.commit_id)?;
-            } else {
-                commander.create_bookmark_commit(&generated_name, &self.commit_id)?;
-            }
-            Ok(())
+            self.set_bookmark(commander, &generated_name)
         } else {
             bail!("No change ID");
         }
@@ -155,19 +275,40 @@ impl BookmarkSetPopup<'_> {
 impl Component for BookmarkSetPopup<'_> {
     fn draw(&mut self, f: &mut ratatui::prelude::Frame<'_>, area: Rect) -> Result<()> {
         if let Some(creating) = self.creating.as_ref() {
+            let completions: Vec<Line> = self
+                .completions()
+                .into_iter()
+                .take(MAX_COMPLETIONS)
+                .map(|name| Line::raw(name.to_owned()).fg(Color::Magenta))
+                .collect();
+            let completions_height = completions.len() as u16;
+            let error_height = self.error.is_some() as u16;
+
             let block = create_popup_block("Create bookmark");
-            let area = centered_rect_line_height(area, 30, 5);
+            let area = centered_rect_line_height(area, 30, 5 + completions_height + error_height);
             f.render_widget(Clear, area);
             f.render_widget(&block, area);
 
             let popup_chunks = Layout::default()
                 .direction(Direction::Vertical)
-                .constraints([Constraint::Fill(1), Constraint::Length(2)])
+                .constraints([
+                    Constraint::Fill(1),
+                    Constraint::Length(error_height),
+                    Constraint::Length(completions_height),
+                    Constraint::Length(2),
+                ])
                 .split(block.inner(area));
 
             f.render_widget(creating, popup_chunks[0]);
+            if let Some(error) = self.error.as_ref() {
+                f.render_widget(
+                    Paragraph::new(Line::raw(error.clone()).fg(Color::Red)),
+                    popup_chunks[1],
+                );
+            }
+            f.render_widget(Paragraph::new(completions), popup_chunks[2]);
 
-            let help = Paragraph::new(vec!["Ctrl+s: save | Escape: cancel".into()])
+            let help = Paragraph::new(vec!["Tab: complete | Ctrl+s: save | Escape: cancel".into()])
                 .fg(Color::DarkGray)
                 .alignment(Alignment::Center)
                 .block(
@@ -177,7 +318,7 @@ impl Component for BookmarkSetPopup<'_> {
                         .border_style(Style::default().fg(Color::DarkGray)),
                 );
 
-            f.render_widget(help, popup_chunks[1]);
+            f.render_widget(help, popup_chunks[3]);
         } else {
             let block = Block::bordered()
                 .title(Span::styled(
@@ -250,14 +391,22 @@ impl Component for BookmarkSetPopup<'_> {
                         || (key.code == KeyCode::Enter) =>
                     {
                         let name = &creating.lines().join("\n");
+                        if self.create_only {
+                            self.error = self.create_new_bookmark(commander, name)?;
+                            if self.error.is_some() {
+                                return Ok(ComponentInputResult::Handled);
+                            }
+                            self.tx.send(true)?;
+                            return Ok(ComponentInputResult::HandledAction(
+                                ComponentAction::SetPopup(None),
+                            ));
+                        }
                         if name.trim().is_empty() {
                             return Ok(ComponentInputResult::Handled);
                         }
 
-                        self.create_bookmark(commander, name)?;
-                        self.tx.send(true)?;
                         return Ok(ComponentInputResult::HandledAction(
-                            ComponentAction::SetPopup(None),
+                            self.set_bookmark(commander, name)?,
                         ));
                     }
                     KeyCode::Esc => {
@@ -265,11 +414,16 @@ impl Component for BookmarkSetPopup<'_> {
                             ComponentAction::SetPopup(None),
                         ));
                     }
+                    KeyCode::Tab => {
+                        self.complete();
+                        return Ok(ComponentInputResult::Handled);
+                    }
                     _ => {}
                 }
             }
 
             creating.input(event);
+            self.error = None;
             return Ok(ComponentInputResult::Handled);
         }
 
@@ -288,10 +442,8 @@ impl Component for BookmarkSetPopup<'_> {
                     self.scroll((self.list_height as isize / 2).saturating_neg());
                 }
                 KeyCode::Char('g') => {
-                    self.generate_bookmark(commander)?;
-                    self.tx.send(true)?;
                     return Ok(ComponentInputResult::HandledAction(
-                        ComponentAction::SetPopup(None),
+                        self.generate_bookmark(commander)?,
                     ));
                 }
                 KeyCode::Char('c') => {
@@ -308,17 +460,13 @@ impl Component for BookmarkSetPopup<'_> {
                                 self.on_creating();
                             }
                             BookmarkSetOption::GeneratedName(_, _) => {
-                                self.generate_bookmark(commander)?;
-                                self.tx.send(true)?;
                                 return Ok(ComponentInputResult::HandledAction(
-                                    ComponentAction::SetPopup(None),
+                                    self.generate_bookmark(commander)?,
                                 ));
                             }
                             BookmarkSetOption::Bookmark(bookmark) => {
-                                commander.set_bookmark_commit(&bookmark.name, &self.commit_id)?;
-                                self.tx.send(true)?;
                                 return Ok(ComponentInputResult::HandledAction(
-                                    ComponentAction::SetPopup(None),
+                                    self.set_bookmark(commander, &bookmark.name)?,
                                 ));
                             }
                             BookmarkSetOption::Error(_) => {
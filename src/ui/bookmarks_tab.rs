@@ -2,14 +2,22 @@
 
 use crate::{
     ComponentInputResult,
-    commander::{CommandError, Commander, bookmarks::BookmarkLine, ids::ChangeId},
+    app::PushTarget,
+    commander::{
+        CommandError, Commander,
+        bookmarks::{AheadBehind, AheadBehindCache, BookmarkLine},
+        ids::ChangeId,
+    },
     env::{Config, DiffFormat},
     ui::{
         Component, ComponentAction,
+        bookmark_delete_popup::BookmarkDeletePopup,
+        confirm_popup::confirm,
         help_popup::HelpPopup,
         message_popup::MessagePopup,
         panel::DetailsPanel,
-        utils::{centered_rect, centered_rect_line_height, tabs_to_spaces},
+        tags_popup::TagsPopup,
+        utils::{centered_rect, centered_rect_line_height, position_indicator, signature_span},
     },
 };
 use ansi_to_tui::IntoText;
@@ -19,6 +27,7 @@ use ratatui::{
     prelude::*,
     widgets::*,
 };
+use std::collections::HashMap;
 use tracing::instrument;
 use tui_confirm_dialog::{ButtonLabel, ConfirmDialog, ConfirmDialogState, Listener};
 use tui_textarea::{CursorMove, TextArea};
@@ -34,18 +43,14 @@ struct RenameBookmark<'a> {
     error: Option<anyhow::Error>,
 }
 
-struct DeleteBookmark {
+struct PushBookmark {
     name: String,
+    allow_new: bool,
 }
 
-struct ForgetBookmark {
-    name: String,
-}
-
-const DELETE_BRANCH_POPUP_ID: u16 = 1;
-const FORGET_BRANCH_POPUP_ID: u16 = 2;
 const NEW_POPUP_ID: u16 = 3;
 const EDIT_POPUP_ID: u16 = 4;
+const PUSH_POPUP_ID: u16 = 5;
 
 /// Bookmarks tab. Shows bookmarks in main panel and selected bookmark current change in details panel.
 pub struct BookmarksTab<'a> {
@@ -55,6 +60,11 @@ pub struct BookmarksTab<'a> {
 
     show_all: bool,
 
+    /// How far local bookmarks are from their remote, computed once per refresh
+    ahead_behind: HashMap<String, AheadBehind>,
+    /// Counts of bookmarks which didn't move since they were last counted
+    ahead_behind_cache: AheadBehindCache,
+
     bookmark: Option<BookmarkLine>,
 
     bookmark_panel: DetailsPanel,
@@ -62,8 +72,7 @@ pub struct BookmarksTab<'a> {
 
     create: Option<CreateBookmark<'a>>,
     rename: Option<RenameBookmark<'a>>,
-    delete: Option<DeleteBookmark>,
-    forget: Option<ForgetBookmark>,
+    push: Option<PushBookmark>,
 
     describe_textarea: Option<TextArea<'a>>,
     describe_after_new: bool,
@@ -110,6 +119,21 @@ fn get_current_bookmark_index(
     }
 }
 
+/// Refresh the tab after deleting or forgetting a bookmark, or show why it failed
+fn bookmark_result(
+    result: Result<(), CommandError>,
+    title: &'static str,
+) -> Result<ComponentAction> {
+    Ok(match result {
+        Ok(()) => ComponentAction::RefreshTab(),
+        Err(err) => ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+            title: title.into(),
+            messages: err.to_string().into_text()?,
+            text_align: None,
+        }))),
+    })
+}
+
 impl BookmarksTab<'_> {
     #[instrument(level = "trace", skip(commander))]
     pub fn new(commander: &mut Commander) -> Result<Self> {
@@ -130,15 +154,17 @@ impl BookmarksTab<'_> {
         ));
 
         let bookmark_output = bookmark.as_ref().and_then(|bookmark| match bookmark {
-            BookmarkLine::Parsed { bookmark, .. } => Some(
-                commander
-                    .get_bookmark_show(bookmark, &diff_format, true)
-                    .map(|diff| tabs_to_spaces(&diff)),
-            ),
+            BookmarkLine::Parsed { bookmark, .. } => {
+                Some(commander.get_bookmark_show(bookmark, &diff_format, true))
+            }
             _ => None,
         });
 
         let (popup_tx, popup_rx) = std::sync::mpsc::channel();
+        let mut ahead_behind_cache = AheadBehindCache::new();
+        let ahead_behind = commander
+            .get_bookmarks_ahead_behind(&mut ahead_behind_cache)
+            .unwrap_or_default();
 
         Ok(Self {
             bookmarks_output,
@@ -148,13 +174,18 @@ impl BookmarksTab<'_> {
 
             show_all,
 
-            bookmark_panel: DetailsPanel::new(),
+            ahead_behind,
+            ahead_behind_cache,
+
+            bookmark_panel: DetailsPanel::new()
+                .with_highlight_color(commander.env.config.highlight_color())
+                .with_tab_width(commander.env.config.tab_width())
+                .with_wrap(commander.env.config.details_wrap()),
             bookmark_output,
 
             create: None,
             rename: None,
-            delete: None,
-            forget: None,
+            push: None,
 
             describe_after_new: false,
             describe_textarea: None,
@@ -178,19 +209,22 @@ impl BookmarksTab<'_> {
 
     pub fn refresh_bookmarks(&mut self, commander: &mut Commander) {
         self.bookmarks_output = commander.get_bookmarks(self.show_all);
+        self.ahead_behind = commander
+            .get_bookmarks_ahead_behind(&mut self.ahead_behind_cache)
+            .unwrap_or_default();
     }
 
     pub fn refresh_bookmark(&mut self, commander: &mut Commander) {
         self.bookmark_output = self.bookmark.as_ref().and_then(|bookmark| match bookmark {
-            BookmarkLine::Parsed { bookmark, .. } => Some(
-                commander
-                    .get_bookmark_show(bookmark, &self.diff_format, true)
-                    .map(|diff| tabs_to_spaces(&diff)),
-            ),
+            BookmarkLine::Parsed { bookmark, .. } => {
+                Some(commander.get_bookmark_show(bookmark, &self.diff_format, true))
+            }
             _ => None,
         });
 
         self.bookmark_panel.scroll_to(0);
+
+        self.bookmark_panel.clear_cursor();
     }
 
     fn scroll_bookmarks(&mut self, commander: &mut Commander, scroll: isize) {
@@ -217,64 +251,28 @@ impl BookmarksTab<'_> {
 impl Component for BookmarksTab<'_> {
     fn focus(&mut self, commander: &mut Commander) -> Result<()> {
         self.refresh_bookmarks(commander);
+        // The selected bookmark is gone, e.g. after deleting it
+        if self.get_current_bookmark_index().is_none() {
+            self.bookmark = self
+                .bookmarks_output
+                .as_ref()
+                .ok()
+                .and_then(|bookmarks| bookmarks.first().cloned());
+        }
         self.refresh_bookmark(commander);
         Ok(())
     }
 
     fn update(&mut self, commander: &mut Commander) -> Result<Option<ComponentAction>> {
+        if let Some(action) = self.bookmark_panel.take_copy_action()? {
+            return Ok(Some(action));
+        }
+
         // Check for popup action
         if let Ok(res) = self.popup_rx.try_recv()
             && res.1.unwrap_or(false)
         {
             match res.0 {
-                DELETE_BRANCH_POPUP_ID => {
-                    if let Some(delete) = self.delete.as_ref() {
-                        match commander.delete_bookmark(&delete.name) {
-                            Ok(_) => {
-                                self.refresh_bookmarks(commander);
-                                let bookmarks = Vec::new();
-                                let bookmarks =
-                                    self.bookmarks_output.as_ref().unwrap_or(&bookmarks);
-                                self.bookmark =
-                                    bookmarks.first().map(|bookmark| bookmark.to_owned());
-                                self.refresh_bookmark(commander);
-                            }
-                            Err(err) => {
-                                return Ok(Some(ComponentAction::SetPopup(Some(Box::new(
-                                    MessagePopup {
-                                        title: "Delete error".into(),
-                                        messages: err.to_string().into_text()?,
-                                        text_align: None,
-                                    },
-                                )))));
-                            }
-                        }
-                    }
-                }
-                FORGET_BRANCH_POPUP_ID => {
-                    if let Some(forget) = self.forget.as_ref() {
-                        match commander.forget_bookmark(&forget.name) {
-                            Ok(_) => {
-                                self.refresh_bookmarks(commander);
-                                let bookmarks = Vec::new();
-                                let bookmarks =
-                                    self.bookmarks_output.as_ref().unwrap_or(&bookmarks);
-                                self.bookmark =
-                                    bookmarks.first().map(|bookmark| bookmark.to_owned());
-                                self.refresh_bookmark(commander);
-                            }
-                            Err(err) => {
-                                return Ok(Some(ComponentAction::SetPopup(Some(Box::new(
-                                    MessagePopup {
-                                        title: "Forget error".into(),
-                                        messages: err.to_string().into_text()?,
-                                        text_align: None,
-                                    },
-                                )))));
-                            }
-                        }
-                    }
-                }
                 NEW_POPUP_ID => {
                     if let Some(BookmarkLine::Parsed { bookmark, .. }) = self.bookmark.as_ref() {
                         commander.run_new(&bookmark.to_string())?;
@@ -297,6 +295,14 @@ impl Component for BookmarksTab<'_> {
                         return Ok(Some(ComponentAction::ViewLog(head)));
                     }
                 }
+                PUSH_POPUP_ID => {
+                    if let Some(push) = self.push.take() {
+                        return Ok(Some(ComponentAction::Push(PushTarget::Bookmark {
+                            name: push.name,
+                            allow_new: push.allow_new,
+                        })));
+                    }
+                }
                 _ => {}
             }
         }
@@ -327,13 +333,37 @@ impl Component for BookmarksTab<'_> {
                     .enumerate()
                     .map(|(i, bookmark)| -> Result<Vec<Line>, ansi_to_tui::Error> {
                         let bookmark_text = bookmark.to_text()?;
+                        let signature = match bookmark {
+                            BookmarkLine::Parsed { signature, .. } => *signature,
+                            BookmarkLine::Unparsable(_) => None,
+                        };
+                        let ahead_behind = match bookmark {
+                            BookmarkLine::Parsed { bookmark, .. } if bookmark.remote.is_none() => {
+                                self.ahead_behind.get(&bookmark.name)
+                            }
+                            _ => None,
+                        };
                         Ok(bookmark_text
                             .iter()
-                            .map(|line| {
+                            .enumerate()
+                            .map(|(line_index, line)| {
                                 let mut line = line.to_owned();
 
-                                // Add padding at start
-                                line.spans.insert(0, Span::from(" "));
+                                // Add padding at start, with the signature indicator on the first line
+                                line.spans.insert(
+                                    0,
+                                    match signature.filter(|_| line_index == 0) {
+                                        Some(signature) => signature_span(signature),
+                                        None => Span::from("  "),
+                                    },
+                                );
+                                if line_index == 0
+                                    && let Some(ahead_behind) = ahead_behind
+                                {
+                                    line.spans.push(
+                                        Span::from(format!(" {ahead_behind}")).fg(Color::Cyan),
+                                    );
+                                }
 
                                 if current_bookmark_index == Some(i) {
                                     line = line.bg(self.config.highlight_color());
@@ -384,9 +414,17 @@ impl Component for BookmarksTab<'_> {
                 bookmark_lines
             };
 
-            let bookmarks_block = Block::bordered()
+            let mut bookmarks_block = Block::bordered()
                 .title(" Bookmarks ")
                 .border_type(BorderType::Rounded);
+            if let Ok(bookmarks_output) = self.bookmarks_output.as_ref()
+                && !bookmarks_output.is_empty()
+            {
+                bookmarks_block = bookmarks_block.title_bottom(position_indicator(
+                    current_bookmark_index,
+                    bookmarks_output.len(),
+                ));
+            }
             self.bookmarks_height = bookmarks_block.inner(chunks[0]).height;
             let bookmark_count = lines.len();
             let bookmarks = List::new(lines).block(bookmarks_block).scroll_padding(3);
@@ -782,6 +820,7 @@ impl Component for BookmarksTab<'_> {
                     self.refresh_bookmarks(commander);
                     self.refresh_bookmark(commander);
                 }
+                KeyCode::Char('/') => self.bookmark_panel.start_search(),
                 KeyCode::Char('a') => {
                     self.show_all = !self.show_all;
                     self.refresh_bookmarks(commander);
@@ -808,36 +847,79 @@ impl Component for BookmarksTab<'_> {
                 }
                 KeyCode::Char('d') => {
                     if let Some(BookmarkLine::Parsed { bookmark, .. }) = self.bookmark.as_ref() {
-                        self.delete = Some(DeleteBookmark {
-                            name: bookmark.name.clone(),
-                        });
-                        self.popup = ConfirmDialogState::new(
-                            DELETE_BRANCH_POPUP_ID,
-                            Span::styled(" Delete ", Style::new().bold().cyan()),
-                            Text::from(vec![Line::from(format!(
-                                "Are you sure you want to delete the {} bookmark?",
-                                bookmark.name
-                            ))]),
-                        );
-                        self.popup
-                            .with_yes_button(ButtonLabel::YES.clone())
-                            .with_no_button(ButtonLabel::NO.clone())
-                            .with_listener(Some(self.popup_tx.clone()))
-                            .open();
+                        let name = bookmark.name.clone();
+
+                        // Offer to delete the bookmark on the remotes it tracks as well
+                        let remotes: Vec<String> = commander
+                            .get_bookmarks_list(false)?
+                            .into_iter()
+                            .filter(|tracked| tracked.name == name)
+                            .filter_map(|tracked| tracked.remote)
+                            .filter(|remote| remote != "git")
+                            .collect();
+                        if bookmark.remote.is_none() && !remotes.is_empty() {
+                            return Ok(ComponentInputResult::HandledAction(
+                                ComponentAction::SetPopup(Some(Box::new(
+                                    BookmarkDeletePopup::new(name, remotes),
+                                ))),
+                            ));
+                        }
+
+                        return Ok(ComponentInputResult::HandledAction(confirm(
+                            &self.config,
+                            commander,
+                            "Delete",
+                            vec![
+                                Line::from("Are you sure you want to delete this bookmark?"),
+                                Line::from(name.clone()).bold(),
+                            ],
+                            move |commander| {
+                                bookmark_result(commander.delete_bookmark(&name), "Delete error")
+                            },
+                        )?));
                     }
                 }
                 KeyCode::Char('f') => {
                     if let Some(BookmarkLine::Parsed { bookmark, .. }) = self.bookmark.as_ref() {
-                        self.forget = Some(ForgetBookmark {
+                        let name = bookmark.name.clone();
+                        return Ok(ComponentInputResult::HandledAction(confirm(
+                            &self.config,
+                            commander,
+                            "Forget",
+                            vec![
+                                Line::from("Are you sure you want to forget this bookmark?"),
+                                Line::from(name.clone()).bold(),
+                            ],
+                            move |commander| {
+                                bookmark_result(commander.forget_bookmark(&name), "Forget error")
+                            },
+                        )?));
+                    }
+                }
+                KeyCode::Char('p') => {
+                    if let Some(BookmarkLine::Parsed { bookmark, .. }) = self.bookmark.as_ref()
+                        && bookmark.remote.is_none()
+                    {
+                        let allow_new = commander
+                            .get_push_summary(true, &commander.get_current_head()?.commit_id)?
+                            .new
+                            .contains(&bookmark.name);
+                        let mut lines = vec![Line::from(format!(
+                            "Are you sure you want to push the {} bookmark?",
+                            bookmark.name
+                        ))];
+                        if allow_new {
+                            lines
+                                .push(Line::from("It will be created on the remote (--allow-new)"));
+                        }
+                        self.push = Some(PushBookmark {
                             name: bookmark.name.clone(),
+                            allow_new,
                         });
                         self.popup = ConfirmDialogState::new(
-                            FORGET_BRANCH_POPUP_ID,
-                            Span::styled(" Forget ", Style::new().bold().cyan()),
-                            Text::from(vec![Line::from(format!(
-                                "Are you sure you want to forget the {} bookmark?",
-                                bookmark.name
-                            ))]),
+                            PUSH_POPUP_ID,
+                            Span::styled(" Push ", Style::new().bold().cyan()),
+                            Text::from(lines),
                         );
                         self.popup
                             .with_yes_button(ButtonLabel::YES.clone())
@@ -934,6 +1016,14 @@ impl Component for BookmarksTab<'_> {
                         ));
                     }
                 }
+                KeyCode::Char('g') => {
+                    return Ok(ComponentInputResult::HandledAction(
+                        ComponentAction::SetPopup(Some(Box::new(TagsPopup::new(
+                            self.config.clone(),
+                            commander,
+                        )))),
+                    ));
+                }
                 KeyCode::Char('?') => {
                     return Ok(ComponentInputResult::HandledAction(
                         ComponentAction::SetPopup(Some(Box::new(HelpPopup::new(
@@ -945,10 +1035,12 @@ impl Component for BookmarksTab<'_> {
                                 ("r".to_owned(), "rename bookmark".to_owned()),
                                 ("d/f".to_owned(), "delete/forget bookmark".to_owned()),
                                 ("t/T".to_owned(), "track/untrack bookmark".to_owned()),
+                                ("p".to_owned(), "git push bookmark".to_owned()),
                                 ("Enter".to_owned(), "view in log".to_owned()),
                                 ("n".to_owned(), "new from bookmark".to_owned()),
                                 ("N".to_owned(), "new and describe".to_owned()),
                                 ("e".to_owned(), "edit bookmark".to_owned()),
+                                ("g".to_owned(), "list tags".to_owned()),
                             ],
                             vec![
                                 ("Ctrl+e/Ctrl+y".to_owned(), "scroll down/up".to_owned()),
@@ -962,6 +1054,16 @@ impl Component for BookmarksTab<'_> {
                                 ),
                                 ("w".to_owned(), "toggle diff format".to_owned()),
                                 ("W".to_owned(), "toggle wrapping".to_owned()),
+                                (
+                                    "</>".to_owned(),
+                                    "scroll long lines left/right when not wrapping".to_owned(),
+                                ),
+                                (
+                                    "/".to_owned(),
+                                    "search details, Enter/Up: next/previous match".to_owned(),
+                                ),
+                                ("V".to_owned(), "toggle side by side git diff".to_owned()),
+                                ("Ctrl+w".to_owned(), "toggle description reflow".to_owned()),
                             ],
                         )))),
                     ));
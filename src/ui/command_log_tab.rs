@@ -14,10 +14,7 @@ use crate::{
     ComponentInputResult,
     commander::{CommandLogItem, Commander},
     env::Config,
-    ui::{
-        Component, ComponentAction, help_popup::HelpPopup, panel::DetailsPanel,
-        utils::tabs_to_spaces,
-    },
+    ui::{Component, ComponentAction, help_popup::HelpPopup, panel::DetailsPanel},
 };
 
 /// Command log tab. Shows list of commands exectured by lazyjj in main panel and selected command
@@ -26,6 +23,8 @@ pub struct CommandLogTab {
     command_history: Vec<CommandLogItem>,
     commands_list_state: ListState,
     commands_height: u16,
+    /// Keep the latest command selected when new commands are run
+    follow_latest: bool,
 
     output_panel: DetailsPanel,
 
@@ -42,8 +41,12 @@ impl CommandLogTab {
         Ok(Self {
             commands_height: 0,
             commands_list_state,
+            follow_latest: true,
             command_history,
-            output_panel: DetailsPanel::new(),
+            output_panel: DetailsPanel::new()
+                .with_highlight_color(commander.env.config.highlight_color())
+                .with_tab_width(commander.env.config.tab_width())
+                .with_wrap(commander.env.config.details_wrap()),
             config: commander.env.config.clone(),
         })
     }
@@ -98,7 +101,7 @@ impl CommandLogTab {
                             Line::default().spans([Span::raw("Output:").fg(Color::Green).bold()]),
                         );
                         output_lines.push(Line::default());
-                        output_lines.append(&mut tabs_to_spaces(stdout).into_text()?.lines);
+                        output_lines.append(&mut stdout.as_ref().into_text()?.lines);
                         has_output = true;
                     }
 
@@ -134,6 +137,35 @@ impl CommandLogTab {
         Ok(output_lines)
     }
 
+    /// Pick up commands run since last sync. Keeps the selected command unless following the latest
+    fn sync_command_history(&mut self, commander: &Commander) {
+        let command_history = commander.command_history.lock().unwrap();
+        if command_history.len() == self.command_history.len() {
+            return;
+        }
+        let added = command_history
+            .len()
+            .saturating_sub(self.command_history.len());
+        self.command_history = command_history.clone();
+        drop(command_history);
+
+        if self.command_history.is_empty() {
+            self.commands_list_state.select(None);
+        } else if self.follow_latest {
+            self.commands_list_state.select(Some(0));
+            self.output_panel.scroll_to(0);
+            self.output_panel.clear_cursor();
+        } else {
+            // Commands are listed newest first, so new commands shift the selection down
+            let selected = self
+                .commands_list_state
+                .selected()
+                .map_or(0, |selected| selected + added)
+                .min(self.command_history.len() - 1);
+            self.commands_list_state.select(Some(selected));
+        }
+    }
+
     fn scroll_commands(&mut self, scroll: isize) {
         *self.commands_list_state.selected_mut() = Some(
             (self
@@ -144,19 +176,23 @@ impl CommandLogTab {
             .min(self.command_history.len() - 1)
             .max(0),
         );
+        self.follow_latest = self.commands_list_state.selected() == Some(0);
         self.output_panel.scroll_to(0);
+        self.output_panel.clear_cursor();
     }
 }
 
 impl Component for CommandLogTab {
     fn focus(&mut self, commander: &mut Commander) -> Result<()> {
-        let command_history = commander.command_history.lock().unwrap().clone();
-        let selected_index = command_history.first().map(|_| 0);
-        self.commands_list_state.select(selected_index);
-        self.command_history = command_history;
+        self.sync_command_history(commander);
         Ok(())
     }
 
+    fn update(&mut self, commander: &mut Commander) -> Result<Option<ComponentAction>> {
+        self.sync_command_history(commander);
+        self.output_panel.take_copy_action()
+    }
+
     fn draw(
         &mut self,
         f: &mut ratatui::prelude::Frame<'_>,
@@ -206,10 +242,15 @@ impl Component for CommandLogTab {
                 })
                 .collect::<Vec<Line>>();
 
+            let title = if self.follow_latest {
+                " Commands (following latest) "
+            } else {
+                " Commands "
+            };
             let commands = List::new(command_lines)
                 .block(
                     Block::bordered()
-                        .title(" Commands ")
+                        .title(title)
                         .border_type(BorderType::Rounded),
                 )
                 .scroll_padding(3);
@@ -275,13 +316,17 @@ impl Component for CommandLogTab {
                 KeyCode::Char('@') => {
                     self.scroll_commands(isize::MIN);
                 }
+                KeyCode::Char('/') => self.output_panel.start_search(),
                 KeyCode::Char('?') => {
                     return Ok(ComponentInputResult::HandledAction(
                         ComponentAction::SetPopup(Some(Box::new(HelpPopup::new(
                             vec![
                                 ("j/k".to_owned(), "scroll down/up".to_owned()),
                                 ("J/K".to_owned(), "scroll down by ½ page".to_owned()),
-                                ("@".to_owned(), "latest command".to_owned()),
+                                (
+                                    "@".to_owned(),
+                                    "latest command and follow new commands".to_owned(),
+                                ),
                             ],
                             vec![
                                 ("Ctrl+e/Ctrl+y".to_owned(), "scroll down/up".to_owned()),
@@ -294,6 +339,14 @@ impl Component for CommandLogTab {
                                     "scroll down/up by page".to_owned(),
                                 ),
                                 ("W".to_owned(), "toggle wrapping".to_owned()),
+                                (
+                                    "</>".to_owned(),
+                                    "scroll long lines left/right when not wrapping".to_owned(),
+                                ),
+                                (
+                                    "/".to_owned(),
+                                    "search details, Enter/Up: next/previous match".to_owned(),
+                                ),
                             ],
                         )))),
                     ));
@@ -0,0 +1,226 @@
+use anyhow::Result;
+use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListState, Paragraph, Wrap},
+};
+use tui_textarea::{CursorMove, TextArea};
+
+use crate::{
+    ComponentInputResult,
+    commander::{Commander, config::ConfigScope},
+    env::Config,
+    ui::{Component, styles::create_popup_block, utils::centered_rect},
+};
+
+/// Config keys which can be edited, with a short description
+const EDITABLE_KEYS: [(&str, &str); 4] = [
+    ("user.name", "Name used for new commits"),
+    ("user.email", "Email used for new commits"),
+    ("ui.editor", "Editor for descriptions"),
+    ("ui.diff-editor", "Tool used to edit diffs"),
+];
+
+/// Form to change the selected config value
+struct ConfigForm<'a> {
+    name: &'static str,
+    value: TextArea<'a>,
+    scope: ConfigScope,
+}
+
+/// Popup showing common jj settings, to edit them with `jj config set`
+pub struct ConfigPopup<'a> {
+    /// Values of [`EDITABLE_KEYS`], in the same order. `None` when not set
+    values: Result<Vec<Option<String>>, String>,
+    list_state: ListState,
+    form: Option<ConfigForm<'a>>,
+    error: Option<String>,
+    config: Config,
+}
+
+impl ConfigPopup<'_> {
+    pub fn new(config: Config, commander: &mut Commander) -> Self {
+        let mut popup = Self {
+            values: Ok(vec![]),
+            list_state: ListState::default().with_selected(Some(0)),
+            form: None,
+            error: None,
+            config,
+        };
+        popup.refresh(commander);
+        popup
+    }
+
+    fn refresh(&mut self, commander: &mut Commander) {
+        self.values = EDITABLE_KEYS
+            .iter()
+            .map(|(name, _)| commander.get_config_value(name))
+            .collect::<Result<_, _>>()
+            .map_err(|err| format!("{err:#}"));
+    }
+
+    fn scroll(&mut self, scroll: isize) {
+        self.list_state.select(Some(
+            self.list_state
+                .selected()
+                .map(|selected| selected.saturating_add_signed(scroll))
+                .unwrap_or(0)
+                .min(EDITABLE_KEYS.len() - 1),
+        ));
+    }
+
+    fn value(&self, index: usize) -> Option<&str> {
+        self.values.as_ref().ok()?.get(index)?.as_deref()
+    }
+
+    /// Set the value of the form. On failure, jj's error is shown and the form stays open
+    fn save_form(&mut self, commander: &mut Commander) {
+        let Some(form) = self.form.as_ref() else {
+            return;
+        };
+        let value = form.value.lines().join("").trim().to_owned();
+
+        match commander.set_config_value(form.scope, form.name, &value) {
+            Ok(()) => {
+                self.form = None;
+                self.error = None;
+                self.refresh(commander);
+            }
+            Err(err) => self.error = Some(err.to_string()),
+        }
+    }
+}
+
+impl Component for ConfigPopup<'_> {
+    fn draw(&mut self, f: &mut ratatui::prelude::Frame<'_>, area: Rect) -> Result<()> {
+        let block = create_popup_block("Settings");
+        let area = centered_rect(area, 60, 60);
+        f.render_widget(Clear, area);
+        f.render_widget(&block, area);
+
+        let popup_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Fill(1),
+                Constraint::Length(if self.form.is_some() { 3 } else { 0 }),
+                Constraint::Length(if self.error.is_some() { 3 } else { 0 }),
+                Constraint::Length(2),
+            ])
+            .split(block.inner(area));
+
+        let items: Vec<Line> = match self.values.as_ref() {
+            Ok(_) => EDITABLE_KEYS
+                .iter()
+                .enumerate()
+                .map(|(index, (name, description))| {
+                    let value = match self.value(index) {
+                        Some(value) => Span::raw(value.to_owned()),
+                        None => Span::raw("(not set)").fg(Color::DarkGray),
+                    };
+                    Line::from(vec![
+                        Span::raw(format!("{name:<16}")).fg(Color::Magenta),
+                        value,
+                        Span::raw(format!("  {description}")).fg(Color::DarkGray),
+                    ])
+                })
+                .collect(),
+            Err(err) => vec![Line::from(err.clone()).fg(Color::Red)],
+        };
+        let list =
+            List::new(items).highlight_style(Style::default().bg(self.config.highlight_color()));
+        f.render_stateful_widget(list, popup_chunks[0], &mut self.list_state);
+
+        if let Some(form) = self.form.as_mut() {
+            let scope = match form.scope {
+                ConfigScope::User => "user",
+                ConfigScope::Repo => "repo",
+            };
+            form.value.set_block(
+                Block::bordered()
+                    .title(format!(" {} ({scope} config) ", form.name))
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(Color::Green)),
+            );
+            f.render_widget(&form.value, popup_chunks[1]);
+        }
+
+        if let Some(error) = self.error.as_ref() {
+            f.render_widget(
+                Paragraph::new(error.as_str())
+                    .fg(Color::Red)
+                    .wrap(Wrap { trim: false }),
+                popup_chunks[2],
+            );
+        }
+
+        let help = if self.form.is_some() {
+            "Tab: switch user/repo config | Enter: save | Escape: cancel"
+        } else {
+            "j/k: scroll | e/Enter: edit | Escape: close"
+        };
+        let help = Paragraph::new(vec![help.into()])
+            .fg(Color::DarkGray)
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::TOP)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(Color::DarkGray)),
+            );
+        f.render_widget(help, popup_chunks[3]);
+
+        Ok(())
+    }
+
+    fn input(&mut self, commander: &mut Commander, event: Event) -> Result<ComponentInputResult> {
+        let Event::Key(key) = event else {
+            return Ok(ComponentInputResult::Handled);
+        };
+        if key.kind != KeyEventKind::Press {
+            return Ok(ComponentInputResult::Handled);
+        }
+
+        if let Some(form) = self.form.as_mut() {
+            match key.code {
+                KeyCode::Esc => {
+                    self.form = None;
+                    self.error = None;
+                }
+                KeyCode::Enter => self.save_form(commander),
+                KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.save_form(commander);
+                }
+                KeyCode::Tab => form.scope = form.scope.next(),
+                _ => {
+                    form.value.input(event);
+                }
+            }
+            return Ok(ComponentInputResult::Handled);
+        }
+
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => self.scroll(1),
+            KeyCode::Char('k') | KeyCode::Up => self.scroll(-1),
+            KeyCode::Char('e') | KeyCode::Enter => {
+                if let Some(selected) = self.list_state.selected()
+                    && let Some(&(name, _)) = EDITABLE_KEYS.get(selected)
+                {
+                    let mut value =
+                        TextArea::new(vec![self.value(selected).unwrap_or_default().to_owned()]);
+                    value.move_cursor(CursorMove::End);
+                    self.form = Some(ConfigForm {
+                        name,
+                        value,
+                        scope: ConfigScope::User,
+                    });
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('q') => return Ok(ComponentInputResult::NotHandled),
+            _ => {}
+        }
+
+        Ok(ComponentInputResult::Handled)
+    }
+}
@@ -0,0 +1,120 @@
+use anyhow::Result;
+use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEventKind},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+};
+
+use crate::{
+    ComponentInputResult,
+    commander::Commander,
+    env::Config,
+    ui::{
+        Component, ComponentAction, styles::create_popup_block, utils::centered_rect_line_height,
+    },
+};
+
+/// Action run once a [ConfirmPopup] is confirmed
+type ConfirmAction = Box<dyn FnOnce(&mut Commander) -> Result<ComponentAction>>;
+
+/// Yes/no popup before a destructive action, naming what it applies to.
+/// `Enter` or `y` confirms, `Esc` or `n` cancels.
+pub struct ConfirmPopup {
+    title: String,
+    lines: Vec<Line<'static>>,
+    on_confirm: Option<ConfirmAction>,
+}
+
+impl ConfirmPopup {
+    pub fn new(
+        title: impl Into<String>,
+        lines: Vec<Line<'static>>,
+        on_confirm: impl FnOnce(&mut Commander) -> Result<ComponentAction> + 'static,
+    ) -> Self {
+        Self {
+            title: title.into(),
+            lines,
+            on_confirm: Some(Box::new(on_confirm)),
+        }
+    }
+}
+
+/// Ask for confirmation before running `on_confirm`, or run it at once when confirmations are
+/// disabled with `lazyjj.confirm-destructive`
+pub fn confirm(
+    config: &Config,
+    commander: &mut Commander,
+    title: impl Into<String>,
+    lines: Vec<Line<'static>>,
+    on_confirm: impl FnOnce(&mut Commander) -> Result<ComponentAction> + 'static,
+) -> Result<ComponentAction> {
+    if config.confirm_destructive() {
+        Ok(ComponentAction::SetPopup(Some(Box::new(
+            ConfirmPopup::new(title, lines, on_confirm),
+        ))))
+    } else {
+        on_confirm(commander)
+    }
+}
+
+impl Component for ConfirmPopup {
+    fn draw(&mut self, f: &mut ratatui::prelude::Frame<'_>, area: Rect) -> Result<()> {
+        let block = create_popup_block(&self.title);
+        let area = centered_rect_line_height(area, 50, self.lines.len() as u16 + 5);
+        f.render_widget(Clear, area);
+        f.render_widget(&block, area);
+
+        let popup_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Fill(1), Constraint::Length(2)])
+            .split(block.inner(area));
+
+        f.render_widget(
+            Paragraph::new(self.lines.clone())
+                .alignment(Alignment::Center)
+                .wrap(Wrap { trim: false }),
+            popup_chunks[0],
+        );
+
+        let help = Paragraph::new(vec!["y/Enter: confirm | n/Escape: cancel".into()])
+            .fg(Color::DarkGray)
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::TOP)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(Color::DarkGray)),
+            );
+        f.render_widget(help, popup_chunks[1]);
+
+        Ok(())
+    }
+
+    fn input(&mut self, commander: &mut Commander, event: Event) -> Result<ComponentInputResult> {
+        if let Event::Key(key) = event
+            && key.kind == KeyEventKind::Press
+        {
+            match key.code {
+                KeyCode::Char('y') | KeyCode::Enter => {
+                    let Some(on_confirm) = self.on_confirm.take() else {
+                        return Ok(ComponentInputResult::NotHandled);
+                    };
+                    return Ok(ComponentInputResult::HandledAction(
+                        ComponentAction::Multiple(vec![
+                            ComponentAction::SetPopup(None),
+                            on_confirm(commander)?,
+                        ]),
+                    ));
+                }
+                KeyCode::Char('n' | 'q') | KeyCode::Esc => {
+                    return Ok(ComponentInputResult::NotHandled);
+                }
+                _ => {}
+            }
+        }
+
+        Ok(ComponentInputResult::Handled)
+    }
+}
@@ -0,0 +1,173 @@
+use anyhow::Result;
+use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEventKind},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListState, Paragraph},
+};
+
+use crate::{
+    ComponentInputResult,
+    commander::{Commander, log::ConflictedCommit},
+    env::Config,
+    ui::{Component, ComponentAction, styles::create_popup_block, utils::centered_rect},
+};
+
+/// Popup listing every conflicted commit in the repo. Selecting one shows it in the log.
+pub struct ConflictsPopup {
+    conflicts: Result<Vec<ConflictedCommit>, String>,
+    list_state: ListState,
+    list_height: u16,
+    config: Config,
+}
+
+impl ConflictsPopup {
+    pub fn new(config: Config, commander: &mut Commander) -> Self {
+        let mut popup = Self {
+            conflicts: Ok(vec![]),
+            list_state: ListState::default(),
+            list_height: 0,
+            config,
+        };
+        popup.refresh(commander);
+        popup
+    }
+
+    fn refresh(&mut self, commander: &mut Commander) {
+        self.conflicts = commander
+            .get_conflicted_commits()
+            .map_err(|err| err.to_string());
+        let has_conflicts = self
+            .conflicts
+            .as_ref()
+            .is_ok_and(|conflicts| !conflicts.is_empty());
+        self.list_state
+            .select(if has_conflicts { Some(0) } else { None });
+    }
+
+    fn scroll(&mut self, scroll: isize) {
+        let Ok(conflicts) = self.conflicts.as_ref() else {
+            return;
+        };
+        if conflicts.is_empty() {
+            return;
+        }
+        self.list_state.select(Some(
+            self.list_state
+                .selected()
+                .map(|selected| selected.saturating_add_signed(scroll))
+                .unwrap_or(0)
+                .min(conflicts.len() - 1),
+        ));
+    }
+}
+
+impl Component for ConflictsPopup {
+    fn draw(&mut self, f: &mut ratatui::prelude::Frame<'_>, area: Rect) -> Result<()> {
+        let title = match self.conflicts.as_ref() {
+            Ok(conflicts) => format!("Conflicts ({})", conflicts.len()),
+            Err(_) => "Conflicts".to_owned(),
+        };
+        let block = create_popup_block(&title);
+        let area = centered_rect(area, 60, 60);
+        f.render_widget(Clear, area);
+        f.render_widget(&block, area);
+
+        let popup_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Fill(1), Constraint::Length(2)])
+            .split(block.inner(area));
+        self.list_height = popup_chunks[0].height;
+
+        match self.conflicts.as_ref() {
+            Ok(conflicts) if conflicts.is_empty() => {
+                f.render_widget(
+                    Paragraph::new("No conflicts in the repository").alignment(Alignment::Center),
+                    popup_chunks[0],
+                );
+            }
+            Ok(conflicts) => {
+                let list_items = conflicts.iter().map(|conflict| {
+                    let files = if conflict.conflicts == 1 {
+                        "1 file".to_owned()
+                    } else {
+                        format!("{} files", conflict.conflicts)
+                    };
+                    let description = if conflict.description.is_empty() {
+                        Span::raw("(no description set)").fg(Color::Yellow)
+                    } else {
+                        Span::raw(conflict.description.clone())
+                    };
+                    let mut change_id = conflict.head.change_id.to_string();
+                    change_id.truncate(8);
+                    Line::from(vec![
+                        Span::raw(change_id).fg(Color::Magenta),
+                        Span::raw(" "),
+                        Span::raw(format!("{files:>8}")).fg(Color::Red),
+                        Span::raw(" "),
+                        description,
+                    ])
+                });
+
+                let list = List::new(list_items)
+                    .scroll_padding(3)
+                    .highlight_style(Style::default().bg(self.config.highlight_color()));
+                f.render_stateful_widget(list, popup_chunks[0], &mut self.list_state);
+            }
+            Err(err) => {
+                f.render_widget(Paragraph::new(err.as_str()).fg(Color::Red), popup_chunks[0]);
+            }
+        }
+
+        let help = Paragraph::new(vec![
+            "j/k: scroll down/up | Enter: show in log | R: refresh | Escape: close".into(),
+        ])
+        .fg(Color::DarkGray)
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::TOP)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+        f.render_widget(help, popup_chunks[1]);
+
+        Ok(())
+    }
+
+    fn input(&mut self, commander: &mut Commander, event: Event) -> Result<ComponentInputResult> {
+        if let Event::Key(key) = event
+            && key.kind == KeyEventKind::Press
+        {
+            match key.code {
+                KeyCode::Char('j') | KeyCode::Down => self.scroll(1),
+                KeyCode::Char('k') | KeyCode::Up => self.scroll(-1),
+                KeyCode::Char('J') => self.scroll(self.list_height as isize / 2),
+                KeyCode::Char('K') => self.scroll((self.list_height as isize / 2).saturating_neg()),
+                KeyCode::Char('R') | KeyCode::F(5) => self.refresh(commander),
+                KeyCode::Enter => {
+                    let selected = self.list_state.selected().and_then(|index| {
+                        self.conflicts
+                            .as_ref()
+                            .ok()
+                            .and_then(|conflicts| conflicts.get(index))
+                    });
+                    let Some(conflict) = selected else {
+                        return Ok(ComponentInputResult::NotHandled);
+                    };
+                    return Ok(ComponentInputResult::HandledAction(
+                        ComponentAction::Multiple(vec![
+                            ComponentAction::SetPopup(None),
+                            ComponentAction::ViewLog(conflict.head.clone()),
+                        ]),
+                    ));
+                }
+                _ => return Ok(ComponentInputResult::NotHandled),
+            }
+            return Ok(ComponentInputResult::Handled);
+        }
+
+        Ok(ComponentInputResult::NotHandled)
+    }
+}
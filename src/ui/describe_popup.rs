@@ -0,0 +1,115 @@
+use anyhow::Result;
+use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+use tui_textarea::{CursorMove, TextArea};
+
+use crate::{
+    ComponentInputResult,
+    commander::{Commander, log::Head},
+    ui::{Component, ComponentAction, styles::create_popup_block, utils::centered_rect},
+};
+
+/// Popup to edit the description of a change, prefilled with the current description.
+/// The description can span several lines, `Enter` starts a new one.
+pub struct DescribePopup<'a> {
+    head: Head,
+    describe_textarea: TextArea<'a>,
+    /// Error of the last save, the description is kept to fix it
+    error: Option<String>,
+}
+
+impl DescribePopup<'_> {
+    pub fn new(commander: &mut Commander, head: Head) -> Result<Self> {
+        let mut describe_textarea = TextArea::new(
+            commander
+                .get_commit_description(&head.commit_id)?
+                .split("\n")
+                .map(|line| line.to_string())
+                .collect(),
+        );
+        describe_textarea.move_cursor(CursorMove::End);
+
+        Ok(Self {
+            head,
+            describe_textarea,
+            error: None,
+        })
+    }
+
+    fn save(&mut self, commander: &mut Commander) -> ComponentInputResult {
+        let description = self.describe_textarea.lines().join("\n");
+        match commander.run_describe(self.head.commit_id.as_str(), &description) {
+            Ok(()) => ComponentInputResult::HandledAction(ComponentAction::Multiple(vec![
+                ComponentAction::SetPopup(None),
+                ComponentAction::RefreshTab(),
+            ])),
+            Err(err) => {
+                self.error = Some(format!("{err:#}"));
+                ComponentInputResult::Handled
+            }
+        }
+    }
+}
+
+impl Component for DescribePopup<'_> {
+    fn draw(&mut self, f: &mut ratatui::prelude::Frame<'_>, area: Rect) -> Result<()> {
+        let block = create_popup_block("Describe @");
+        let area = centered_rect(area, 50, 50);
+        f.render_widget(Clear, area);
+        f.render_widget(&block, area);
+
+        let popup_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Fill(1),
+                Constraint::Length(if self.error.is_some() { 1 } else { 0 }),
+                Constraint::Length(2),
+            ])
+            .split(block.inner(area));
+
+        f.render_widget(&self.describe_textarea, popup_chunks[0]);
+
+        if let Some(error) = self.error.as_ref() {
+            f.render_widget(
+                Paragraph::new(error.as_str()).fg(Color::Red),
+                popup_chunks[1],
+            );
+        }
+
+        let help = Paragraph::new(vec![
+            "Enter: new line | Ctrl+s: save | Escape: cancel".into(),
+        ])
+        .fg(Color::DarkGray)
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::TOP)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+        f.render_widget(help, popup_chunks[2]);
+
+        Ok(())
+    }
+
+    fn input(&mut self, commander: &mut Commander, event: Event) -> Result<ComponentInputResult> {
+        if let Event::Key(key) = event
+            && key.kind == KeyEventKind::Press
+        {
+            match key.code {
+                KeyCode::Esc => return Ok(ComponentInputResult::NotHandled),
+                KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(self.save(commander));
+                }
+                _ => {}
+            }
+        }
+
+        self.describe_textarea.input(event);
+        Ok(ComponentInputResult::Handled)
+    }
+}
@@ -1,28 +1,37 @@
-use std::vec;
+use std::{collections::HashSet, vec};
 
-use anyhow::Result;
+use anyhow::{Result, anyhow};
+use itertools::Itertools;
 use tracing::instrument;
 
 use crate::{
     ComponentInputResult,
     commander::{
         CommandError, Commander,
-        files::{Conflict, File},
+        files::{Conflict, DiffType, File},
         log::Head,
     },
     env::{Config, DiffFormat},
     ui::{
-        Component, ComponentAction, help_popup::HelpPopup, message_popup::MessagePopup,
-        panel::DetailsPanel, utils::tabs_to_spaces,
+        Component, ComponentAction,
+        annotate_popup::AnnotatePopup,
+        help_popup::HelpPopup,
+        message_popup::MessagePopup,
+        panel::DetailsPanel,
+        sparse_popup::SparsePopup,
+        split_popup::SplitPopup,
+        squash_files_popup::SquashFilesPopup,
+        utils::{copy_action, describe_in_editor, draw_prompt, head_id_spans, position_indicator},
     },
 };
 
 use ansi_to_tui::IntoText;
 use ratatui::{
-    crossterm::event::{Event, KeyCode, KeyEventKind},
+    crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers},
     prelude::*,
     widgets::*,
 };
+use tui_textarea::{CursorMove, TextArea};
 
 /// Files tab. Shows files in selected change in main panel and selected file diff in details panel
 pub struct FilesTab {
@@ -34,7 +43,21 @@ pub struct FilesTab {
     files_list_state: ListState,
     files_height: u16,
 
+    /// Only files with a path matching this substring or glob are listed
+    filter: Option<String>,
+    filter_textarea: Option<TextArea<'static>>,
+    /// Selected file before filtering, selected again when the filter is cleared
+    unfiltered_file: Option<File>,
+
+    /// List the files as a tree grouped by directory, instead of a flat list
+    tree: bool,
+    collapsed_dirs: HashSet<String>,
+    /// Directory selected in the tree. No file is selected meanwhile
+    selected_dir: Option<String>,
+
     pub file: Option<File>,
+    /// Paths of the files marked to be split out of the change or restored
+    marked_files: HashSet<String>,
     diff_panel: DetailsPanel,
     diff_output: Result<Option<String>, CommandError>,
     diff_format: DiffFormat,
@@ -42,21 +65,158 @@ pub struct FilesTab {
     config: Config,
 }
 
-fn get_current_file_index(
+fn get_current_file_index<'f>(
     current_file: Option<&File>,
-    files_output: Result<&Vec<File>, &CommandError>,
+    files: impl IntoIterator<Item = &'f File>,
 ) -> Option<usize> {
-    if let (Some(current_file), Ok(files_output)) = (current_file, files_output)
+    if let Some(current_file) = current_file
         && let Some(path) = current_file.path.as_ref()
     {
-        return files_output
-            .iter()
+        return files
+            .into_iter()
             .position(|file| file.path.as_ref() == Some(path));
     }
 
     None
 }
 
+/// Row of the files list, a file or in the tree a directory
+enum FileRow<'f> {
+    Dir {
+        path: String,
+        depth: usize,
+        collapsed: bool,
+    },
+    File {
+        file: &'f File,
+        depth: usize,
+    },
+}
+
+/// Path used to place a file in the tree, the new path for renames
+fn file_tree_path(file: &File) -> &str {
+    file.target_path().unwrap_or(&file.line)
+}
+
+/// Rows listing the files, either flat or as a tree grouped by directory.
+/// Files and directories inside collapsed directories are left out
+fn file_rows<'f>(
+    mut files: Vec<&'f File>,
+    tree: bool,
+    collapsed_dirs: &HashSet<String>,
+) -> Vec<FileRow<'f>> {
+    if !tree {
+        return files
+            .into_iter()
+            .map(|file| FileRow::File { file, depth: 0 })
+            .collect();
+    }
+
+    files.sort_by(|a, b| {
+        file_tree_path(a)
+            .split('/')
+            .cmp(file_tree_path(b).split('/'))
+    });
+
+    let mut rows = vec![];
+    // Directories containing the previous file, and whether each is collapsed
+    let mut dirs: Vec<(&str, bool)> = vec![];
+    for file in files {
+        let mut components: Vec<&str> = file_tree_path(file).split('/').collect();
+        components.pop();
+
+        let common = dirs
+            .iter()
+            .zip(&components)
+            .take_while(|((dir, _), component)| dir == component)
+            .count();
+        dirs.truncate(common);
+        for component in &components[common..] {
+            let path = dirs
+                .iter()
+                .map(|(dir, _)| *dir)
+                .chain([*component])
+                .join("/");
+            let collapsed = collapsed_dirs.contains(&path);
+            if !dirs.iter().any(|(_, collapsed)| *collapsed) {
+                rows.push(FileRow::Dir {
+                    path,
+                    depth: dirs.len(),
+                    collapsed,
+                });
+            }
+            dirs.push((*component, collapsed));
+        }
+
+        if !dirs.iter().any(|(_, collapsed)| *collapsed) {
+            rows.push(FileRow::File {
+                file,
+                depth: dirs.len(),
+            });
+        }
+    }
+    rows
+}
+
+/// Index of the row of the selected directory or file
+fn selected_row(
+    rows: &[FileRow],
+    file: Option<&File>,
+    selected_dir: Option<&str>,
+) -> Option<usize> {
+    rows.iter().position(|row| match row {
+        FileRow::Dir { path, .. } => selected_dir == Some(path.as_str()),
+        FileRow::File { file: row_file, .. } => {
+            selected_dir.is_none()
+                && file.is_some_and(|file| file.path.is_some() && file.path == row_file.path)
+        }
+    })
+}
+
+/// Line of a file in the tree: its status and its name, with the old and new names for renames
+fn file_tree_line(file: &File) -> String {
+    let path = file.path.as_deref().unwrap_or(&file.line);
+    let dir = file_tree_path(file)
+        .rsplit_once('/')
+        .map(|(dir, _)| format!("{dir}/"))
+        .unwrap_or_default();
+    let status = file.line.chars().next().unwrap_or(' ');
+    format!("{status} {}", path.strip_prefix(&dir).unwrap_or(path))
+}
+
+/// Files with a path matching the filter, or all files without a filter
+fn filter_files<'f>(files: &'f [File], filter: Option<&str>) -> Vec<&'f File> {
+    files
+        .iter()
+        .filter(|file| {
+            filter.is_none_or(|filter| {
+                matches_filter(file.path.as_deref().unwrap_or(&file.line), filter)
+            })
+        })
+        .collect()
+}
+
+/// Whether a path matches a filter. Filters with `*` or `?` are globs matching the whole path,
+/// others match a part of the path, ignoring case
+fn matches_filter(path: &str, filter: &str) -> bool {
+    fn glob_match(pattern: &[char], text: &[char]) -> bool {
+        match pattern.split_first() {
+            None => text.is_empty(),
+            Some(('*', rest)) => (0..=text.len()).any(|skip| glob_match(rest, &text[skip..])),
+            Some(('?', rest)) => !text.is_empty() && glob_match(rest, &text[1..]),
+            Some((c, rest)) => text.first() == Some(c) && glob_match(rest, &text[1..]),
+        }
+    }
+
+    if filter.contains(['*', '?']) {
+        let pattern: Vec<char> = filter.chars().collect();
+        let path: Vec<char> = path.chars().collect();
+        glob_match(&pattern, &path)
+    } else {
+        path.to_lowercase().contains(&filter.to_lowercase())
+    }
+}
+
 impl FilesTab {
     #[instrument(level = "trace", skip(commander))]
     pub fn new(commander: &mut Commander, head: &Head) -> Result<Self> {
@@ -77,13 +237,11 @@ impl FilesTab {
             .map(|current_change| {
                 commander.get_file_diff(&head, current_change, &diff_format, true)
             })
-            .map_or(Ok(None), |r| {
-                r.map(|diff| diff.map(|diff| tabs_to_spaces(&diff)))
-            });
+            .unwrap_or(Ok(None));
 
         let files_list_state = ListState::default().with_selected(get_current_file_index(
             current_file.as_ref(),
-            files_output.as_ref(),
+            files_output.iter().flatten(),
         ));
 
         Ok(Self {
@@ -95,11 +253,25 @@ impl FilesTab {
             files_list_state,
             files_height: 0,
 
+            filter: None,
+            filter_textarea: None,
+            unfiltered_file: None,
+
+            tree: commander.env.config.files_tree(),
+            collapsed_dirs: HashSet::new(),
+            selected_dir: None,
+
             conflicts_output,
 
+            marked_files: HashSet::new(),
             diff_output,
             diff_format,
-            diff_panel: DetailsPanel::new(),
+            diff_panel: DetailsPanel::new()
+                .with_highlight_color(commander.env.config.highlight_color())
+                .with_tab_width(commander.env.config.tab_width())
+                .with_wrap(commander.env.config.details_wrap())
+                .with_syntax_highlighting(commander.env.config.syntax_highlighting())
+                .with_theme_colors(commander.env.config.theme_colors()),
 
             config: commander.env.config.clone(),
         })
@@ -108,6 +280,12 @@ impl FilesTab {
     pub fn set_head(&mut self, commander: &mut Commander, head: &Head) -> Result<()> {
         self.head = head.clone();
         self.is_current_head = self.head == commander.get_current_head()?;
+        self.marked_files.clear();
+        self.filter = None;
+        self.filter_textarea = None;
+        self.unfiltered_file = None;
+        self.collapsed_dirs.clear();
+        self.selected_dir = None;
 
         self.refresh_files(commander)?;
         self.file = self
@@ -121,8 +299,71 @@ impl FilesTab {
         Ok(())
     }
 
-    pub fn get_current_file_index(&self) -> Option<usize> {
-        get_current_file_index(self.file.as_ref(), self.files_output.as_ref())
+    /// Index of the row of the selected directory or file
+    fn get_selected_row(&self) -> Option<usize> {
+        selected_row(
+            &self.rows(),
+            self.file.as_ref(),
+            self.selected_dir.as_deref(),
+        )
+    }
+
+    fn rows(&self) -> Vec<FileRow<'_>> {
+        file_rows(self.listed_files(), self.tree, &self.collapsed_dirs)
+    }
+
+    /// Select the directory or file on a row. The diff is not refreshed
+    fn select_row(&mut self, index: usize) {
+        let (file, selected_dir) = match self.rows().get(index) {
+            Some(FileRow::Dir { path, .. }) => (None, Some(path.clone())),
+            Some(FileRow::File { file, .. }) => (Some((*file).clone()), None),
+            None => (None, None),
+        };
+        self.file = file;
+        self.selected_dir = selected_dir;
+    }
+
+    /// Select the first file if the selection is no longer listed, e.g. after filtering
+    fn fix_selection(&mut self) {
+        if self.get_selected_row().is_none() {
+            let index = self
+                .rows()
+                .iter()
+                .position(|row| matches!(row, FileRow::File { .. }))
+                .unwrap_or(0);
+            self.select_row(index);
+        }
+    }
+
+    /// Files listed in the tab, which match the filter if there is one
+    fn listed_files(&self) -> Vec<&File> {
+        match self.files_output.as_ref() {
+            Ok(files) => filter_files(files, self.filter.as_deref()),
+            Err(_) => vec![],
+        }
+    }
+
+    /// Change the filter, selecting the first listed file if the selected one is filtered out.
+    /// Clearing the filter selects the file which was selected before filtering, if still present
+    fn set_filter(&mut self, commander: &mut Commander, filter: Option<String>) -> Result<()> {
+        if self.filter.is_none() && filter.is_some() {
+            self.unfiltered_file = self.file.clone();
+        }
+        self.filter = filter;
+
+        let previous_file = self.file.clone();
+        if self.filter.is_none()
+            && let Some(unfiltered_file) = self.unfiltered_file.take()
+            && get_current_file_index(Some(&unfiltered_file), self.listed_files()).is_some()
+        {
+            self.file = Some(unfiltered_file);
+            self.selected_dir = None;
+        }
+        self.fix_selection();
+        if self.file != previous_file {
+            self.refresh_diff(commander)?;
+        }
+        Ok(())
     }
 
     pub fn refresh_files(&mut self, commander: &mut Commander) -> Result<()> {
@@ -138,10 +379,9 @@ impl FilesTab {
             .map(|current_file| {
                 commander.get_file_diff(&self.head, current_file, &self.diff_format, true)
             })
-            .map_or(Ok(None), |r| {
-                r.map(|diff| diff.map(|diff| tabs_to_spaces(&diff)))
-            });
+            .unwrap_or(Ok(None));
         self.diff_panel.scroll_to(0);
+        self.diff_panel.clear_cursor();
         Ok(())
     }
 
@@ -153,34 +393,140 @@ impl FilesTab {
         Ok(())
     }
 
-    fn scroll_files(&mut self, commander: &mut Commander, scroll: isize) -> Result<()> {
-        if let Ok(files) = self.files_output.as_ref() {
-            let current_file_index = self.get_current_file_index();
-            let next_file = match current_file_index {
-                Some(current_file_index) => files.get(
-                    current_file_index
-                        .saturating_add_signed(scroll)
-                        .min(files.len() - 1),
-                ),
-                None => files.first(),
-            }
-            .map(|x| x.to_owned());
-            if let Some(next_file) = next_file {
-                self.file = Some(next_file.to_owned());
-                self.refresh_diff(commander)?;
+    /// Restore paths from the parents of the change, then keep the selection at the same position
+    fn restore_files(
+        &mut self,
+        commander: &mut Commander,
+        paths: &[String],
+    ) -> Result<ComponentInputResult> {
+        let changed_paths: Vec<&str> = paths
+            .iter()
+            .map(String::as_str)
+            .filter(|path| {
+                self.files_output
+                    .as_ref()
+                    .is_ok_and(|files| files.iter().any(|file| file.target_path() == Some(*path)))
+            })
+            .collect();
+        if changed_paths.is_empty() {
+            return Ok(ComponentInputResult::HandledAction(
+                ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                    title: "Restore".into(),
+                    messages: "No changes to restore".into(),
+                    text_align: None,
+                }))),
+            ));
+        }
+
+        let selected_row = self.get_selected_row();
+        if let Err(err) = commander.run_restore_changes(&self.head.commit_id, &changed_paths) {
+            return Ok(ComponentInputResult::HandledAction(
+                ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                    title: "Restore".into(),
+                    messages: format!("{err:#}").into_text()?,
+                    text_align: None,
+                }))),
+            ));
+        }
+        self.reload(commander, selected_row)?;
+
+        Ok(ComponentInputResult::Handled)
+    }
+
+    /// Copy the full contents of the selected file at the change to the clipboard. Large files
+    /// ask for confirmation first
+    fn copy_file_content(&self, commander: &mut Commander) -> Result<ComponentInputResult> {
+        let Some(file) = self.file.as_ref() else {
+            return Ok(ComponentInputResult::Handled);
+        };
+        let Some(path) = file.target_path() else {
+            return Ok(ComponentInputResult::Handled);
+        };
+        let content = match file.diff_type {
+            Some(DiffType::Deleted) => Err(anyhow!("{path} is deleted in this change")),
+            _ => commander
+                .get_file_content(&self.head, path)
+                .map_err(anyhow::Error::from),
+        };
+        let content = match content {
+            Ok(content) => content,
+            Err(err) => {
+                return Ok(ComponentInputResult::HandledAction(
+                    ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                        title: "Copy error".into(),
+                        messages: format!("{err:#}").into_text()?,
+                        text_align: None,
+                    }))),
+                ));
             }
+        };
+
+        Ok(ComponentInputResult::HandledAction(copy_action(
+            path, content,
+        )?))
+    }
+
+    /// Reload the change after it was edited. Marks on files which are gone are dropped, and
+    /// when the selected file is gone, the row which took its place is selected
+    fn reload(&mut self, commander: &mut Commander, selected_row: Option<usize>) -> Result<()> {
+        self.head = commander.get_head_latest(&self.head)?;
+        self.refresh_files(commander)?;
+        if let Ok(files) = self.files_output.as_ref() {
+            self.marked_files
+                .retain(|path| files.iter().any(|file| file.target_path() == Some(path)));
+        }
+        if self.get_selected_row().is_none() {
+            let rows = self.rows().len();
+            self.select_row(selected_row.unwrap_or(0).min(rows.saturating_sub(1)));
+        }
+        self.refresh_diff(commander)
+    }
+
+    fn scroll_files(&mut self, commander: &mut Commander, scroll: isize) -> Result<()> {
+        let rows = self.rows().len();
+        if rows == 0 {
+            return Ok(());
+        }
+        let index = match self.get_selected_row() {
+            Some(selected_row) => selected_row.saturating_add_signed(scroll).min(rows - 1),
+            None => 0,
+        };
+        let previous_file = self.file.clone();
+        self.select_row(index);
+        if self.file != previous_file {
+            self.refresh_diff(commander)?;
         }
         Ok(())
     }
+
+    /// Collapse or expand a directory of the tree
+    fn set_dir_collapsed(&mut self, path: &str, collapsed: bool) {
+        if collapsed {
+            self.collapsed_dirs.insert(path.to_owned());
+        } else {
+            self.collapsed_dirs.remove(path);
+        }
+    }
+
+    /// Paths of the listed files inside a directory
+    fn dir_file_paths(&self, dir: &str) -> Vec<String> {
+        self.listed_files()
+            .into_iter()
+            .filter_map(|file| file.target_path())
+            .filter(|path| path.starts_with(&format!("{dir}/")))
+            .map(str::to_owned)
+            .collect()
+    }
 }
 
 impl Component for FilesTab {
     fn focus(&mut self, commander: &mut Commander) -> Result<()> {
         self.is_current_head = self.head == commander.get_current_head()?;
-        self.head = commander.get_head_latest(&self.head)?;
-        self.refresh_files(commander)?;
-        self.refresh_diff(commander)?;
-        Ok(())
+        self.reload(commander, self.get_selected_row())
+    }
+
+    fn update(&mut self, _commander: &mut Commander) -> Result<Option<ComponentAction>> {
+        self.diff_panel.take_copy_action()
     }
 
     fn draw(
@@ -198,33 +544,77 @@ impl Component for FilesTab {
 
         // Draw files
         {
-            let current_file_index = self.get_current_file_index();
+            let rows = self.files_output.as_ref().map(|files| {
+                file_rows(
+                    filter_files(files, self.filter.as_deref()),
+                    self.tree,
+                    &self.collapsed_dirs,
+                )
+            });
+            let current_row = rows.as_ref().ok().and_then(|rows| {
+                selected_row(rows, self.file.as_ref(), self.selected_dir.as_deref())
+            });
 
-            let mut lines: Vec<Line> = match self.files_output.as_ref() {
-                Ok(files_output) => {
-                    let files_lines = files_output
+            let mut lines: Vec<Line> = match rows.as_ref() {
+                Ok(rows) => {
+                    let files_lines = rows
                         .iter()
                         .enumerate()
-                        .flat_map(|(i, file)| {
-                            file.line
-                                .to_text()
-                                .unwrap()
-                                .iter()
-                                .map(|line| {
-                                    let mut line = line.to_owned();
-
-                                    // Add padding at start
-                                    line.spans.insert(0, Span::from(" "));
-
-                                    if let Some(diff_type) = file.diff_type.as_ref() {
+                        .flat_map(|(i, row)| {
+                            let (row_lines, marked, color) = match row {
+                                FileRow::Dir {
+                                    path,
+                                    depth,
+                                    collapsed,
+                                } => {
+                                    let name = path.rsplit('/').next().unwrap_or(path);
+                                    let arrow = if *collapsed { "▸" } else { "▾" };
+                                    (
+                                        vec![
+                                            Line::from(format!(
+                                                "{}{arrow} {name}/",
+                                                "  ".repeat(*depth)
+                                            ))
+                                            .bold(),
+                                        ],
+                                        false,
+                                        None,
+                                    )
+                                }
+                                FileRow::File { file, depth } => (
+                                    if self.tree {
+                                        vec![Line::from(format!(
+                                            "{}{}",
+                                            "  ".repeat(*depth),
+                                            file_tree_line(file)
+                                        ))]
+                                    } else {
+                                        file.line.to_text().unwrap().lines
+                                    },
+                                    file.target_path()
+                                        .is_some_and(|path| self.marked_files.contains(path)),
+                                    file.diff_type.as_ref().map(|diff_type| diff_type.color()),
+                                ),
+                            };
+                            row_lines
+                                .into_iter()
+                                .map(|mut line| {
+                                    // Add padding at start, marking files to split
+                                    if marked {
+                                        line.spans.insert(0, Span::from("*").bold());
+                                    } else {
+                                        line.spans.insert(0, Span::from(" "));
+                                    }
+
+                                    if let Some(color) = color {
                                         line.spans = line
                                             .spans
                                             .iter_mut()
-                                            .map(|span| span.to_owned().fg(diff_type.color()))
+                                            .map(|span| span.to_owned().fg(color))
                                             .collect();
                                     }
 
-                                    if current_file_index == Some(i) {
+                                    if current_row == Some(i) {
                                         line = line.bg(self.config.highlight_color());
 
                                         line.spans = line
@@ -244,9 +634,13 @@ impl Component for FilesTab {
 
                     if files_lines.is_empty() {
                         vec![
-                            Line::from(" No changed files in change")
-                                .fg(Color::DarkGray)
-                                .italic(),
+                            Line::from(if self.filter.is_some() {
+                                " No changed files match the filter"
+                            } else {
+                                " No changed files in change"
+                            })
+                            .fg(Color::DarkGray)
+                            .italic(),
                         ]
                     } else {
                         files_lines
@@ -255,11 +649,15 @@ impl Component for FilesTab {
                 Err(err) => err.into_text("Error getting files")?.lines,
             };
 
-            let title_change = if self.is_current_head {
-                format!("@ ({})", self.head.change_id)
-            } else {
-                self.head.change_id.as_string()
-            };
+            let mut title = vec![Span::raw(" Files for ")];
+            if self.is_current_head {
+                title.push(Span::raw("@ "));
+            }
+            title.extend(head_id_spans(&self.head, self.config.primary_id()));
+            title.push(Span::raw(" "));
+            if let Some(filter) = self.filter.as_ref() {
+                title.push(Span::raw(format!("matching {filter} ")).fg(Color::Cyan));
+            }
 
             if !self.conflicts_output.is_empty() {
                 lines.push(Line::default());
@@ -269,18 +667,20 @@ impl Component for FilesTab {
                 }
             }
 
-            let files = List::new(lines)
-                .block(
-                    Block::bordered()
-                        .title(" Files for ".to_owned() + &title_change + " ")
-                        .border_type(BorderType::Rounded),
-                )
-                .scroll_padding(3);
-            *self.files_list_state.selected_mut() = current_file_index;
+            let mut files_block = Block::bordered()
+                .title(Line::from(title))
+                .border_type(BorderType::Rounded);
+            if let Ok(rows) = rows.as_ref()
+                && !rows.is_empty()
+            {
+                files_block = files_block.title_bottom(position_indicator(current_row, rows.len()));
+            }
+            let files = List::new(lines).block(files_block).scroll_padding(3);
+            *self.files_list_state.selected_mut() = current_row;
             f.render_stateful_widget(&files, chunks[0], &mut self.files_list_state);
             self.files_height = chunks[0].height - 2;
 
-            if let Some(index) = current_file_index
+            if let Some(index) = current_row
                 && files.len() > self.files_height as usize
             {
                 let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight);
@@ -297,6 +697,10 @@ impl Component for FilesTab {
                     &mut scrollbar_state,
                 );
             }
+
+            if let Some(filter_textarea) = self.filter_textarea.as_ref() {
+                draw_prompt(f, chunks[0], " Filter ", filter_textarea);
+            }
         }
 
         // Draw diff
@@ -317,6 +721,30 @@ impl Component for FilesTab {
     }
 
     fn input(&mut self, commander: &mut Commander, event: Event) -> Result<ComponentInputResult> {
+        if let Some(filter_textarea) = self.filter_textarea.as_mut() {
+            if let Event::Key(key) = event {
+                match key.code {
+                    KeyCode::Enter => {
+                        self.filter_textarea = None;
+                        return Ok(ComponentInputResult::Handled);
+                    }
+                    KeyCode::Esc => {
+                        self.filter_textarea = None;
+                        self.set_filter(commander, None)?;
+                        return Ok(ComponentInputResult::Handled);
+                    }
+                    _ => {}
+                }
+            }
+
+            // Filter as you type, without running jj again
+            if filter_textarea.input(event) {
+                let filter = filter_textarea.lines().join("");
+                self.set_filter(commander, (!filter.is_empty()).then_some(filter))?;
+            }
+            return Ok(ComponentInputResult::Handled);
+        }
+
         if let Event::Key(key) = event {
             if key.kind != KeyEventKind::Press {
                 return Ok(ComponentInputResult::Handled);
@@ -342,6 +770,13 @@ impl Component for FilesTab {
                     self.diff_format = self.diff_format.get_next(self.config.diff_tool());
                     self.refresh_diff(commander)?;
                 }
+                KeyCode::Char('x') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(ComponentInputResult::HandledAction(describe_in_editor(
+                        commander,
+                        &self.head,
+                        ComponentAction::ChangeHead,
+                    )?));
+                }
                 KeyCode::Char('x') => {
                     // this works even for deleted files because jj doesn't return error in that case
                     if self.untrack_file(commander).is_err() {
@@ -355,6 +790,137 @@ impl Component for FilesTab {
                     }
                     self.set_head(commander, &commander.get_current_head()?)?;
                 }
+                KeyCode::Char(' ') => {
+                    if let Some(dir) = self.selected_dir.as_deref() {
+                        // Mark all files of the directory, or unmark them if all are marked
+                        let paths = self.dir_file_paths(dir);
+                        if paths.iter().all(|path| self.marked_files.contains(path)) {
+                            for path in &paths {
+                                self.marked_files.remove(path);
+                            }
+                        } else {
+                            self.marked_files.extend(paths);
+                        }
+                    } else if let Some(path) =
+                        self.file.as_ref().and_then(|file| file.target_path())
+                        && !self.marked_files.remove(path)
+                    {
+                        self.marked_files.insert(path.to_owned());
+                    }
+                    self.scroll_files(commander, 1)?;
+                }
+                KeyCode::Enter | KeyCode::Right | KeyCode::Left if self.selected_dir.is_some() => {
+                    if let Some(dir) = self.selected_dir.clone() {
+                        let collapsed = match key.code {
+                            KeyCode::Right => false,
+                            KeyCode::Left => true,
+                            _ => !self.collapsed_dirs.contains(&dir),
+                        };
+                        self.set_dir_collapsed(&dir, collapsed);
+                    }
+                }
+                KeyCode::Left if self.tree => {
+                    // Select the directory of the file
+                    if let Some(dir) = self
+                        .file
+                        .as_ref()
+                        .and_then(|file| file_tree_path(file).rsplit_once('/'))
+                        .map(|(dir, _)| dir.to_owned())
+                    {
+                        self.selected_dir = Some(dir);
+                        self.file = None;
+                        self.refresh_diff(commander)?;
+                    }
+                }
+                KeyCode::Char('v') => {
+                    self.tree = !self.tree;
+                    let previous_file = self.file.clone();
+                    self.fix_selection();
+                    if self.file != previous_file {
+                        self.refresh_diff(commander)?;
+                    }
+                }
+                KeyCode::Char('s') => {
+                    if self.head.immutable {
+                        return Ok(ComponentInputResult::HandledAction(
+                            ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                                title: "Split".into(),
+                                messages: "Cannot split an immutable change".into(),
+                                text_align: None,
+                            }))),
+                        ));
+                    }
+
+                    // Split the marked files, or the selected one if none are marked
+                    let mut paths: Vec<String> = self.marked_files.iter().cloned().collect();
+                    paths.sort();
+                    if paths.is_empty()
+                        && let Some(path) = self.file.as_ref().and_then(|file| file.target_path())
+                    {
+                        paths.push(path.to_owned());
+                    }
+                    if paths.is_empty() {
+                        return Ok(ComponentInputResult::Handled);
+                    }
+
+                    return Ok(ComponentInputResult::HandledAction(
+                        ComponentAction::SetPopup(Some(Box::new(SplitPopup::new(
+                            self.head.clone(),
+                            paths,
+                        )))),
+                    ));
+                }
+                KeyCode::Char('S') => {
+                    // Pick the files to squash, starting with the marked ones or the selected one
+                    let mut paths: Vec<String> = self.marked_files.iter().cloned().collect();
+                    paths.sort();
+                    if paths.is_empty()
+                        && let Some(path) = self.file.as_ref().and_then(|file| file.target_path())
+                    {
+                        paths.push(path.to_owned());
+                    }
+                    let Some(files) = self.files_output.as_ref().ok().cloned() else {
+                        return Ok(ComponentInputResult::Handled);
+                    };
+                    if files.is_empty() {
+                        return Ok(ComponentInputResult::Handled);
+                    }
+
+                    return Ok(ComponentInputResult::HandledAction(
+                        ComponentAction::SetPopup(Some(Box::new(SquashFilesPopup::new(
+                            commander,
+                            self.head.clone(),
+                            files,
+                            &paths,
+                            self.diff_format.clone(),
+                        )?))),
+                    ));
+                }
+                KeyCode::Char('y') => return self.copy_file_content(commander),
+                KeyCode::Char('b') => {
+                    if let Some(path) = self.file.as_ref().and_then(|file| file.target_path()) {
+                        return Ok(ComponentInputResult::HandledAction(
+                            ComponentAction::SetPopup(Some(Box::new(AnnotatePopup::new(
+                                commander, &self.head, path,
+                            )))),
+                        ));
+                    }
+                }
+                KeyCode::Char('r') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    let mut paths: Vec<String> = self.marked_files.iter().cloned().collect();
+                    paths.sort();
+                    return self.restore_files(commander, &paths);
+                }
+                KeyCode::Char('r') => {
+                    let paths: Vec<String> = self
+                        .file
+                        .as_ref()
+                        .and_then(|file| file.target_path())
+                        .map(str::to_owned)
+                        .into_iter()
+                        .collect();
+                    return self.restore_files(commander, &paths);
+                }
                 KeyCode::Char('R') | KeyCode::F(5) => {
                     self.head = commander.get_head_latest(&self.head)?;
                     self.refresh_files(commander)?;
@@ -364,6 +930,25 @@ impl Component for FilesTab {
                     let head = &commander.get_current_head()?;
                     self.set_head(commander, head)?;
                 }
+                KeyCode::Char('P') => {
+                    let popup: Box<dyn Component> = match SparsePopup::new(commander) {
+                        Ok(popup) => Box::new(popup),
+                        Err(err) => Box::new(MessagePopup {
+                            title: "Sparse".into(),
+                            messages: format!("{err:#}").into_text()?,
+                            text_align: None,
+                        }),
+                    };
+                    return Ok(ComponentInputResult::HandledAction(
+                        ComponentAction::SetPopup(Some(popup)),
+                    ));
+                }
+                KeyCode::Char('/') => {
+                    let mut textarea = TextArea::new(vec![self.filter.clone().unwrap_or_default()]);
+                    textarea.move_cursor(CursorMove::End);
+                    self.filter_textarea = Some(textarea);
+                }
+                KeyCode::Esc if self.filter.is_some() => self.set_filter(commander, None)?,
                 KeyCode::Char('?') => {
                     return Ok(ComponentInputResult::HandledAction(
                         ComponentAction::SetPopup(Some(Box::new(HelpPopup::new(
@@ -371,7 +956,40 @@ impl Component for FilesTab {
                                 ("j/k".to_owned(), "scroll down/up".to_owned()),
                                 ("J/K".to_owned(), "scroll down by ½ page".to_owned()),
                                 ("x".to_owned(), "untrack file".to_owned()),
+                                (
+                                    "Ctrl+x".to_owned(),
+                                    "describe the change in $EDITOR".to_owned(),
+                                ),
+                                ("space".to_owned(), "mark file".to_owned()),
+                                (
+                                    "s".to_owned(),
+                                    "split marked files into a new change".to_owned(),
+                                ),
+                                (
+                                    "S".to_owned(),
+                                    "squash files into another change".to_owned(),
+                                ),
+                                ("b".to_owned(), "annotate file (blame)".to_owned()),
+                                ("y".to_owned(), "copy file contents".to_owned()),
+                                ("r".to_owned(), "restore file from parent".to_owned()),
+                                (
+                                    "Ctrl+r".to_owned(),
+                                    "restore marked files from parent".to_owned(),
+                                ),
                                 ("@".to_owned(), "view current change files".to_owned()),
+                                (
+                                    "P".to_owned(),
+                                    "edit sparse patterns of the working copy".to_owned(),
+                                ),
+                                (
+                                    "/".to_owned(),
+                                    "filter files by path or glob, Esc: clear".to_owned(),
+                                ),
+                                ("v".to_owned(), "toggle tree/flat list".to_owned()),
+                                (
+                                    "Enter/←/→".to_owned(),
+                                    "toggle/collapse/expand directory".to_owned(),
+                                ),
                             ],
                             vec![
                                 ("Ctrl+e/Ctrl+y".to_owned(), "scroll down/up".to_owned()),
@@ -385,6 +1003,19 @@ impl Component for FilesTab {
                                 ),
                                 ("w".to_owned(), "toggle diff format".to_owned()),
                                 ("W".to_owned(), "toggle wrapping".to_owned()),
+                                (
+                                    "</>".to_owned(),
+                                    "scroll long lines left/right when not wrapping".to_owned(),
+                                ),
+                                (
+                                    "#".to_owned(),
+                                    "search details, Enter/Up: next/previous match".to_owned(),
+                                ),
+                                ("V".to_owned(), "toggle side by side git diff".to_owned()),
+                                (
+                                    "Y".to_owned(),
+                                    "copy the diff, or the selected lines".to_owned(),
+                                ),
                             ],
                         )))),
                     ));
@@ -403,3 +1034,63 @@ impl Component for FilesTab {
         Ok(ComponentInputResult::Handled)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_filter_substring_or_glob() {
+        assert!(matches_filter("src/ui/files_tab.rs", "files"));
+        assert!(matches_filter("src/ui/files_tab.rs", "FILES"));
+        assert!(!matches_filter("src/ui/files_tab.rs", "log"));
+        assert!(matches_filter("src/ui/files_tab.rs", "*.rs"));
+        assert!(matches_filter("src/ui/files_tab.rs", "src/*/files_?ab.rs"));
+        assert!(!matches_filter("src/ui/files_tab.rs", "*.md"));
+        assert!(!matches_filter("src/ui/files_tab.rs", "ui/*"));
+    }
+
+    #[test]
+    fn file_rows_tree() {
+        let file = |path: &str| File {
+            line: format!("M {path}"),
+            path: Some(path.to_owned()),
+            diff_type: Some(DiffType::Modified),
+        };
+        let files = [
+            file("src/ui/files_tab.rs"),
+            file("README.md"),
+            file("src/main.rs"),
+            file("src/ui/log_tab.rs"),
+        ];
+        let labels = |collapsed_dirs: &HashSet<String>| -> Vec<String> {
+            file_rows(files.iter().collect(), true, collapsed_dirs)
+                .iter()
+                .map(|row| match row {
+                    FileRow::Dir { path, depth, .. } => format!("{depth} {path}/"),
+                    FileRow::File { file, depth } => format!("{depth} {}", file_tree_line(file)),
+                })
+                .collect()
+        };
+
+        assert_eq!(
+            labels(&HashSet::new()),
+            [
+                "0 M README.md",
+                "0 src/",
+                "1 M main.rs",
+                "1 src/ui/",
+                "2 M files_tab.rs",
+                "2 M log_tab.rs",
+            ]
+        );
+        assert_eq!(
+            labels(&HashSet::from(["src/ui".to_owned()])),
+            ["0 M README.md", "0 src/", "1 M main.rs", "1 src/ui/"]
+        );
+        assert_eq!(
+            labels(&HashSet::from(["src".to_owned()])),
+            ["0 M README.md", "0 src/"]
+        );
+    }
+}
@@ -1,21 +1,31 @@
 use ratatui::{
     crossterm::event::{self, Event, KeyCode},
-    layout::{Constraint, Direction, Layout},
-    style::Stylize,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Stylize},
     text::Span,
-    widgets::{Block, Clear, Row, Table},
+    widgets::{Block, Clear, Paragraph, Row, Table},
 };
+use tui_textarea::{CursorMove, TextArea};
 
 use crate::{
     ComponentInputResult,
-    ui::{Component, styles::create_popup_block, utils::centered_rect},
+    ui::{
+        Component,
+        styles::create_popup_block,
+        utils::{centered_rect, centered_rect_line_height},
+    },
 };
 
+/// Default file name suggested when exporting key mappings
+const EXPORT_DEFAULT_PATH: &str = "lazyjj-keybindings.md";
+
 pub struct HelpPopup {
     pub left_items: Vec<(String, String)>,
     pub right_items: Vec<(String, String)>,
     height: u16,
     scroll: usize,
+    export_textarea: Option<TextArea<'static>>,
+    export_message: Option<String>,
 }
 
 impl HelpPopup {
@@ -26,6 +36,8 @@ impl HelpPopup {
             height: 0,
             // Can't use TableState as it's broken: https://github.com/ratatui-org/ratatui/issues/1179
             scroll: 0,
+            export_textarea: None,
+            export_message: None,
         }
     }
 
@@ -44,6 +56,35 @@ impl HelpPopup {
 
         Table::new(rows, widths).block(Block::new().title(Span::from(title).bold()))
     }
+
+    /// Format the key mappings as a markdown cheat sheet
+    fn to_markdown(&self) -> String {
+        fn table(title: &str, items: &[(String, String)]) -> String {
+            let mut text = format!("## {title}\n\n| Key | Action |\n| --- | --- |\n");
+            for (keys, description) in items {
+                text.push_str(&format!(
+                    "| `{}` | {} |\n",
+                    keys.replace('|', "\\|"),
+                    description.replace('|', "\\|")
+                ));
+            }
+            text
+        }
+
+        [
+            "# lazyjj key mappings\n".to_owned(),
+            table("Main panel", &self.left_items),
+            table("Details panel", &self.right_items),
+        ]
+        .join("\n")
+    }
+
+    fn export(&mut self, path: &str) {
+        self.export_message = Some(match std::fs::write(path, self.to_markdown()) {
+            Ok(()) => format!("Exported key mappings to {path}"),
+            Err(err) => format!("Failed to export key mappings: {err}"),
+        });
+    }
 }
 
 impl Component for HelpPopup {
@@ -57,9 +98,14 @@ impl Component for HelpPopup {
 
         let block = create_popup_block("Help");
         let block_inner = block.inner(area);
-        self.height = block_inner.height;
         f.render_widget(&block, area);
 
+        let popup_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Fill(1), Constraint::Length(1)])
+            .split(block_inner);
+        self.height = popup_chunks[0].height;
+
         let chunks = Layout::default()
             .direction(Direction::Horizontal)
             .constraints([
@@ -67,7 +113,7 @@ impl Component for HelpPopup {
                 Constraint::Length(2),
                 Constraint::Fill(1),
             ])
-            .split(block_inner);
+            .split(popup_chunks[0]);
 
         f.render_widget(
             self.create_table(&self.left_items, "Main panel".into()),
@@ -78,6 +124,20 @@ impl Component for HelpPopup {
             chunks[2],
         );
 
+        let footer = match self.export_message.as_ref() {
+            Some(message) => Paragraph::new(message.as_str()),
+            None => Paragraph::new("j/k: scroll | e: export to file").fg(Color::DarkGray),
+        };
+        f.render_widget(footer.alignment(Alignment::Center), popup_chunks[1]);
+
+        if let Some(export_textarea) = self.export_textarea.as_ref() {
+            let block = create_popup_block("Export key mappings to");
+            let area = centered_rect_line_height(area, 80, 3);
+            f.render_widget(Clear, area);
+            f.render_widget(&block, area);
+            f.render_widget(export_textarea, block.inner(area));
+        }
+
         Ok(())
     }
 
@@ -86,6 +146,28 @@ impl Component for HelpPopup {
         _commander: &mut crate::commander::Commander,
         event: Event,
     ) -> anyhow::Result<crate::ComponentInputResult> {
+        if let Some(export_textarea) = self.export_textarea.as_mut() {
+            if let Event::Key(key) = event {
+                match key.code {
+                    KeyCode::Enter => {
+                        let path = export_textarea.lines().join("");
+                        self.export_textarea = None;
+                        if !path.trim().is_empty() {
+                            self.export(path.trim());
+                        }
+                        return Ok(ComponentInputResult::Handled);
+                    }
+                    KeyCode::Esc => {
+                        self.export_textarea = None;
+                        return Ok(ComponentInputResult::Handled);
+                    }
+                    _ => {}
+                }
+            }
+            export_textarea.input(event);
+            return Ok(ComponentInputResult::Handled);
+        }
+
         if let Event::Key(key) = event
             && key.kind == event::KeyEventKind::Press
         {
@@ -95,6 +177,11 @@ impl Component for HelpPopup {
                     self.scroll = (self.scroll + 1).min(max.saturating_sub(self.height as usize));
                 }
                 KeyCode::Char('k') => self.scroll = self.scroll.saturating_sub(1),
+                KeyCode::Char('e') => {
+                    let mut textarea = TextArea::new(vec![EXPORT_DEFAULT_PATH.to_owned()]);
+                    textarea.move_cursor(CursorMove::End);
+                    self.export_textarea = Some(textarea);
+                }
                 _ => return Ok(ComponentInputResult::NotHandled),
             }
 
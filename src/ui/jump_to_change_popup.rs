@@ -0,0 +1,207 @@
+use anyhow::Result;
+use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEventKind},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span, Text},
+    widgets::{Block, BorderType, Borders, Clear, List, ListState, Paragraph},
+};
+use tui_textarea::TextArea;
+
+use crate::{
+    ComponentInputResult,
+    commander::{Commander, log::PrefixMatch},
+    env::Config,
+    ui::{
+        Component, ComponentAction, message_popup::MessagePopup, styles::create_popup_block,
+        utils::centered_rect,
+    },
+};
+
+/// Popup asking for a change or commit id prefix, to select that commit in the log.
+/// An ambiguous prefix lists the matching commits to pick from.
+pub struct JumpToChangePopup<'a> {
+    textarea: TextArea<'a>,
+    /// Commits matching an ambiguous prefix
+    candidates: Vec<PrefixMatch>,
+    list_state: ListState,
+    list_height: u16,
+    config: Config,
+}
+
+impl JumpToChangePopup<'_> {
+    pub fn new(config: Config) -> Self {
+        Self {
+            textarea: TextArea::default(),
+            candidates: vec![],
+            list_state: ListState::default(),
+            list_height: 0,
+            config,
+        }
+    }
+
+    fn scroll(&mut self, scroll: isize) {
+        if self.candidates.is_empty() {
+            return;
+        }
+        self.list_state.select(Some(
+            self.list_state
+                .selected()
+                .map(|selected| selected.saturating_add_signed(scroll))
+                .unwrap_or(0)
+                .min(self.candidates.len() - 1),
+        ));
+    }
+
+    fn view(candidate: &PrefixMatch) -> ComponentInputResult {
+        ComponentInputResult::HandledAction(ComponentAction::Multiple(vec![
+            ComponentAction::SetPopup(None),
+            ComponentAction::JumpToChange(candidate.head.clone()),
+        ]))
+    }
+
+    fn jump(&mut self, commander: &mut Commander) -> Result<ComponentInputResult> {
+        let prefix = self.textarea.lines().join("").trim().to_owned();
+        if prefix.is_empty() {
+            return Ok(ComponentInputResult::Handled);
+        }
+
+        let message = match commander.find_by_id_prefix(&prefix) {
+            Ok(mut candidates) => match candidates.len() {
+                0 => Text::from(format!("No change or commit id starts with {prefix}")),
+                1 => return Ok(Self::view(&candidates.remove(0))),
+                _ => {
+                    self.candidates = candidates;
+                    self.list_state.select(Some(0));
+                    return Ok(ComponentInputResult::Handled);
+                }
+            },
+            Err(err) => Text::from(format!("{err:#}")),
+        };
+        Ok(ComponentInputResult::HandledAction(
+            ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                title: "Jump to change".into(),
+                messages: message,
+                text_align: None,
+            }))),
+        ))
+    }
+}
+
+impl Component for JumpToChangePopup<'_> {
+    fn draw(&mut self, f: &mut ratatui::prelude::Frame<'_>, area: Rect) -> Result<()> {
+        let block = create_popup_block("Jump to change");
+        let area = centered_rect(area, 60, if self.candidates.is_empty() { 20 } else { 60 });
+        f.render_widget(Clear, area);
+        f.render_widget(&block, area);
+
+        let popup_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Fill(1),
+                Constraint::Length(2),
+            ])
+            .split(block.inner(area));
+
+        self.textarea.set_block(
+            Block::bordered()
+                .title(" Change or commit id ")
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(if self.candidates.is_empty() {
+                    Color::Green
+                } else {
+                    Color::DarkGray
+                })),
+        );
+        f.render_widget(&self.textarea, popup_chunks[0]);
+
+        self.list_height = popup_chunks[1].height;
+        let lines: Vec<Line> = self
+            .candidates
+            .iter()
+            .map(|candidate| {
+                let mut change_id = candidate.head.change_id.to_string();
+                change_id.truncate(12);
+                let mut commit_id = candidate.head.commit_id.to_string();
+                commit_id.truncate(12);
+                let description = if candidate.description.is_empty() {
+                    Span::raw("(no description set)").fg(Color::Yellow)
+                } else {
+                    Span::raw(candidate.description.clone())
+                };
+                Line::from(vec![
+                    Span::raw(change_id).fg(Color::Magenta),
+                    Span::raw(" "),
+                    Span::raw(commit_id).fg(Color::Blue),
+                    Span::raw(" "),
+                    description,
+                ])
+            })
+            .collect();
+        let list = List::new(lines)
+            .scroll_padding(3)
+            .highlight_style(Style::default().bg(self.config.highlight_color()));
+        f.render_stateful_widget(list, popup_chunks[1], &mut self.list_state);
+
+        let help = if self.candidates.is_empty() {
+            "Enter: jump | Escape: cancel".to_owned()
+        } else {
+            format!(
+                "{} matches | j/k: scroll down/up | Enter: jump | Escape: cancel",
+                self.candidates.len()
+            )
+        };
+        let help = Paragraph::new(vec![help.into()])
+            .fg(Color::DarkGray)
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::TOP)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(Color::DarkGray)),
+            );
+        f.render_widget(help, popup_chunks[2]);
+
+        Ok(())
+    }
+
+    fn input(&mut self, commander: &mut Commander, event: Event) -> Result<ComponentInputResult> {
+        let Event::Key(key) = event else {
+            return Ok(ComponentInputResult::Handled);
+        };
+        if key.kind != KeyEventKind::Press {
+            return Ok(ComponentInputResult::Handled);
+        }
+
+        if !self.candidates.is_empty() {
+            match key.code {
+                KeyCode::Char('j') | KeyCode::Down => self.scroll(1),
+                KeyCode::Char('k') | KeyCode::Up => self.scroll(-1),
+                KeyCode::Char('J') => self.scroll(self.list_height as isize / 2),
+                KeyCode::Char('K') => self.scroll((self.list_height as isize / 2).saturating_neg()),
+                KeyCode::Enter => {
+                    if let Some(candidate) = self
+                        .list_state
+                        .selected()
+                        .and_then(|index| self.candidates.get(index))
+                    {
+                        return Ok(Self::view(candidate));
+                    }
+                }
+                KeyCode::Esc | KeyCode::Char('q') => return Ok(ComponentInputResult::NotHandled),
+                _ => {}
+            }
+            return Ok(ComponentInputResult::Handled);
+        }
+
+        match key.code {
+            KeyCode::Enter => return self.jump(commander),
+            KeyCode::Esc => return Ok(ComponentInputResult::NotHandled),
+            _ => {
+                self.textarea.input(event);
+            }
+        }
+        Ok(ComponentInputResult::Handled)
+    }
+}
@@ -3,40 +3,184 @@
 use ansi_to_tui::IntoText;
 use anyhow::Result;
 use ratatui::{
-    crossterm::event::{Event, KeyEventKind},
+    crossterm::event::{Event, KeyCode, KeyEventKind},
     layout::Rect,
     prelude::*,
     widgets::*,
 };
+use std::{
+    collections::HashMap,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    thread,
+    time::Duration,
+};
 use tracing::instrument;
 use tui_confirm_dialog::{ButtonLabel, ConfirmDialog, ConfirmDialogState, Listener};
 use tui_textarea::{CursorMove, TextArea};
 
 use crate::{
     ComponentInputResult,
-    commander::{CommandError, Commander, log::Head},
+    app::{FetchTarget, PushTarget},
+    commander::{
+        CommandError, Commander,
+        bookmarks::PushSummary,
+        ids::CommitId,
+        log::{DEFAULT_LOG_TEMPLATE, DiffStat, Head},
+    },
     env::{Config, DiffFormat},
     keybinds::{LogTabEvent, LogTabKeybinds},
     ui::{
         Component, ComponentAction,
+        abandon_popup::{AbandonPopup, abandon, abandon_lines, abandon_many, abandon_many_lines},
+        bookmark_move_popup::BookmarkMovePopup,
         bookmark_set_popup::BookmarkSetPopup,
+        confirm_popup::{ConfirmPopup, confirm},
+        conflicts_popup::ConflictsPopup,
         help_popup::HelpPopup,
+        jump_to_change_popup::JumpToChangePopup,
         message_popup::MessagePopup,
         panel::DetailsPanel,
         panel::LogPanel,
-        utils::{centered_rect, centered_rect_line_height, tabs_to_spaces},
+        rebase_editor_popup::RebaseEditorPopup,
+        rebase_popup::RebasePopup,
+        remotes_popup::RemotesPopup,
+        split_files_popup::SplitFilesPopup,
+        utils::{
+            centered_rect, centered_rect_line_height, copy_error_popup, copy_to_clipboard,
+            describe_in_editor, draw_prompt, head_id_spans, open_url, spawn_background,
+        },
     },
 };
 
 const NEW_POPUP_ID: u16 = 1;
 const EDIT_POPUP_ID: u16 = 2;
-const ABANDON_POPUP_ID: u16 = 3;
 const SQUASH_POPUP_ID: u16 = 4;
+const PUSH_POPUP_ID: u16 = 5;
+const RESTORE_POPUP_ID: u16 = 6;
+
+/// Delay before computing the diff stat of the selected change, so scrolling through the log
+/// doesn't run `jj diff --stat` for every change passed
+const HEAD_STAT_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// What the details panel shows for the selected change
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum DetailsView {
+    /// Description, metadata and diff, like `jj show`
+    Full,
+    Description,
+    Diff,
+}
+
+impl DetailsView {
+    fn next(self) -> Self {
+        match self {
+            DetailsView::Full => DetailsView::Description,
+            DetailsView::Description => DetailsView::Diff,
+            DetailsView::Diff => DetailsView::Full,
+        }
+    }
+}
+
+/// Edit the diff of a change in jj's diff editor, suspending the UI meanwhile. Then show the
+/// rewritten change, or jj's error, e.g. when the diff editor can't be run
+fn diffedit(head: Head, ignore_immutable: bool) -> ComponentAction {
+    ComponentAction::Suspend(Box::new(move |commander| {
+        if let Err(err) = commander.run_diffedit(&head.commit_id, ignore_immutable) {
+            return Ok(ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                title: "Diff edit".into(),
+                messages: format!("{err:#}").into_text()?,
+                text_align: None,
+            }))));
+        }
+
+        Ok(view_rewritten(commander.get_head_latest(&head)?))
+    }))
+}
+
+/// Select a change rewritten with the UI suspended, in the log and the files tab
+fn view_rewritten(head: Head) -> ComponentAction {
+    ComponentAction::Multiple(vec![
+        ComponentAction::ViewLog(head.clone()),
+        ComponentAction::ChangeHead(head),
+    ])
+}
+
+/// Lines listing the bookmarks a push creates, updates and deletes, colored by kind
+fn push_summary_lines(summary: &PushSummary) -> Vec<Line<'static>> {
+    [
+        ("New", &summary.new, Color::Green),
+        ("Updated", &summary.updated, Color::Yellow),
+        ("Deleted", &summary.deleted, Color::Red),
+    ]
+    .into_iter()
+    .filter(|(_, names, _)| !names.is_empty())
+    .map(|(label, names, color)| {
+        Line::from(format!("{label}: {} ({})", names.len(), names.join(", "))).fg(color)
+    })
+    .collect()
+}
+
+/// Run `jj git push --dry-run` and show what it would change on the remotes, offering to push
+/// for real. jj's error is shown instead if the push would fail
+fn push_dry_run(
+    commander: &mut Commander,
+    head: &Head,
+    all_bookmarks: bool,
+    allow_new: bool,
+) -> Result<ComponentAction> {
+    let summary = match commander.git_push_dry_run(all_bookmarks, allow_new, &head.commit_id) {
+        Ok(summary) => summary,
+        Err(err) => {
+            return Ok(ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                title: "Push dry run error".into(),
+                messages: err.into_text("")?,
+                text_align: None,
+            }))));
+        }
+    };
+    if summary.is_empty() {
+        return Ok(ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+            title: "Push dry run".into(),
+            messages: "Nothing to push".into(),
+            text_align: None,
+        }))));
+    }
+
+    let mut lines = vec![Line::from(if all_bookmarks {
+        "Pushing all bookmarks would change:".to_owned()
+    } else {
+        format!(
+            "Pushing bookmarks of {} would change:",
+            head.change_id.as_str()
+        )
+    })];
+    lines.extend(push_summary_lines(&summary));
+    lines.push(Line::from(""));
+    lines.push(Line::from("Push for real?"));
+
+    let commit_id = head.commit_id.clone();
+    Ok(ComponentAction::SetPopup(Some(Box::new(
+        ConfirmPopup::new("Push dry run", lines, move |_| {
+            Ok(ComponentAction::Push(PushTarget::Change {
+                commit_id,
+                all_bookmarks,
+                allow_new,
+            }))
+        }),
+    ))))
+}
 
 /// Log tab. Shows `jj log` in main panel and shows selected change details of in details panel.
 pub struct LogTab<'a> {
     /// The revset filter to apply to jj log
     log_revset_textarea: Option<TextArea<'a>>,
+    /// The path filter to apply to jj log
+    log_path_textarea: Option<TextArea<'a>>,
+    /// The text to search for in the log
+    log_search_textarea: Option<TextArea<'a>>,
 
     /// The list of changes shown to the left
     log_panel: LogPanel<'a>,
@@ -44,6 +188,14 @@ pub struct LogTab<'a> {
     /// The change content shown to the right
     head_panel: DetailsPanel,
     head_output: Result<String, CommandError>,
+    /// Size of the diff in head_output, shown above it
+    head_stat: Option<DiffStat>,
+    /// Receives head_stat while it is computed in the background, as it is slow for large diffs
+    head_stat_rx: Option<std::sync::mpsc::Receiver<Option<DiffStat>>>,
+    /// Incremented for each head_stat request. Requests replaced while waiting are skipped
+    head_stat_generation: Arc<AtomicUsize>,
+    /// Whether head_output has the description, the diff or both
+    details_view: DetailsView,
 
     /// The currently selected change. Indicates what to render
     /// in head_output. It is a copy of self.log_panel.head,
@@ -51,6 +203,25 @@ pub struct LogTab<'a> {
     /// self.head_output
     head: Head,
 
+    /// Parents of the selected change
+    head_parents: Vec<CommitId>,
+    /// Which parent of a merge to diff against. `None` shows the combined diff
+    diff_parent: Option<usize>,
+    /// Show the diff between the two marked changes instead of the selected change
+    interdiff: bool,
+    /// The marked changes diffed in head_output, when interdiff applies
+    interdiff_range: Option<(Head, Head)>,
+
+    /// Details panel scroll position of the changes scrolled before selecting another one.
+    /// Keyed by commit id, so a change whose content changed, like @, starts at the top again
+    head_scroll: HashMap<CommitId, u16>,
+
+    /// Hide the details panel, giving the log the full width
+    details_folded: bool,
+
+    /// Whether jj's error for an invalid `lazyjj.log-template` was shown in a popup already
+    log_template_error_shown: bool,
+
     // Location of panels on screen. [0] = log, [1] = details
     panel_rect: [Rect; 2],
 
@@ -65,11 +236,20 @@ pub struct LogTab<'a> {
 
     describe_textarea: Option<TextArea<'a>>,
     describe_after_new: bool,
+    /// Changes left to describe in a bulk describe, last one next
+    bulk_describe_queue: Vec<Head>,
+    /// Number of changes in the current bulk describe, 0 if none
+    bulk_describe_total: usize,
 
     squash_ignore_immutable: bool,
+    /// Keep the emptied source change after squashing
+    squash_keep_emptied: bool,
 
     edit_ignore_immutable: bool,
 
+    push_all_bookmarks: bool,
+    push_allow_new: bool,
+
     config: Config,
     keybinds: LogTabKeybinds,
 }
@@ -81,10 +261,6 @@ impl<'a> LogTab<'a> {
 
         let head = commander.get_current_head()?;
 
-        let head_output = commander
-            .get_commit_show(&head.commit_id, &diff_format, true)
-            .map(|text| tabs_to_spaces(&text));
-
         let (popup_tx, popup_rx) = std::sync::mpsc::channel();
         let (bookmark_set_popup_tx, bookmark_set_popup_rx) = std::sync::mpsc::channel();
 
@@ -98,14 +274,34 @@ impl<'a> LogTab<'a> {
             keybinds.extend_from_config(&new_keybinds);
         }
 
-        Ok(Self {
+        let mut log_tab = Self {
             log_revset_textarea: None,
+            log_path_textarea: None,
+            log_search_textarea: None,
 
             log_panel: LogPanel::new(commander)?,
 
             head,
-            head_panel: DetailsPanel::new(),
-            head_output,
+            head_panel: DetailsPanel::new()
+                .with_highlight_color(commander.env.config.highlight_color())
+                .with_tab_width(commander.env.config.tab_width())
+                .with_wrap(commander.env.config.details_wrap())
+                .with_syntax_highlighting(commander.env.config.syntax_highlighting())
+                .with_theme_colors(commander.env.config.theme_colors()),
+            head_output: Ok(String::new()),
+            head_stat: None,
+            head_stat_rx: None,
+            head_stat_generation: Arc::new(AtomicUsize::new(0)),
+            details_view: DetailsView::Full,
+
+            head_parents: Vec::new(),
+            diff_parent: Some(0),
+            head_scroll: HashMap::new(),
+            interdiff: false,
+            interdiff_range: None,
+
+            details_folded: commander.env.config.fold_details(),
+            log_template_error_shown: false,
 
             panel_rect: [Rect::ZERO, Rect::ZERO],
 
@@ -120,14 +316,23 @@ impl<'a> LogTab<'a> {
 
             describe_textarea: None,
             describe_after_new: false,
+            bulk_describe_queue: Vec::new(),
+            bulk_describe_total: 0,
 
             squash_ignore_immutable: false,
+            squash_keep_emptied: false,
 
             edit_ignore_immutable: false,
 
+            push_all_bookmarks: false,
+            push_allow_new: false,
+
             config: commander.env.config.clone(),
             keybinds,
-        })
+        };
+        log_tab.refresh_head_output(commander);
+
+        Ok(log_tab)
     }
 
     /// Update change details panel if the selection has changed
@@ -136,24 +341,194 @@ impl<'a> LogTab<'a> {
             // log panel and head panel agree on head
             return;
         }
+        // Remember how far the previous change was scrolled, to restore it when coming back
+        match self.head_panel.get_scroll() {
+            0 => self.head_scroll.remove(&self.head.commit_id),
+            scroll => self.head_scroll.insert(self.head.commit_id.clone(), scroll),
+        };
         // Update head panel to show new head
         self.head = self.log_panel.head.clone();
+        self.diff_parent = Some(0);
         self.refresh_head_output(commander);
+        if let Some(scroll) = self.head_scroll.remove(&self.head.commit_id) {
+            self.head_panel.set_scroll(scroll);
+        }
+    }
+
+    /// Compute the diff stat in the background, once the selection stayed for
+    /// [`HEAD_STAT_DEBOUNCE`]. The result of a replaced request is dropped with its receiver
+    fn spawn_head_stat(
+        &self,
+        commander: &Commander,
+        commit_id: CommitId,
+        parent_id: Option<CommitId>,
+        path: Option<&str>,
+    ) -> std::sync::mpsc::Receiver<Option<DiffStat>> {
+        let generation = self.head_stat_generation.fetch_add(1, Ordering::Relaxed) + 1;
+        let latest_generation = self.head_stat_generation.clone();
+        let commander = commander.clone();
+        let path = path.map(str::to_owned);
+        spawn_background(move || {
+            thread::sleep(HEAD_STAT_DEBOUNCE);
+            if latest_generation.load(Ordering::Relaxed) != generation {
+                return None;
+            }
+            commander
+                .get_commit_diff_stat(&commit_id, parent_id.as_ref(), path.as_deref(), true)
+                .ok()
+        })
     }
 
     fn refresh_head_output(&mut self, commander: &mut Commander) {
-        self.head_output = commander
-            .get_commit_show(&self.head.commit_id, &self.diff_format, true)
-            .map(|text| tabs_to_spaces(&text));
+        self.head_parents = commander
+            .get_commit_parents(&self.head.commit_id)
+            .unwrap_or_default();
+        let diff_parent = self
+            .diff_parent
+            .and_then(|index| self.head_parents.get(index))
+            .filter(|_| self.is_merge());
+        // When the log is filtered by path, only show the diff of that path
+        let path = self.log_panel.log_path.as_deref();
+        self.head_stat = None;
+        // With a single marked change, fall back to the diff of the selected change
+        self.interdiff_range = match self.log_panel.marked.as_slice() {
+            [from, to] if self.interdiff => commander
+                .get_head_latest(from)
+                .ok()
+                .zip(commander.get_head_latest(to).ok()),
+            _ => None,
+        };
+        if let Some((from, to)) = self.interdiff_range.as_ref() {
+            self.head_stat_rx = Some(self.spawn_head_stat(
+                commander,
+                to.commit_id.clone(),
+                Some(from.commit_id.clone()),
+                path,
+            ));
+            self.head_output = commander.get_diff_range(
+                &from.commit_id,
+                &to.commit_id,
+                path,
+                &self.diff_format,
+                true,
+            );
+            self.head_panel.scroll_to(0);
+            self.head_panel.clear_cursor();
+            return;
+        }
+        self.head_stat_rx = match self.details_view {
+            DetailsView::Description => None,
+            DetailsView::Full | DetailsView::Diff => Some(self.spawn_head_stat(
+                commander,
+                self.head.commit_id.clone(),
+                diff_parent.cloned(),
+                path,
+            )),
+        };
+        self.head_output = match (self.details_view, diff_parent, path) {
+            (DetailsView::Description, ..) => {
+                commander.get_commit_metadata(&self.head.commit_id, true)
+            }
+            (DetailsView::Diff, ..) => commander.get_commit_diff(
+                &self.head.commit_id,
+                diff_parent,
+                path,
+                &self.diff_format,
+                true,
+            ),
+            (DetailsView::Full, None, None) => {
+                commander.get_commit_show(&self.head.commit_id, &self.diff_format, true)
+            }
+            (DetailsView::Full, ..) => commander.get_commit_show_with_diff(
+                &self.head.commit_id,
+                diff_parent,
+                path,
+                &self.diff_format,
+                true,
+            ),
+        };
         self.head_panel.scroll_to(0);
+        self.head_panel.clear_cursor();
+    }
+
+    /// Whether the selected change has more than one parent
+    fn is_merge(&self) -> bool {
+        self.head_parents.len() > 1
+    }
+
+    /// Describe what the details diff is taken against, for merge commits
+    fn diff_parent_label(&self) -> Option<String> {
+        if !self.is_merge() || self.details_view == DetailsView::Description {
+            return None;
+        }
+        Some(match self.diff_parent {
+            Some(index) => format!(
+                "diff from parent {} of {}",
+                index + 1,
+                self.head_parents.len()
+            ),
+            None => "combined diff".to_owned(),
+        })
+    }
+
+    /// Select the next change of a bulk describe and open the describe popup for it
+    fn describe_next_in_bulk(&mut self, commander: &mut Commander) -> Result<()> {
+        match self.bulk_describe_queue.pop() {
+            Some(head) => {
+                // Describing earlier changes rewrites their descendants
+                let head = commander.get_head_latest(&head)?;
+                self.set_head(commander, head);
+                self.describe_textarea = Some(TextArea::default());
+            }
+            None => {
+                self.bulk_describe_total = 0;
+                self.describe_textarea = None;
+            }
+        }
+        Ok(())
     }
 
     pub fn set_head(&mut self, commander: &mut Commander, head: Head) {
+        self.select_head(commander, head, false);
+    }
+
+    pub fn clear_marks(&mut self) {
+        self.log_panel.marked.clear();
+    }
+
+    /// Select a change jumped to by id, widening the log until the next refresh when it
+    /// doesn't show the change
+    pub fn jump_to_head(&mut self, commander: &mut Commander, head: Head) {
+        self.select_head(commander, head, true);
+    }
+
+    fn select_head(&mut self, commander: &mut Commander, head: Head, reveal: bool) {
         self.log_panel.set_head(head);
         self.log_panel.refresh_log_output(commander);
+        if reveal {
+            self.log_panel.reveal_head(commander);
+        }
+        self.forget_hidden_scroll();
         self.sync_head_output(commander);
     }
 
+    /// Run jj log again, see [`Self::forget_hidden_scroll`]
+    fn refresh_log(&mut self, commander: &mut Commander) {
+        self.log_panel.refresh_log_output(commander);
+        self.forget_hidden_scroll();
+    }
+
+    /// Forget the scroll position of changes which were rewritten or are no longer shown.
+    /// Kept while the log fails, as it shows no changes then
+    fn forget_hidden_scroll(&mut self) {
+        if self.log_panel.log_error().is_some() {
+            return;
+        }
+        let heads = self.log_panel.heads();
+        self.head_scroll
+            .retain(|commit_id, _| heads.iter().any(|head| &head.commit_id == commit_id));
+    }
+
     fn handle_event(
         &mut self,
         commander: &mut Commander,
@@ -163,19 +538,62 @@ impl<'a> LogTab<'a> {
             LogTabEvent::ScrollDown
             | LogTabEvent::ScrollUp
             | LogTabEvent::ScrollDownHalf
-            | LogTabEvent::ScrollUpHalf => {
+            | LogTabEvent::ScrollUpHalf
+            | LogTabEvent::ToggleCompact
+            | LogTabEvent::ToggleGraph => {
                 self.log_panel.handle_event(commander, log_tab_event)?;
                 self.sync_head_output(commander);
             }
             LogTabEvent::FocusCurrent => {
-                self.set_head(commander, commander.get_current_head()?);
+                let head = commander.get_current_head()?;
+                // Tell where it is, as selecting it would leave nothing selected in the log
+                if let Some(revset) = self.log_panel.filtered_revset()
+                    && !commander.is_in_revset(&head.commit_id, &revset)?
+                {
+                    return Ok(ComponentInputResult::HandledAction(
+                        ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                            title: "Working copy".into(),
+                            messages: vec![
+                                Line::from(format!(
+                                    "The working copy {} is not in the log for:",
+                                    head.change_id.as_str()
+                                )),
+                                Line::from(revset),
+                                Line::from(""),
+                                Line::from("Clear the revset or path filter to select it."),
+                            ]
+                            .into(),
+                            text_align: None,
+                        }))),
+                    ));
+                }
+                self.set_head(commander, head);
             }
             LogTabEvent::ToggleDiffFormat => {
                 self.diff_format = self.diff_format.get_next(self.config.diff_tool());
                 self.refresh_head_output(commander);
             }
+            LogTabEvent::CycleDetailsView => {
+                self.details_view = self.details_view.next();
+                self.refresh_head_output(commander);
+            }
+            LogTabEvent::ToggleInterdiff => {
+                self.interdiff = !self.interdiff;
+                self.refresh_head_output(commander);
+            }
+            LogTabEvent::CycleDiffParent => {
+                if self.is_merge() {
+                    self.diff_parent = match self.diff_parent {
+                        Some(index) if index + 1 < self.head_parents.len() => Some(index + 1),
+                        Some(_) => None,
+                        None => Some(0),
+                    };
+                    self.refresh_head_output(commander);
+                }
+            }
             LogTabEvent::Refresh => {
-                self.log_panel.refresh_log_output(commander);
+                self.log_panel.clear_reveal();
+                self.refresh_log(commander);
                 self.refresh_head_output(commander);
             }
             LogTabEvent::CreateNew { describe } => {
@@ -195,6 +613,19 @@ impl<'a> LogTab<'a> {
                     .open();
                 self.describe_after_new = describe;
             }
+            LogTabEvent::CreateNewNoEdit => {
+                if let Err(err) = commander.run_new_no_edit(self.head.commit_id.as_str()) {
+                    return Ok(ComponentInputResult::HandledAction(
+                        ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                            title: "New".into(),
+                            messages: format!("{err:#}").into_text()?,
+                            text_align: None,
+                        }))),
+                    ));
+                }
+                // Stay on the parent, to add more changes to it
+                self.set_head(commander, commander.get_head_latest(&self.head)?);
+            }
             LogTabEvent::Squash { ignore_immutable } => {
                 if self.head.change_id == commander.get_current_head()?.change_id {
                     return Ok(ComponentInputResult::HandledAction(
@@ -222,6 +653,11 @@ impl<'a> LogTab<'a> {
                 if ignore_immutable {
                     lines.push(Line::from("This change is immutable."));
                 }
+                lines.push(Line::from(if self.squash_keep_emptied {
+                    "@ will be kept as an empty change (--keep-emptied)"
+                } else {
+                    "@ will be abandoned if it becomes empty"
+                }));
                 self.popup = ConfirmDialogState::new(
                     SQUASH_POPUP_ID,
                     Span::styled(" Squash ", Style::new().bold().cyan()),
@@ -234,6 +670,38 @@ impl<'a> LogTab<'a> {
                     .open();
                 self.squash_ignore_immutable = ignore_immutable;
             }
+            LogTabEvent::ToggleSquashKeepEmptied => {
+                self.squash_keep_emptied = !self.squash_keep_emptied;
+                return Ok(ComponentInputResult::HandledAction(
+                    ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                        title: "Squash".into(),
+                        messages: if self.squash_keep_emptied {
+                            "Squash will keep the emptied source change"
+                        } else {
+                            "Squash will abandon the emptied source change"
+                        }
+                        .into_text()?,
+                        text_align: None,
+                    }))),
+                ));
+            }
+            LogTabEvent::DiffEdit => {
+                let head = self.head.clone();
+                if head.immutable {
+                    return Ok(ComponentInputResult::HandledAction(
+                        ComponentAction::SetPopup(Some(Box::new(ConfirmPopup::new(
+                            "Diff edit",
+                            vec![
+                                Line::from("The change is immutable."),
+                                Line::from("Edit its diff anyway? (--ignore-immutable)"),
+                                Line::from(format!("Change: {}", head.change_id.as_str())),
+                            ],
+                            move |_| Ok(diffedit(head, true)),
+                        )))),
+                    ));
+                }
+                return Ok(ComponentInputResult::HandledAction(diffedit(head, false)));
+            }
             LogTabEvent::EditChange { ignore_immutable } => {
                 if self.head.immutable && !ignore_immutable {
                     return Ok(ComponentInputResult::HandledAction(
@@ -267,6 +735,46 @@ impl<'a> LogTab<'a> {
                     .open();
                 self.edit_ignore_immutable = ignore_immutable;
             }
+            LogTabEvent::Abandon if !self.log_panel.marked.is_empty() => {
+                let changes = self
+                    .log_panel
+                    .marked
+                    .iter()
+                    .map(|marked| {
+                        let head = commander.get_head_latest(marked)?;
+                        let description = commander.get_commit_description(&head.commit_id)?;
+                        Ok((head, description))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let immutable: Vec<&str> = changes
+                    .iter()
+                    .filter(|(head, _)| head.immutable)
+                    .map(|(head, _)| head.change_id.as_str())
+                    .collect();
+                if !immutable.is_empty() {
+                    return Ok(ComponentInputResult::HandledAction(
+                        ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                            title: "Abandon".into(),
+                            messages: format!(
+                                "Nothing was abandoned, because these marked changes are immutable: {}",
+                                immutable.join(", ")
+                            )
+                            .into(),
+                            text_align: None,
+                        }))),
+                    ));
+                }
+
+                let lines = abandon_many_lines(&changes);
+                let heads: Vec<Head> = changes.into_iter().map(|(head, _)| head).collect();
+                return Ok(ComponentInputResult::HandledAction(confirm(
+                    &self.config,
+                    commander,
+                    "Abandon",
+                    lines,
+                    move |commander| abandon_many(commander, &heads),
+                )?));
+            }
             LogTabEvent::Abandon => {
                 if self.head.immutable {
                     return Ok(ComponentInputResult::HandledAction(
@@ -279,22 +787,58 @@ impl<'a> LogTab<'a> {
                             text_align: None,
                         }))),
                     ));
-                } else {
-                    self.popup = ConfirmDialogState::new(
-                        ABANDON_POPUP_ID,
-                        Span::styled(" Abandon ", Style::new().bold().cyan()),
-                        Text::from(vec![
-                            Line::from("Are you sure you want to abandon this change?"),
-                            Line::from(format!("Change: {}", self.head.change_id.as_str())),
-                        ])
-                        .fg(Color::default()),
-                    );
-                    self.popup
-                        .with_yes_button(ButtonLabel::YES.clone())
-                        .with_no_button(ButtonLabel::NO.clone())
-                        .with_listener(Some(self.popup_tx.clone()))
-                        .open();
                 }
+
+                let description = commander.get_commit_description(&self.head.commit_id)?;
+                let bookmarks = commander.get_commit_bookmarks(&self.head.commit_id)?;
+                if !bookmarks.is_empty() {
+                    return Ok(ComponentInputResult::HandledAction(
+                        ComponentAction::SetPopup(Some(Box::new(AbandonPopup::new(
+                            self.head.clone(),
+                            description,
+                            bookmarks,
+                        )))),
+                    ));
+                }
+
+                let head = self.head.clone();
+                return Ok(ComponentInputResult::HandledAction(confirm(
+                    &self.config,
+                    commander,
+                    "Abandon",
+                    abandon_lines(&head, &description),
+                    move |commander| abandon(commander, &head, false),
+                )?));
+            }
+            LogTabEvent::RestoreWorkingCopy => {
+                if self.head.change_id == commander.get_current_head()?.change_id {
+                    return Ok(ComponentInputResult::HandledAction(
+                        ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                            title: "Restore".into(),
+                            messages: "Cannot restore the working copy from itself".into_text()?,
+                            text_align: None,
+                        }))),
+                    ));
+                }
+
+                self.popup = ConfirmDialogState::new(
+                    RESTORE_POPUP_ID,
+                    Span::styled(" Restore ", Style::new().bold().cyan()),
+                    Text::from(vec![
+                        Line::from("Are you sure you want to restore the working copy?"),
+                        Line::from(format!("Restore all files from {}", self.head.change_id)),
+                        Line::from(""),
+                        Line::from("All changes in @ will be discarded!")
+                            .bold()
+                            .fg(Color::Red),
+                    ])
+                    .fg(Color::default()),
+                );
+                self.popup
+                    .with_yes_button(ButtonLabel::YES.clone())
+                    .with_no_button(ButtonLabel::NO.clone())
+                    .with_listener(Some(self.popup_tx.clone()))
+                    .open();
             }
             LogTabEvent::Describe => {
                 if self.head.immutable {
@@ -321,6 +865,29 @@ impl<'a> LogTab<'a> {
                     return Ok(ComponentInputResult::Handled);
                 }
             }
+            LogTabEvent::DescribeInEditor => {
+                return Ok(ComponentInputResult::HandledAction(describe_in_editor(
+                    commander,
+                    &self.head,
+                    view_rewritten,
+                )?));
+            }
+            LogTabEvent::BulkDescribe => {
+                let mut heads = commander.get_undescribed_heads(&self.log_panel.log_revset)?;
+                if heads.is_empty() {
+                    return Ok(ComponentInputResult::HandledAction(
+                        ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                            title: "Describe".into(),
+                            messages: "No mutable changes without a description".into_text()?,
+                            text_align: None,
+                        }))),
+                    ));
+                }
+                heads.reverse();
+                self.bulk_describe_total = heads.len();
+                self.bulk_describe_queue = heads;
+                self.describe_next_in_bulk(commander)?;
+            }
             LogTabEvent::EditRevset => {
                 let mut textarea = TextArea::new(
                     self.log_panel
@@ -335,6 +902,35 @@ impl<'a> LogTab<'a> {
                 self.log_revset_textarea = Some(textarea);
                 return Ok(ComponentInputResult::Handled);
             }
+            LogTabEvent::EditPathFilter => {
+                let mut textarea =
+                    TextArea::new(vec![self.log_panel.log_path.clone().unwrap_or_default()]);
+                textarea.move_cursor(CursorMove::End);
+                self.log_path_textarea = Some(textarea);
+                return Ok(ComponentInputResult::Handled);
+            }
+            LogTabEvent::Search => {
+                let mut textarea =
+                    TextArea::new(vec![self.log_panel.search.clone().unwrap_or_default()]);
+                textarea.move_cursor(CursorMove::End);
+                self.log_search_textarea = Some(textarea);
+                return Ok(ComponentInputResult::Handled);
+            }
+            LogTabEvent::SearchNext | LogTabEvent::SearchPrevious => {
+                if self.log_panel.search.is_none() {
+                    return Ok(ComponentInputResult::NotHandled);
+                }
+                self.log_panel
+                    .select_search_match(log_tab_event == LogTabEvent::SearchPrevious, false);
+                self.sync_head_output(commander);
+            }
+            LogTabEvent::JumpToChange => {
+                return Ok(ComponentInputResult::HandledAction(
+                    ComponentAction::SetPopup(Some(Box::new(JumpToChangePopup::new(
+                        self.config.clone(),
+                    )))),
+                ));
+            }
             LogTabEvent::SetBookmark => {
                 return Ok(ComponentInputResult::HandledAction(
                     ComponentAction::SetPopup(Some(Box::new(BookmarkSetPopup::new(
@@ -346,65 +942,424 @@ impl<'a> LogTab<'a> {
                     )))),
                 ));
             }
-            LogTabEvent::OpenFiles => {
+            LogTabEvent::SetBookmarkAtCurrent => {
+                let current_head = commander.get_current_head()?;
                 return Ok(ComponentInputResult::HandledAction(
-                    ComponentAction::ViewFiles(self.head.clone()),
+                    ComponentAction::SetPopup(Some(Box::new(BookmarkSetPopup::new_name_prompt(
+                        self.config.clone(),
+                        commander,
+                        Some(current_head.change_id),
+                        current_head.commit_id,
+                        self.bookmark_set_popup_tx.clone(),
+                    )))),
                 ));
             }
-            LogTabEvent::Push {
-                all_bookmarks,
-                allow_new,
-            } => {
-                match commander.git_push(all_bookmarks, allow_new, &self.head.commit_id) {
-                    Ok(result) if !result.is_empty() => {
+            LogTabEvent::CreateBookmark => {
+                return Ok(ComponentInputResult::HandledAction(
+                    ComponentAction::SetPopup(Some(Box::new(BookmarkSetPopup::new_create_prompt(
+                        self.config.clone(),
+                        commander,
+                        self.head.commit_id.clone(),
+                        self.bookmark_set_popup_tx.clone(),
+                    )))),
+                ));
+            }
+            LogTabEvent::MoveBookmark => {
+                return Ok(ComponentInputResult::HandledAction(
+                    ComponentAction::SetPopup(Some(Box::new(BookmarkMovePopup::new(
+                        self.config.clone(),
+                        commander,
+                        self.head.commit_id.clone(),
+                        self.bookmark_set_popup_tx.clone(),
+                    )))),
+                ));
+            }
+            LogTabEvent::OpenInBrowser => {
+                let remotes = commander.get_git_remotes()?;
+                let remote = remotes
+                    .iter()
+                    .find(|remote| remote.name == "origin")
+                    .or(remotes.first());
+                let url = remote.and_then(|remote| {
+                    remote.commit_web_url(self.config.remote_url_template(), &self.head.commit_id)
+                });
+                let message = match (remote, url) {
+                    (None, _) => "The repository has no git remotes".to_owned(),
+                    (Some(remote), None) => {
+                        format!("Could not make a web URL from remote {}", remote.url)
+                    }
+                    (Some(_), Some(url)) => match open_url(&url) {
+                        Ok(()) => return Ok(ComponentInputResult::Handled),
+                        Err(err) => format!("{err:#}"),
+                    },
+                };
+                return Ok(ComponentInputResult::HandledAction(
+                    ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                        title: "Open in browser".into(),
+                        messages: message.into_text()?,
+                        text_align: None,
+                    }))),
+                ));
+            }
+            LogTabEvent::ToggleDetails => {
+                self.details_folded = !self.details_folded;
+            }
+            LogTabEvent::OpenConflicts => {
+                return Ok(ComponentInputResult::HandledAction(
+                    ComponentAction::SetPopup(Some(Box::new(ConflictsPopup::new(
+                        self.config.clone(),
+                        commander,
+                    )))),
+                ));
+            }
+            LogTabEvent::RebaseRevset => {
+                return Ok(ComponentInputResult::HandledAction(
+                    ComponentAction::SetPopup(Some(Box::new(RebasePopup::new(
+                        self.config.clone(),
+                        self.head.clone(),
+                    )))),
+                ));
+            }
+            LogTabEvent::InteractiveRebase => {
+                let popup: Box<dyn Component> =
+                    match RebaseEditorPopup::new(commander, self.config.clone(), &self.head) {
+                        Ok(popup) => Box::new(popup),
+                        Err(err) => Box::new(MessagePopup {
+                            title: "Interactive rebase".into(),
+                            messages: format!("{err:#}").into_text()?,
+                            text_align: None,
+                        }),
+                    };
+                return Ok(ComponentInputResult::HandledAction(
+                    ComponentAction::SetPopup(Some(popup)),
+                ));
+            }
+            LogTabEvent::Split => {
+                let files = commander.get_files(&self.head)?;
+                if files.is_empty() {
+                    return Ok(ComponentInputResult::HandledAction(
+                        ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                            title: "Split".into(),
+                            messages: vec!["The change has no changed files to split.".into()]
+                                .into(),
+                            text_align: None,
+                        }))),
+                    ));
+                }
+                return Ok(ComponentInputResult::HandledAction(
+                    ComponentAction::SetPopup(Some(Box::new(SplitFilesPopup::new(
+                        self.head.clone(),
+                        files,
+                        self.config.clone(),
+                    )))),
+                ));
+            }
+            LogTabEvent::ToggleMark => {
+                let marked = &mut self.log_panel.marked;
+                let count = marked.len();
+                marked.retain(|marked| marked.change_id != self.head.change_id);
+                if marked.len() == count {
+                    marked.push(self.head.clone());
+                }
+                if self.interdiff {
+                    self.refresh_head_output(commander);
+                }
+            }
+            LogTabEvent::Duplicate => {
+                let destinations = self
+                    .log_panel
+                    .marked
+                    .iter()
+                    .filter(|marked| marked.change_id != self.head.change_id)
+                    .map(|marked| Ok(commander.get_head_latest(marked)?.commit_id))
+                    .collect::<Result<Vec<_>>>()?;
+                let duplicate = match commander.run_duplicate(&self.head.commit_id, &destinations) {
+                    Ok(duplicate) => duplicate,
+                    Err(err) => {
                         return Ok(ComponentInputResult::HandledAction(
                             ComponentAction::SetPopup(Some(Box::new(MessagePopup {
-                                title: "Push message".into(),
-                                messages: result.into_text()?,
+                                title: "Duplicate".into(),
+                                messages: format!("{err:#}").into_text()?,
                                 text_align: None,
                             }))),
                         ));
                     }
+                };
+                // Keep the marks on failure, to retry with the same destinations
+                self.log_panel.marked.clear();
+                self.set_head(commander, duplicate);
+            }
+            LogTabEvent::Backout => {
+                let backout = match commander.run_backout(&self.head.commit_id, "@-") {
+                    Ok(backout) => backout,
                     Err(err) => {
                         return Ok(ComponentInputResult::HandledAction(
                             ComponentAction::SetPopup(Some(Box::new(MessagePopup {
-                                title: "Push error".into(),
-                                messages: err.into_text("")?,
+                                title: "Backout".into(),
+                                messages: format!("{err:#}").into_text()?,
                                 text_align: None,
                             }))),
                         ));
                     }
-                    _ => (),
+                };
+                let conflicts = commander.get_conflicts(&backout.commit_id)?;
+                self.set_head(commander, backout);
+                if !conflicts.is_empty() {
+                    return Ok(ComponentInputResult::HandledAction(
+                        ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                            title: "Backout".into(),
+                            messages: vec![
+                                Line::from(format!(
+                                    "The backout has conflicts in {} file(s).",
+                                    conflicts.len()
+                                ))
+                                .fg(Color::Red),
+                                Line::from("Resolve them before building on it."),
+                            ]
+                            .into(),
+                            text_align: None,
+                        }))),
+                    ));
                 }
-
-                self.log_panel.refresh_log_output(commander);
-                self.refresh_head_output(commander);
             }
-            LogTabEvent::Fetch { all_remotes } => {
-                match commander.git_fetch(all_remotes) {
-                    Ok(result) if !result.is_empty() => {
+            LogTabEvent::Sign | LogTabEvent::Unsign => {
+                let (title, result) = if log_tab_event == LogTabEvent::Sign {
+                    ("Sign", commander.run_sign(&self.head.commit_id))
+                } else {
+                    ("Unsign", commander.run_unsign(&self.head.commit_id))
+                };
+                if let Err(err) = result {
+                    return Ok(ComponentInputResult::HandledAction(
+                        ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                            title: title.into(),
+                            messages: format!("{err:#}").into_text()?,
+                            text_align: None,
+                        }))),
+                    ));
+                }
+                self.set_head(commander, commander.get_head_latest(&self.head)?);
+            }
+            LogTabEvent::Parallelize => {
+                let marked = self
+                    .log_panel
+                    .marked
+                    .iter()
+                    .map(|marked| Ok(commander.get_head_latest(marked)?.commit_id))
+                    .collect::<Result<Vec<_>>>()?;
+                // A single marked change parallelizes the range between it and the selected one
+                let revset = match marked.as_slice() {
+                    [] => None,
+                    [marked] => Some(format!(
+                        "connected({} | {})",
+                        marked.as_str(),
+                        self.head.commit_id.as_str()
+                    )),
+                    marked => Some(
+                        marked
+                            .iter()
+                            .map(CommitId::as_str)
+                            .collect::<Vec<_>>()
+                            .join(" | "),
+                    ),
+                };
+                let message = match revset {
+                    None => Some(Text::from(
+                        "Mark the changes to parallelize with t, or mark one end of the range and select the other.",
+                    )),
+                    Some(revset) => {
+                        let revisions: Vec<CommitId> = commander
+                            .get_revset_commits(&revset)?
+                            .into_iter()
+                            .map(|(head, _)| head.commit_id)
+                            .collect();
+                        let connected = commander
+                            .get_revset_commits(&format!("connected({revset})"))?
+                            .len();
+                        if connected > revisions.len() {
+                            Some(Text::from(format!(
+                                "The marked changes don't form a contiguous chain: {} change(s) between them are not marked.",
+                                connected - revisions.len()
+                            )))
+                        } else if let Err(err) = commander.run_parallelize(&revisions) {
+                            Some(format!("{err:#}").into_text()?)
+                        } else {
+                            None
+                        }
+                    }
+                };
+                if let Some(message) = message {
+                    return Ok(ComponentInputResult::HandledAction(
+                        ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                            title: "Parallelize".into(),
+                            messages: message,
+                            text_align: None,
+                        }))),
+                    ));
+                }
+                self.log_panel.marked.clear();
+                self.set_head(commander, commander.get_head_latest(&self.head)?);
+                return Ok(ComponentInputResult::HandledAction(
+                    ComponentAction::ChangeHead(self.head.clone()),
+                ));
+            }
+            LogTabEvent::CreateMerge => {
+                let parents = self
+                    .log_panel
+                    .marked
+                    .iter()
+                    .map(|marked| Ok(commander.get_head_latest(marked)?.commit_id))
+                    .collect::<Result<Vec<_>>>()?;
+                if let Err(err) = commander.run_new_merge(&parents) {
+                    return Ok(ComponentInputResult::HandledAction(
+                        ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                            title: "New merge".into(),
+                            messages: format!("{err:#}").into_text()?,
+                            text_align: None,
+                        }))),
+                    ));
+                }
+                self.log_panel.marked.clear();
+                self.set_head(commander, commander.get_current_head()?);
+                return Ok(ComponentInputResult::HandledAction(
+                    ComponentAction::ChangeHead(self.head.clone()),
+                ));
+            }
+            LogTabEvent::Absorb => {
+                let summary = match commander.run_absorb(None, None) {
+                    Ok(summary) => summary,
+                    Err(err) => {
                         return Ok(ComponentInputResult::HandledAction(
                             ComponentAction::SetPopup(Some(Box::new(MessagePopup {
-                                title: "Fetch message".into(),
-                                messages: result.into_text()?,
+                                title: "Absorb".into(),
+                                messages: format!("{err:#}").into_text()?,
                                 text_align: None,
                             }))),
                         ));
                     }
+                };
+                self.set_head(commander, commander.get_current_head()?);
+                return Ok(ComponentInputResult::HandledAction(
+                    ComponentAction::Multiple(vec![
+                        ComponentAction::ChangeHead(self.head.clone()),
+                        ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                            title: "Absorb".into(),
+                            messages: summary.into_text()?,
+                            text_align: None,
+                        }))),
+                    ]),
+                ));
+            }
+            LogTabEvent::Fix => {
+                let summary = match commander.run_fix(Some(self.head.commit_id.as_str())) {
+                    Ok(summary) => summary,
                     Err(err) => {
                         return Ok(ComponentInputResult::HandledAction(
                             ComponentAction::SetPopup(Some(Box::new(MessagePopup {
-                                title: "Fetch error".into(),
-                                messages: err.into_text("")?,
+                                title: "Fix".into(),
+                                messages: format!("{err:#}").into_text()?,
                                 text_align: None,
                             }))),
                         ));
                     }
-                    _ => (),
+                };
+                self.set_head(commander, commander.get_head_latest(&self.head)?);
+                return Ok(ComponentInputResult::HandledAction(
+                    ComponentAction::Multiple(vec![
+                        ComponentAction::ChangeHead(self.head.clone()),
+                        ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                            title: "Fix".into(),
+                            messages: summary.into_text()?,
+                            text_align: None,
+                        }))),
+                    ]),
+                ));
+            }
+            LogTabEvent::CopyChangeId | LogTabEvent::CopyCommitId => {
+                let (name, id) = if log_tab_event == LogTabEvent::CopyChangeId {
+                    ("change", self.head.change_id.as_str())
+                } else {
+                    ("commit", self.head.commit_id.as_str())
+                };
+                let action = match copy_to_clipboard(id) {
+                    Ok(()) => ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                        title: "Copied".into(),
+                        messages: format!("Copied {name} id {id}").into(),
+                        text_align: None,
+                    }))),
+                    Err(err) => copy_error_popup(err)?,
+                };
+                return Ok(ComponentInputResult::HandledAction(action));
+            }
+            LogTabEvent::OpenFiles => {
+                return Ok(ComponentInputResult::HandledAction(
+                    ComponentAction::ViewFiles(self.head.clone()),
+                ));
+            }
+            LogTabEvent::Push {
+                all_bookmarks,
+                allow_new,
+            } => {
+                if self.config.push_dry_run() {
+                    return Ok(ComponentInputResult::HandledAction(push_dry_run(
+                        commander,
+                        &self.head,
+                        all_bookmarks,
+                        allow_new,
+                    )?));
                 }
 
-                self.log_panel.refresh_log_output(commander);
-                self.refresh_head_output(commander);
+                let mut lines = vec![Line::from(if all_bookmarks {
+                    "Are you sure you want to push all bookmarks?".to_owned()
+                } else {
+                    format!(
+                        "Are you sure you want to push bookmarks of {}?",
+                        self.head.change_id.as_str()
+                    )
+                })];
+                match commander.get_push_summary(all_bookmarks, &self.head.commit_id) {
+                    Ok(summary) if summary.is_empty() => {
+                        lines.push(Line::from("Nothing to push according to tracked remotes"));
+                    }
+                    Ok(summary) => {
+                        lines.extend(push_summary_lines(&summary));
+                        if !summary.new.is_empty() && !allow_new {
+                            lines.push(Line::from("New bookmarks are skipped without --allow-new"));
+                        }
+                    }
+                    Err(_) => lines.push(Line::from("Could not compute what would be pushed")),
+                }
+
+                self.popup = ConfirmDialogState::new(
+                    PUSH_POPUP_ID,
+                    Span::styled(" Push ", Style::new().bold().cyan()),
+                    Text::from(lines).fg(Color::default()),
+                );
+                self.popup
+                    .with_yes_button(ButtonLabel::YES.clone())
+                    .with_no_button(ButtonLabel::NO.clone())
+                    .with_listener(Some(self.popup_tx.clone()))
+                    .open();
+                self.push_all_bookmarks = all_bookmarks;
+                self.push_allow_new = allow_new;
+            }
+            LogTabEvent::Fetch { all_remotes } => {
+                // Ask which remote to fetch from instead of assuming the default one
+                if !all_remotes && commander.get_git_remotes()?.len() > 1 {
+                    return Ok(ComponentInputResult::HandledAction(
+                        ComponentAction::SetPopup(Some(Box::new(RemotesPopup::new_fetch_picker(
+                            self.config.clone(),
+                            commander,
+                        )))),
+                    ));
+                }
+
+                return Ok(ComponentInputResult::HandledAction(ComponentAction::Fetch(
+                    if all_remotes {
+                        FetchTarget::AllRemotes
+                    } else {
+                        FetchTarget::Default
+                    },
+                )));
             }
             LogTabEvent::OpenHelp => {
                 return Ok(ComponentInputResult::HandledAction(
@@ -422,6 +1377,16 @@ impl<'a> LogTab<'a> {
                             ),
                             ("w".to_owned(), "toggle diff format".to_owned()),
                             ("W".to_owned(), "toggle wrapping".to_owned()),
+                            (
+                                "</>".to_owned(),
+                                "scroll long lines left/right when not wrapping".to_owned(),
+                            ),
+                            (
+                                "#".to_owned(),
+                                "search details, Enter/Up: next/previous match".to_owned(),
+                            ),
+                            ("V".to_owned(), "toggle side by side git diff".to_owned()),
+                            ("Ctrl+w".to_owned(), "toggle description reflow".to_owned()),
                         ],
                     )))),
                 ));
@@ -438,11 +1403,37 @@ impl<'a> LogTab<'a> {
 impl Component for LogTab<'_> {
     fn focus(&mut self, commander: &mut Commander) -> Result<()> {
         let latest_head = commander.get_head_latest(&self.head)?;
-        self.log_panel.set_head(latest_head);
+        self.set_head(commander, latest_head);
         Ok(())
     }
 
     fn update(&mut self, commander: &mut Commander) -> Result<Option<ComponentAction>> {
+        if let Some(action) = self.head_panel.take_copy_action()? {
+            return Ok(Some(action));
+        }
+
+        if let Some(head_stat_rx) = self.head_stat_rx.as_ref()
+            && let Ok(head_stat) = head_stat_rx.try_recv()
+        {
+            self.head_stat = head_stat;
+            self.head_stat_rx = None;
+        }
+
+        // Surface jj's error for a malformed log template once, the log panel keeps showing it
+        if !self.log_template_error_shown
+            && self.config.log_template() != DEFAULT_LOG_TEMPLATE
+            && let Some(err) = self.log_panel.log_error()
+        {
+            self.log_template_error_shown = true;
+            return Ok(Some(ComponentAction::SetPopup(Some(Box::new(
+                MessagePopup {
+                    title: "Invalid log template".into(),
+                    messages: err.into_text("")?,
+                    text_align: None,
+                },
+            )))));
+        }
+
         // Check for popup action
         if let Ok(res) = self.popup_rx.try_recv()
             && res.1.unwrap_or(false)
@@ -460,33 +1451,45 @@ impl Component for LogTab<'_> {
                 }
                 EDIT_POPUP_ID => {
                     commander.run_edit(self.head.commit_id.as_str(), self.edit_ignore_immutable)?;
-                    self.log_panel.refresh_log_output(commander);
+                    self.refresh_log(commander);
                     self.refresh_head_output(commander);
                     return Ok(Some(ComponentAction::ChangeHead(self.head.clone())));
                 }
-                ABANDON_POPUP_ID => {
-                    if self.head == commander.get_current_head()? {
-                        commander.run_abandon(&self.head.commit_id)?;
-                        self.set_head(commander, commander.get_current_head()?);
-                        return Ok(Some(ComponentAction::ChangeHead(self.head.clone())));
-                    } else {
-                        let head_parent = commander.get_commit_parent(&self.head.commit_id)?;
-                        commander.run_abandon(&self.head.commit_id)?;
-                        self.set_head(commander, head_parent);
-                    }
-                }
                 SQUASH_POPUP_ID => {
-                    commander
-                        .run_squash(self.head.commit_id.as_str(), self.squash_ignore_immutable)?;
+                    commander.run_squash(
+                        self.head.commit_id.as_str(),
+                        self.squash_ignore_immutable,
+                        self.squash_keep_emptied,
+                    )?;
+                    self.set_head(commander, commander.get_current_head()?);
+                    return Ok(Some(ComponentAction::ChangeHead(self.head.clone())));
+                }
+                RESTORE_POPUP_ID => {
+                    if let Err(err) = commander.run_restore(&self.head.commit_id, &[]) {
+                        return Ok(Some(ComponentAction::SetPopup(Some(Box::new(
+                            MessagePopup {
+                                title: "Restore".into(),
+                                messages: format!("{err:#}").into_text()?,
+                                text_align: None,
+                            },
+                        )))));
+                    }
                     self.set_head(commander, commander.get_current_head()?);
                     return Ok(Some(ComponentAction::ChangeHead(self.head.clone())));
                 }
+                PUSH_POPUP_ID => {
+                    return Ok(Some(ComponentAction::Push(PushTarget::Change {
+                        commit_id: self.head.commit_id.clone(),
+                        all_bookmarks: self.push_all_bookmarks,
+                        allow_new: self.push_allow_new,
+                    })));
+                }
                 _ => {}
             }
         }
 
         if let Ok(true) = self.bookmark_set_popup_rx.try_recv() {
-            self.log_panel.refresh_log_output(commander);
+            self.refresh_log(commander);
             self.refresh_head_output(commander)
         }
 
@@ -498,27 +1501,83 @@ impl Component for LogTab<'_> {
         f: &mut ratatui::prelude::Frame<'_>,
         area: ratatui::prelude::Rect,
     ) -> Result<()> {
-        let chunks = Layout::default()
-            .direction(self.config.layout().into())
-            .constraints([
-                Constraint::Percentage(self.config.layout_percent()),
-                Constraint::Percentage(100 - self.config.layout_percent()),
-            ])
-            .split(area);
+        let chunks = if self.details_folded {
+            Layout::default()
+                .constraints([Constraint::Fill(1), Constraint::Length(0)])
+                .split(area)
+        } else {
+            Layout::default()
+                .direction(self.config.layout().into())
+                .constraints([
+                    Constraint::Percentage(self.config.layout_percent()),
+                    Constraint::Percentage(100 - self.config.layout_percent()),
+                ])
+                .split(area)
+        };
         self.panel_rect = [chunks[0], chunks[1]];
 
         // Draw log
         self.log_panel.draw(f, chunks[0])?;
 
         // Draw change details
-        {
-            let head_content = match self.head_output.as_ref() {
+        if !self.details_folded {
+            let mut head_content = match self.head_output.as_ref() {
                 Ok(head_output) => head_output.into_text()?.lines,
                 Err(err) => err.into_text("Error getting head details")?.lines,
             };
+            let stat_line = match self.head_stat {
+                Some(stat) => Some(Line::from(vec![
+                    Span::raw(format!(
+                        "{} {} changed",
+                        stat.files,
+                        if stat.files == 1 { "file" } else { "files" }
+                    ))
+                    .bold(),
+                    Span::raw(", "),
+                    Span::raw(format!("+{}", stat.insertions)).fg(Color::Green),
+                    Span::raw(" "),
+                    Span::raw(format!("-{}", stat.deletions)).fg(Color::Red),
+                ])),
+                // Keep the space of the summary while it is computed
+                None if self.head_stat_rx.is_some() => {
+                    Some(Line::from("Counting changes...").fg(Color::DarkGray))
+                }
+                None => None,
+            };
+            if let Some(stat_line) = stat_line {
+                head_content.splice(0..0, [stat_line, Line::default()]);
+            }
+            let title = match self.interdiff_range.as_ref() {
+                Some((from, to)) => Line::from(
+                    [
+                        vec![Span::raw(" Diff ")],
+                        head_id_spans(from, self.config.primary_id()),
+                        vec![Span::raw("..")],
+                        head_id_spans(to, self.config.primary_id()),
+                        vec![Span::raw(" ")],
+                    ]
+                    .concat(),
+                ),
+                None => Line::from(
+                    [
+                        vec![Span::raw(" Details for ")],
+                        head_id_spans(&self.head, self.config.primary_id()),
+                        vec![Span::raw(match self.details_view {
+                            DetailsView::Full => "",
+                            DetailsView::Description => " - description",
+                            DetailsView::Diff => " - diff",
+                        })],
+                        vec![Span::raw(match self.diff_parent_label() {
+                            Some(label) => format!(" - {label} "),
+                            None => " ".to_owned(),
+                        })],
+                    ]
+                    .concat(),
+                ),
+            };
             self.head_panel
                 .render_context()
-                .title(format!(" Details for {} ", self.head.change_id))
+                .title(title)
                 .content(head_content)
                 .draw(f, chunks[1])
         }
@@ -540,8 +1599,17 @@ impl Component for LogTab<'_> {
         // Draw describe textarea
         {
             if let Some(describe_textarea) = self.describe_textarea.as_mut() {
+                let title = if self.bulk_describe_total > 0 {
+                    format!(
+                        " Describe ({}/{}) ",
+                        self.bulk_describe_total - self.bulk_describe_queue.len(),
+                        self.bulk_describe_total
+                    )
+                } else {
+                    " Describe ".to_owned()
+                };
                 let block = Block::bordered()
-                    .title(Span::styled(" Describe ", Style::new().bold().cyan()))
+                    .title(Span::styled(title, Style::new().bold().cyan()))
                     .title_alignment(Alignment::Center)
                     .border_type(BorderType::Rounded)
                     .border_style(Style::default().fg(Color::Green));
@@ -570,37 +1638,15 @@ impl Component for LogTab<'_> {
             }
         }
 
-        // Draw revset textarea
-        {
-            if let Some(log_revset_textarea) = self.log_revset_textarea.as_mut() {
-                let block = Block::bordered()
-                    .title(Span::styled(" Revset ", Style::new().bold().cyan()))
-                    .title_alignment(Alignment::Center)
-                    .border_type(BorderType::Rounded)
-                    .border_style(Style::default().fg(Color::Green));
-                let area = centered_rect_line_height(area, 30, 7);
-                f.render_widget(Clear, area);
-                f.render_widget(&block, area);
-
-                let popup_chunks = Layout::default()
-                    .direction(Direction::Vertical)
-                    .constraints([Constraint::Fill(1), Constraint::Length(2)])
-                    .split(block.inner(area));
-
-                f.render_widget(&*log_revset_textarea, popup_chunks[0]);
-
-                let help = Paragraph::new(vec!["Ctrl+s: save | Escape: cancel".into()])
-                    .fg(Color::DarkGray)
-                    .alignment(Alignment::Center)
-                    .block(
-                        Block::default()
-                            .borders(Borders::TOP)
-                            .border_type(BorderType::Rounded)
-                            .border_style(Style::default().fg(Color::DarkGray)),
-                    );
-
-                f.render_widget(help, popup_chunks[1]);
-            }
+        // Draw revset and path textareas
+        if let Some(log_revset_textarea) = self.log_revset_textarea.as_ref() {
+            draw_filter_prompt(f, area, " Revset ", log_revset_textarea);
+        }
+        if let Some(log_path_textarea) = self.log_path_textarea.as_ref() {
+            draw_filter_prompt(f, area, " Path ", log_path_textarea);
+        }
+        if let Some(log_search_textarea) = self.log_search_textarea.as_ref() {
+            draw_prompt(f, self.panel_rect[0], " Search ", log_search_textarea);
         }
 
         Ok(())
@@ -611,17 +1657,30 @@ impl Component for LogTab<'_> {
             if let Event::Key(key) = event {
                 match self.keybinds.match_event(key) {
                     LogTabEvent::Save => {
-                        // TODO: Handle error
-                        commander.run_describe(
+                        // Keep the description on failure, to save it again
+                        if let Err(err) = commander.run_describe(
                             self.head.commit_id.as_str(),
                             &describe_textarea.lines().join("\n"),
-                        )?;
+                        ) {
+                            return Ok(ComponentInputResult::HandledAction(
+                                ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                                    title: "Describe".into(),
+                                    messages: format!("{err:#}").into_text()?,
+                                    text_align: None,
+                                }))),
+                            ));
+                        }
                         self.set_head(commander, commander.get_head_latest(&self.head)?);
                         self.describe_textarea = None;
+                        if self.bulk_describe_total > 0 {
+                            self.describe_next_in_bulk(commander)?;
+                        }
                         return Ok(ComponentInputResult::Handled);
                     }
                     LogTabEvent::Cancel => {
                         self.describe_textarea = None;
+                        self.bulk_describe_queue.clear();
+                        self.bulk_describe_total = 0;
                         return Ok(ComponentInputResult::Handled);
                     }
                     _ => (),
@@ -636,13 +1695,31 @@ impl Component for LogTab<'_> {
                 match self.keybinds.match_event(key) {
                     LogTabEvent::Save => {
                         let log_revset = log_revset_textarea.lines().join("\n");
-                        self.log_panel.log_revset = if log_revset.trim().is_empty() {
-                            None
-                        } else {
-                            Some(log_revset)
-                        };
-                        self.log_panel.refresh_log_output(commander);
+                        let previous_revset = std::mem::replace(
+                            &mut self.log_panel.log_revset,
+                            if log_revset.trim().is_empty() {
+                                None
+                            } else {
+                                Some(log_revset)
+                            },
+                        );
+                        self.log_panel.clear_reveal();
+                        self.refresh_log(commander);
                         self.log_revset_textarea = None;
+
+                        // Keep showing the previous revset if jj rejects the new one
+                        if let Some(err) = self.log_panel.log_error() {
+                            let messages = err.into_text("")?;
+                            self.log_panel.log_revset = previous_revset;
+                            self.refresh_log(commander);
+                            return Ok(ComponentInputResult::HandledAction(
+                                ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                                    title: "Invalid revset".into(),
+                                    messages,
+                                    text_align: None,
+                                }))),
+                            ));
+                        }
                         return Ok(ComponentInputResult::Handled);
                     }
                     LogTabEvent::Cancel => {
@@ -656,6 +1733,62 @@ impl Component for LogTab<'_> {
             return Ok(ComponentInputResult::Handled);
         }
 
+        if let Some(log_path_textarea) = self.log_path_textarea.as_mut() {
+            if let Event::Key(key) = event {
+                match self.keybinds.match_event(key) {
+                    LogTabEvent::Save => {
+                        let log_path = log_path_textarea.lines().join("");
+                        self.log_panel.log_path = if log_path.trim().is_empty() {
+                            None
+                        } else {
+                            Some(log_path.trim().to_owned())
+                        };
+                        self.log_panel.clear_reveal();
+                        self.refresh_log(commander);
+                        self.refresh_head_output(commander);
+                        self.log_path_textarea = None;
+                        return Ok(ComponentInputResult::Handled);
+                    }
+                    LogTabEvent::Cancel => {
+                        self.log_path_textarea = None;
+                        return Ok(ComponentInputResult::Handled);
+                    }
+                    _ => (),
+                }
+            }
+            log_path_textarea.input(event);
+            return Ok(ComponentInputResult::Handled);
+        }
+
+        if let Some(log_search_textarea) = self.log_search_textarea.as_mut() {
+            if let Event::Key(key) = event {
+                if key.code == KeyCode::Enter || self.keybinds.match_event(key) == LogTabEvent::Save
+                {
+                    self.log_search_textarea = None;
+                    self.sync_head_output(commander);
+                    return Ok(ComponentInputResult::Handled);
+                }
+                if self.keybinds.match_event(key) == LogTabEvent::Cancel {
+                    self.log_search_textarea = None;
+                    self.log_panel.search = None;
+                    self.sync_head_output(commander);
+                    return Ok(ComponentInputResult::Handled);
+                }
+            }
+
+            // Search as you type, without running jj again
+            if log_search_textarea.input(event) {
+                let search = log_search_textarea.lines().join("");
+                self.log_panel.search = if search.is_empty() {
+                    None
+                } else {
+                    Some(search)
+                };
+                self.log_panel.select_search_match(false, true);
+            }
+            return Ok(ComponentInputResult::Handled);
+        }
+
         if let Event::Key(key) = event {
             if key.kind != KeyEventKind::Press {
                 return Ok(ComponentInputResult::Handled);
@@ -674,27 +1807,35 @@ impl Component for LogTab<'_> {
                 return Ok(ComponentInputResult::Handled);
             }
 
-            if self.head_panel.input(key) {
+            if !self.details_folded && self.head_panel.input(key) {
                 return Ok(ComponentInputResult::Handled);
             }
 
             let input_result = self.log_panel.input(commander, event)?;
             if input_result.is_handled() {
+                self.forget_hidden_scroll();
                 self.sync_head_output(commander);
                 return Ok(input_result);
             }
 
             let log_tab_event = self.keybinds.match_event(key);
+
+            if self.log_panel.search.is_some() && log_tab_event == LogTabEvent::Cancel {
+                self.log_panel.search = None;
+                return Ok(ComponentInputResult::Handled);
+            }
+
             return self.handle_event(commander, log_tab_event);
         }
 
         if let Event::Mouse(mouse_event) = event {
             let input_result = self.log_panel.input(commander, event.clone())?;
             if input_result.is_handled() {
+                self.forget_hidden_scroll();
                 self.sync_head_output(commander);
                 return Ok(input_result);
             }
-            if self.head_panel.input_mouse(mouse_event) {
+            if !self.details_folded && self.head_panel.input_mouse(mouse_event) {
                 return Ok(ComponentInputResult::Handled);
             }
             return Ok(ComponentInputResult::NotHandled);
@@ -703,3 +1844,34 @@ impl Component for LogTab<'_> {
         Ok(ComponentInputResult::Handled)
     }
 }
+
+/// Draw a popup with a textarea editing a log filter
+fn draw_filter_prompt(f: &mut Frame<'_>, area: Rect, title: &str, textarea: &TextArea<'_>) {
+    let block = Block::bordered()
+        .title(Span::styled(title, Style::new().bold().cyan()))
+        .title_alignment(Alignment::Center)
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Green));
+    let area = centered_rect_line_height(area, 30, 7);
+    f.render_widget(Clear, area);
+    f.render_widget(&block, area);
+
+    let popup_chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Fill(1), Constraint::Length(2)])
+        .split(block.inner(area));
+
+    f.render_widget(textarea, popup_chunks[0]);
+
+    let help = Paragraph::new(vec!["Ctrl+s: save | Escape: cancel".into()])
+        .fg(Color::DarkGray)
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::TOP)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+
+    f.render_widget(help, popup_chunks[1]);
+}
@@ -1,23 +1,47 @@
+pub mod abandon_popup;
+pub mod annotate_popup;
+pub mod bookmark_delete_popup;
+pub mod bookmark_move_popup;
 pub mod bookmark_set_popup;
 pub mod bookmarks_tab;
 pub mod command_log_tab;
 pub mod command_popup;
+pub mod config_popup;
+pub mod confirm_popup;
+pub mod conflicts_popup;
+pub mod describe_popup;
 pub mod files_tab;
 pub mod help_popup;
+pub mod jump_to_change_popup;
 pub mod log_tab;
 pub mod message_popup;
+pub mod not_a_repo;
+pub mod op_log_tab;
+pub mod operations_popup;
 pub mod panel;
+pub mod rebase_editor_popup;
+pub mod rebase_popup;
+pub mod remotes_popup;
+pub mod sparse_popup;
+pub mod split_files_popup;
+pub mod split_popup;
+pub mod squash_files_popup;
 pub mod styles;
+pub mod syntax;
+pub mod tags_popup;
 pub mod utils;
+pub mod workspaces_popup;
 
 use std::time::Instant;
 
 use crate::{
     ComponentInputResult,
-    app::{App, Tab},
+    app::{App, AutoRefresh, FetchTarget, PushTarget},
     commander::{Commander, log::Head},
+    ui::utils::{format_elapsed, head_id_spans},
 };
 use anyhow::Result;
+use chrono::Local;
 use ratatui::{
     Frame,
     crossterm::event::Event,
@@ -27,13 +51,32 @@ use ratatui::{
 };
 use ratatui::{prelude::*, widgets::*};
 
+/// Frames of the spinner shown while a fetch or push runs
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+
+/// Work run while the UI is suspended, see [ComponentAction::Suspend]
+pub type SuspendedTask = Box<dyn FnOnce(&mut Commander) -> Result<ComponentAction>>;
+
 pub enum ComponentAction {
     ViewFiles(Head),
     ViewLog(Head),
+    /// Select the change in the log, showing it even when the log filter or limit hides it
+    JumpToChange(Head),
     ChangeHead(Head),
     SetPopup(Option<Box<dyn Component>>),
     Multiple(Vec<ComponentAction>),
+    /// Run `jj git fetch` in the background
+    Fetch(FetchTarget),
+    /// Run `jj git push` in the background
+    Push(PushTarget),
     RefreshTab(),
+    /// Forget the changes marked in the log, e.g. once they are abandoned
+    ClearMarks(),
+    /// Operate on the workspace at the path, restarting lazyjj there
+    SwitchWorkspace(String),
+    /// Leave the UI to run a program which uses the terminal, e.g. an editor, then handle the
+    /// action returned once the UI is back
+    Suspend(SuspendedTask),
 }
 
 pub trait Component {
@@ -66,7 +109,7 @@ pub fn ui(f: &mut Frame, app: &mut App) -> Result<()> {
 
     {
         let tabs = Tabs::new(
-            Tab::VALUES
+            app.tab_sequence
                 .iter()
                 .enumerate()
                 .map(|(i, tab)| format!("[{}] {}", i + 1, tab)),
@@ -78,7 +121,7 @@ pub fn ui(f: &mut Frame, app: &mut App) -> Result<()> {
         )
         .highlight_style(Style::default().bg(app.env.config.highlight_color()))
         .select(
-            Tab::VALUES
+            app.tab_sequence
                 .iter()
                 .position(|tab| tab == &app.current_tab)
                 .unwrap_or(0),
@@ -88,14 +131,128 @@ pub fn ui(f: &mut Frame, app: &mut App) -> Result<()> {
         f.render_widget(tabs, header_chunks[0]);
     }
     {
-        let tabs = Paragraph::new("q: quit | ?: help | R: refresh | 1/2/3/4: change tab")
-            .fg(Color::DarkGray)
-            .block(
-                Block::bordered()
-                    .title(" lazyjj ")
-                    .border_type(BorderType::Rounded)
-                    .fg(Color::default()),
+        let mut block = Block::bordered()
+            .title(" lazyjj ")
+            .border_type(BorderType::Rounded)
+            .fg(Color::default());
+        if let Some(workspace_name) = app.workspace_name.as_ref() {
+            block =
+                block.title(Line::from(format!(" workspace: {workspace_name} ")).fg(Color::Cyan));
+        }
+        if let Some((ahead, behind)) = app.trunk_ahead_behind {
+            let status = match (ahead, behind) {
+                (0, 0) => "@: on trunk".to_owned(),
+                (ahead, 0) => format!("@: {ahead} ahead of trunk"),
+                (0, behind) => format!("@: {behind} behind trunk"),
+                (ahead, behind) => format!("@: {ahead} ahead, {behind} behind trunk"),
+            };
+            block = block.title(Line::from(format!(" {status} ")).right_aligned());
+        }
+        if let Some((remote_command, started)) = app.remote_command.as_ref() {
+            let frame = (started.elapsed().as_millis() / 100) as usize % SPINNER.len();
+            block = block.title(
+                Line::from(format!(
+                    " {} {} ",
+                    SPINNER[frame],
+                    remote_command.progress_label()
+                ))
+                .fg(Color::Yellow)
+                .right_aligned(),
+            );
+        } else if let Some(last_fetch_time) = app.last_fetch_time {
+            block = block.title(
+                Line::from(format!(
+                    " fetched {} ",
+                    format_elapsed(Local::now() - last_fetch_time)
+                ))
+                .fg(Color::DarkGray)
+                .right_aligned(),
+            );
+        }
+        match app.auto_refresh {
+            AutoRefresh::Running => {
+                block = block.title(
+                    Line::from(format!(" ⟳ {}s ", app.auto_refresh_interval.as_secs_f32()))
+                        .fg(Color::DarkGray)
+                        .right_aligned(),
+                );
+            }
+            AutoRefresh::Paused => {
+                block = block.title(
+                    Line::from(" auto-refresh paused ")
+                        .fg(Color::Yellow)
+                        .right_aligned(),
+                );
+            }
+            AutoRefresh::Disabled => {}
+        }
+        if let Some(at_operation) = app.at_operation.as_ref() {
+            block = block.title(
+                Line::from(format!(" Viewing operation {at_operation} (read-only) "))
+                    .bold()
+                    .fg(Color::Yellow)
+                    .centered(),
             );
+        }
+
+        let quit_key = app
+            .env
+            .config
+            .quit_shortcuts()
+            .first()
+            .map_or("Control+c".to_owned(), |shortcut| shortcut.to_string());
+        if let Some(status) = app.working_copy_status.as_ref() {
+            let description = if status.description.is_empty() {
+                Span::raw("(no description set)").fg(Color::Yellow)
+            } else {
+                Span::raw(status.description.clone())
+            };
+            let mut spans = vec![Span::raw(" @: ")];
+            spans.extend(head_id_spans(&status.head, app.env.config.primary_id()));
+            spans.extend([Span::raw(" "), description, Span::raw(" ")]);
+            // Changed file counts, like `jj status`
+            for (count, label, color) in [
+                (status.added, "A", Color::Green),
+                (status.modified, "M", Color::Cyan),
+                (status.deleted, "D", Color::Red),
+            ] {
+                if count > 0 {
+                    spans.push(Span::raw(format!("{label}{count} ")).fg(color));
+                }
+            }
+            if !status.conflicts.is_empty() {
+                spans.push(
+                    Span::raw(format!(
+                        " {} conflicted {} ",
+                        status.conflicts.len(),
+                        if status.conflicts.len() == 1 {
+                            "file"
+                        } else {
+                            "files"
+                        }
+                    ))
+                    .bold()
+                    .fg(Color::White)
+                    .bg(Color::Red),
+                );
+            }
+            spans.push(Span::raw(" (i: edit) ").fg(Color::DarkGray));
+            block = block.title_bottom(Line::from(spans));
+        }
+        if app.quit_pending() {
+            block = block.title(
+                Line::from(format!(" Press {quit_key} again to quit "))
+                    .bold()
+                    .fg(Color::Yellow)
+                    .centered(),
+            );
+        }
+
+        let tabs = Paragraph::new(format!(
+            "{quit_key}: quit | ?: help | R: refresh | 1/2/3/4: change tab"
+        ))
+        .fg(Color::DarkGray)
+        .block(block);
 
         f.render_widget(tabs, header_chunks[1]);
     }
@@ -0,0 +1,53 @@
+use ratatui::{
+    Frame,
+    layout::{Alignment, Constraint, Direction, Layout},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, BorderType, Borders, Paragraph, Wrap},
+};
+
+use crate::{
+    env::NotARepoError,
+    ui::{styles::create_popup_block, utils::centered_rect_line_height},
+};
+
+/// Screen shown instead of the tabs when lazyjj is started outside of a jj repo
+pub fn draw_not_a_repo(f: &mut Frame, err: &NotARepoError) {
+    let block = create_popup_block("No jj repository");
+    let area = centered_rect_line_height(f.area(), 60, 11);
+    f.render_widget(&block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Fill(1), Constraint::Length(2)])
+        .split(block.inner(area));
+
+    let hint = if err.git_repo {
+        "This is a git repo. Run `jj git init --colocate` to use jj in it, alongside git."
+    } else {
+        "Run `jj git init` to create a repo here, or start lazyjj in a jj repo with `lazyjj --path <path>`."
+    };
+    let lines = vec![
+        Line::from("lazyjj needs a jj repository, and there is none in"),
+        Line::from(err.path.clone()).bold(),
+        Line::from(""),
+        Line::from(hint).fg(Color::Yellow),
+    ];
+    f.render_widget(
+        Paragraph::new(lines)
+            .alignment(Alignment::Center)
+            .wrap(Wrap { trim: false }),
+        chunks[0],
+    );
+
+    let help = Paragraph::new(vec!["q/Escape: quit".into()])
+        .fg(Color::DarkGray)
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::TOP)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+    f.render_widget(help, chunks[1]);
+}
@@ -0,0 +1,304 @@
+use anyhow::Result;
+
+use ansi_to_tui::IntoText;
+use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEventKind},
+    prelude::*,
+    widgets::*,
+};
+use tracing::instrument;
+
+use crate::{
+    ComponentInputResult,
+    commander::{CommandError, Commander, operations::Operation},
+    env::Config,
+    ui::{
+        Component, ComponentAction, confirm_popup::confirm, help_popup::HelpPopup,
+        message_popup::MessagePopup, panel::DetailsPanel, utils::position_indicator,
+    },
+};
+
+/// Number of operations listed in the tab
+const OPERATIONS_LIMIT: usize = 200;
+
+/// Op log tab. Shows the operation log in main panel and the changes made by the selected
+/// operation in details panel
+pub struct OpLogTab {
+    operations_output: Result<Vec<Operation>, CommandError>,
+    operations_list_state: ListState,
+    operations_height: u16,
+
+    operation_output: Result<Option<String>, CommandError>,
+    operation_panel: DetailsPanel,
+
+    config: Config,
+}
+
+impl OpLogTab {
+    #[instrument(level = "trace", skip(commander))]
+    pub fn new(commander: &mut Commander) -> Result<Self> {
+        let mut op_log_tab = Self {
+            operations_output: Ok(vec![]),
+            operations_list_state: ListState::default(),
+            operations_height: 0,
+            operation_output: Ok(None),
+            operation_panel: DetailsPanel::new()
+                .with_highlight_color(commander.env.config.highlight_color())
+                .with_tab_width(commander.env.config.tab_width())
+                .with_wrap(commander.env.config.details_wrap()),
+            config: commander.env.config.clone(),
+        };
+        op_log_tab.refresh_operations(commander);
+        Ok(op_log_tab)
+    }
+
+    fn selected_operation(&self) -> Option<&Operation> {
+        let operations = self.operations_output.as_ref().ok()?;
+        operations.get(self.operations_list_state.selected()?)
+    }
+
+    /// Reload the operation log, keeping the selected operation if it is still listed
+    fn refresh_operations(&mut self, commander: &mut Commander) {
+        let selected_id = self
+            .selected_operation()
+            .map(|operation| operation.id.clone());
+        self.operations_output = commander.get_operations(OPERATIONS_LIMIT);
+
+        let selected = self.operations_output.as_ref().ok().and_then(|operations| {
+            selected_id
+                .and_then(|id| operations.iter().position(|operation| operation.id == id))
+                .or(operations.first().map(|_| 0))
+        });
+        self.operations_list_state.select(selected);
+        self.refresh_operation(commander);
+    }
+
+    fn refresh_operation(&mut self, commander: &mut Commander) {
+        self.operation_output = self
+            .selected_operation()
+            .map(|operation| operation.id.clone())
+            .map(|id| commander.get_operation_show(&id))
+            .transpose();
+    }
+
+    fn scroll_operations(&mut self, commander: &mut Commander, scroll: isize) {
+        let Ok(operations) = self.operations_output.as_ref() else {
+            return;
+        };
+        if operations.is_empty() {
+            return;
+        }
+        self.operations_list_state.select(Some(
+            self.operations_list_state
+                .selected()
+                .map(|selected| selected.saturating_add_signed(scroll))
+                .unwrap_or(0)
+                .min(operations.len() - 1),
+        ));
+        self.operation_panel.scroll_to(0);
+        self.operation_panel.clear_cursor();
+        self.refresh_operation(commander);
+    }
+}
+
+impl Component for OpLogTab {
+    fn focus(&mut self, commander: &mut Commander) -> Result<()> {
+        self.refresh_operations(commander);
+        Ok(())
+    }
+
+    fn update(&mut self, _commander: &mut Commander) -> Result<Option<ComponentAction>> {
+        self.operation_panel.take_copy_action()
+    }
+
+    fn draw(
+        &mut self,
+        f: &mut ratatui::prelude::Frame<'_>,
+        area: ratatui::prelude::Rect,
+    ) -> Result<()> {
+        let chunks = Layout::default()
+            .direction(self.config.layout().into())
+            .constraints([
+                Constraint::Percentage(self.config.layout_percent()),
+                Constraint::Percentage(100 - self.config.layout_percent()),
+            ])
+            .split(area);
+
+        // Draw operations
+        {
+            let selected = self.operations_list_state.selected();
+            let (lines, len) = match self.operations_output.as_ref() {
+                Ok(operations) => (
+                    operations
+                        .iter()
+                        .enumerate()
+                        .map(|(i, operation)| {
+                            let mut line = Line::from(vec![
+                                Span::raw(" "),
+                                Span::raw(operation.id.clone()).fg(Color::Blue),
+                                Span::raw(" "),
+                                Span::raw(operation.time.clone()).fg(Color::Cyan),
+                                Span::raw(" "),
+                                Span::raw(operation.user.clone()).fg(Color::Green),
+                                Span::raw(" "),
+                                Span::raw(operation.description.clone()),
+                            ]);
+                            if selected == Some(i) {
+                                line = line.bg(self.config.highlight_color());
+                            }
+                            line
+                        })
+                        .collect::<Vec<Line>>(),
+                    operations.len(),
+                ),
+                Err(err) => (err.into_text("Error getting operations")?.lines, 0),
+            };
+
+            let mut operations_block = Block::bordered()
+                .title(" Operations ")
+                .border_type(BorderType::Rounded);
+            if len > 0 {
+                operations_block = operations_block.title_bottom(position_indicator(selected, len));
+            }
+            let operations = List::new(lines).block(operations_block).scroll_padding(3);
+            f.render_stateful_widget(operations, chunks[0], &mut self.operations_list_state);
+            self.operations_height = chunks[0].height.saturating_sub(2);
+
+            if let Some(index) = selected
+                && len > self.operations_height as usize
+            {
+                let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight);
+                let mut scrollbar_state = ScrollbarState::default()
+                    .content_length(len)
+                    .position(index);
+
+                f.render_stateful_widget(
+                    scrollbar,
+                    chunks[0].inner(Margin {
+                        vertical: 1,
+                        horizontal: 0,
+                    }),
+                    &mut scrollbar_state,
+                );
+            }
+        }
+
+        // Draw operation changes
+        {
+            let operation_content = match self.operation_output.as_ref() {
+                Ok(Some(operation_content)) => operation_content.into_text()?,
+                Ok(None) => Text::default(),
+                Err(err) => err.into_text("Error getting operation")?,
+            };
+            self.operation_panel
+                .render_context()
+                .title(" Operation ")
+                .content(operation_content)
+                .draw(f, chunks[1]);
+        }
+
+        Ok(())
+    }
+
+    fn input(&mut self, commander: &mut Commander, event: Event) -> Result<ComponentInputResult> {
+        if let Event::Key(key) = event {
+            if key.kind != KeyEventKind::Press {
+                return Ok(ComponentInputResult::Handled);
+            }
+
+            if self.operation_panel.input(key) {
+                return Ok(ComponentInputResult::Handled);
+            }
+
+            match key.code {
+                KeyCode::Char('j') | KeyCode::Down => self.scroll_operations(commander, 1),
+                KeyCode::Char('k') | KeyCode::Up => self.scroll_operations(commander, -1),
+                KeyCode::Char('J') => {
+                    self.scroll_operations(commander, self.operations_height as isize / 2);
+                }
+                KeyCode::Char('K') => {
+                    self.scroll_operations(
+                        commander,
+                        (self.operations_height as isize / 2).saturating_neg(),
+                    );
+                }
+                KeyCode::Char('@') => self.scroll_operations(commander, isize::MIN),
+                KeyCode::Char('/') => self.operation_panel.start_search(),
+                KeyCode::Char('r') => {
+                    if let Some(id) = self
+                        .selected_operation()
+                        .map(|operation| operation.id.clone())
+                    {
+                        return Ok(ComponentInputResult::HandledAction(confirm(
+                            &self.config,
+                            commander,
+                            "Restore operation",
+                            vec![
+                                Line::from(format!("Restore the repo to operation {id}?")),
+                                Line::from("Use u to undo the restore.").fg(Color::DarkGray),
+                            ],
+                            move |commander| match commander.run_op_restore(&id) {
+                                Ok(()) => Ok(ComponentAction::RefreshTab()),
+                                Err(err) => {
+                                    Ok(ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                                        title: "Restore operation".into(),
+                                        messages: format!("{err:#}").into_text()?,
+                                        text_align: None,
+                                    }))))
+                                }
+                            },
+                        )?));
+                    }
+                }
+                KeyCode::Char('R') | KeyCode::F(5) => self.refresh_operations(commander),
+                KeyCode::Char('?') => {
+                    return Ok(ComponentInputResult::HandledAction(
+                        ComponentAction::SetPopup(Some(Box::new(HelpPopup::new(
+                            vec![
+                                ("j/k".to_owned(), "scroll down/up".to_owned()),
+                                ("J/K".to_owned(), "scroll down by ½ page".to_owned()),
+                                ("@".to_owned(), "latest operation".to_owned()),
+                                (
+                                    "r".to_owned(),
+                                    "restore the repo to the operation".to_owned(),
+                                ),
+                                ("u/U".to_owned(), "undo/redo".to_owned()),
+                                ("R".to_owned(), "refresh".to_owned()),
+                            ],
+                            vec![
+                                ("Ctrl+e/Ctrl+y".to_owned(), "scroll down/up".to_owned()),
+                                (
+                                    "Ctrl+d/Ctrl+u".to_owned(),
+                                    "scroll down/up by ½ page".to_owned(),
+                                ),
+                                (
+                                    "Ctrl+f/Ctrl+b".to_owned(),
+                                    "scroll down/up by page".to_owned(),
+                                ),
+                                ("W".to_owned(), "toggle wrapping".to_owned()),
+                                (
+                                    "</>".to_owned(),
+                                    "scroll long lines left/right when not wrapping".to_owned(),
+                                ),
+                                (
+                                    "/".to_owned(),
+                                    "search details, Enter/Up: next/previous match".to_owned(),
+                                ),
+                            ],
+                        )))),
+                    ));
+                }
+                _ => return Ok(ComponentInputResult::NotHandled),
+            };
+        }
+
+        if let Event::Mouse(mouse) = event {
+            if self.operation_panel.input_mouse(mouse) {
+                return Ok(ComponentInputResult::Handled);
+            }
+            return Ok(ComponentInputResult::NotHandled);
+        }
+
+        Ok(ComponentInputResult::Handled)
+    }
+}
@@ -0,0 +1,153 @@
+use anyhow::Result;
+use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEventKind},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListState, Paragraph},
+};
+
+use crate::{
+    ComponentInputResult,
+    commander::{Commander, operations::Operation},
+    env::Config,
+    ui::{Component, ComponentAction, styles::create_popup_block, utils::centered_rect},
+};
+
+/// Number of operations listed in the popup
+const OPERATIONS_LIMIT: usize = 100;
+
+/// Popup to pick an operation to view the repo at. The first entry returns to the latest operation.
+pub struct OperationsPopup {
+    operations: Result<Vec<Operation>, String>,
+    at_operation: Option<String>,
+    list_state: ListState,
+    list_height: u16,
+    config: Config,
+}
+
+impl OperationsPopup {
+    pub fn new(config: Config, commander: &mut Commander) -> Self {
+        Self {
+            operations: commander
+                .get_operations(OPERATIONS_LIMIT)
+                .map_err(|err| err.to_string()),
+            at_operation: commander.at_operation.clone(),
+            list_state: ListState::default().with_selected(Some(0)),
+            list_height: 0,
+            config,
+        }
+    }
+
+    fn len(&self) -> usize {
+        1 + self
+            .operations
+            .as_ref()
+            .map_or(0, |operations| operations.len())
+    }
+
+    fn scroll(&mut self, scroll: isize) {
+        self.list_state.select(Some(
+            self.list_state
+                .selected()
+                .map(|selected| selected.saturating_add_signed(scroll))
+                .unwrap_or(0)
+                .min(self.len() - 1),
+        ));
+    }
+}
+
+impl Component for OperationsPopup {
+    fn draw(&mut self, f: &mut ratatui::prelude::Frame<'_>, area: Rect) -> Result<()> {
+        let block = create_popup_block("View repo at operation");
+        let area = centered_rect(area, 60, 60);
+        f.render_widget(Clear, area);
+        f.render_widget(&block, area);
+
+        let popup_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Fill(1), Constraint::Length(2)])
+            .split(block.inner(area));
+        self.list_height = popup_chunks[0].height;
+
+        let mut items = vec![Line::from("Latest operation (editable)").fg(Color::Yellow)];
+        match self.operations.as_ref() {
+            Ok(operations) => {
+                items.extend(operations.iter().map(|operation| {
+                    let mut line = Line::from(vec![
+                        Span::raw(operation.id.clone()).fg(Color::Blue),
+                        Span::raw(" "),
+                        Span::raw(operation.time.clone()).fg(Color::Cyan),
+                        Span::raw(" "),
+                        Span::raw(operation.user.clone()).fg(Color::Green),
+                        Span::raw(" "),
+                        Span::raw(operation.description.clone()),
+                    ]);
+                    if self.at_operation.as_ref() == Some(&operation.id) {
+                        line = line.bold();
+                    }
+                    line
+                }));
+            }
+            Err(err) => items.push(Line::from(err.clone()).fg(Color::Red)),
+        }
+
+        let list = List::new(items)
+            .scroll_padding(3)
+            .highlight_style(Style::default().bg(self.config.highlight_color()));
+        f.render_stateful_widget(list, popup_chunks[0], &mut self.list_state);
+
+        let help = Paragraph::new(vec![
+            "j/k: scroll down/up | Enter: view repo at operation | Escape: cancel".into(),
+        ])
+        .fg(Color::DarkGray)
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::TOP)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+        f.render_widget(help, popup_chunks[1]);
+
+        Ok(())
+    }
+
+    fn input(&mut self, commander: &mut Commander, event: Event) -> Result<ComponentInputResult> {
+        if let Event::Key(key) = event
+            && key.kind == KeyEventKind::Press
+        {
+            match key.code {
+                KeyCode::Char('j') | KeyCode::Down => self.scroll(1),
+                KeyCode::Char('k') | KeyCode::Up => self.scroll(-1),
+                KeyCode::Char('J') => self.scroll(self.list_height as isize / 2),
+                KeyCode::Char('K') => self.scroll((self.list_height as isize / 2).saturating_neg()),
+                KeyCode::Enter => {
+                    let at_operation = match self.list_state.selected() {
+                        Some(0) | None => None,
+                        Some(index) => match self
+                            .operations
+                            .as_ref()
+                            .ok()
+                            .and_then(|operations| operations.get(index - 1))
+                        {
+                            Some(operation) => Some(operation.id.clone()),
+                            None => return Ok(ComponentInputResult::Handled),
+                        },
+                    };
+                    commander.at_operation = at_operation;
+                    return Ok(ComponentInputResult::HandledAction(
+                        ComponentAction::Multiple(vec![
+                            ComponentAction::SetPopup(None),
+                            ComponentAction::RefreshTab(),
+                        ]),
+                    ));
+                }
+                _ => return Ok(ComponentInputResult::NotHandled),
+            }
+            return Ok(ComponentInputResult::Handled);
+        }
+
+        Ok(ComponentInputResult::NotHandled)
+    }
+}
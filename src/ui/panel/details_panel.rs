@@ -1,22 +1,110 @@
+use std::{
+    ops::{Range, RangeInclusive},
+    sync::LazyLock,
+};
+
+use anyhow::Result;
+use itertools::{EitherOrBoth, Itertools};
 use ratatui::{
-    crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseEvent, MouseEventKind},
+    crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind},
     layout::{Margin, Position, Rect},
-    text::{Line, Text},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span, Text},
     widgets::{
         Block, BorderType, Padding, Paragraph, Scrollbar, ScrollbarOrientation, ScrollbarState,
         Wrap,
     },
 };
+use regex::Regex;
 use tracing::trace;
+use tui_textarea::{CursorMove, TextArea};
+
+use crate::{
+    env::ThemeColors,
+    ui::{
+        ComponentAction,
+        syntax::{Language, highlight_line},
+        utils::{copy_action, draw_prompt, line_string, search_ranges, style_chars},
+    },
+};
+
+/// Style of the lines selected by clicking them
+const CURSOR_STYLE: Style = Style::new().bg(Color::DarkGray);
+
+/// Narrowest content width at which git diffs are shown side by side
+const MIN_SIDE_BY_SIDE_WIDTH: usize = 80;
+
+/// Separator between the old and new columns of side by side diffs
+const SIDE_BY_SIDE_SEPARATOR: &str = " │ ";
+
+/// Only the first lines of longer content get syntax highlighting, to keep rendering fast
+const MAX_SYNTAX_HIGHLIGHT_LINES: usize = 5000;
+
+/// Longest content whose changed words are highlighted, as pairing its lines gets slow
+const MAX_WORD_DIFF_LINES: usize = 5000;
+
+/// Columns scrolled sideways by `<`/`>` or a horizontal mouse wheel step
+const HORIZONTAL_SCROLL_COLUMNS: usize = 8;
 
 /// Details panel used for the right side of each tab.
-/// This handles scrolling and wrapping.
+/// This handles scrolling, wrapping, tab expansion, word diff highlighting and selecting lines
+/// to copy.
 pub struct DetailsPanel {
     panel_rect: Rect,
     scroll: u16,
+    /// Columns scrolled to the right, when lines don't wrap
+    hscroll: u16,
     height: u16,
+    /// Width of the content area, updated when rendering
+    width: u16,
     lines: u16,
+    /// Width of the longest line, updated when rendering
+    longest_line: u16,
     wrap: bool,
+    reflow: bool,
+    /// Show git diffs in two columns, old and new
+    side_by_side: bool,
+    /// Color the code of added and removed lines of git diffs
+    syntax_highlighting: bool,
+    /// Colors replacing jj's diff colors
+    theme_colors: ThemeColors,
+    tab_width: usize,
+    /// Index of the content line marked by clicking it, the moving end of the selection
+    cursor: Option<usize>,
+    /// Index of the content line where the selection started
+    anchor: Option<usize>,
+    /// Rendered row clicked since the last render, and whether the click extends the selection.
+    /// Resolved to a content line when rendering
+    pending_click: Option<(u16, bool)>,
+    /// Selected lines stripped of diff markers, updated when rendering
+    selected_text: Option<String>,
+    /// Copy the whole content, resolved when rendering as the content is only known then
+    pending_copy_all: bool,
+    /// Text to copy with what it is, taken by [DetailsPanel::take_copy_action]
+    copied_text: Option<(&'static str, String)>,
+    /// Background of the text matching the search
+    highlight_color: Color,
+    /// Text searched for in the content. Matches are highlighted
+    search: Option<String>,
+    /// Prompt editing the search, while it is open
+    search_textarea: Option<TextArea<'static>>,
+    /// Content lines matching the search, updated when rendering
+    search_matches: Vec<usize>,
+    /// Content line of the match scrolled to last
+    search_line: Option<usize>,
+    /// Match to scroll to, resolved when rendering as the content is only known then
+    pending_search_jump: Option<SearchJump>,
+    /// Plain lines of the last content whose changed words were highlighted, with the changed
+    /// characters of each line, to avoid pairing the lines again on every render
+    word_diff_cache: Option<(Vec<String>, Vec<Option<Range<usize>>>)>,
+}
+
+/// Which search match to scroll to
+#[derive(Clone, Copy)]
+enum SearchJump {
+    First,
+    Next,
+    Previous,
 }
 
 /// Transient object holding render data
@@ -34,7 +122,11 @@ pub enum DetailsPanelEvent {
     ScrollUpHalfPage,
     ScrollDownPage,
     ScrollUpPage,
+    ScrollLeft,
+    ScrollRight,
     ToggleWrap,
+    ToggleReflow,
+    ToggleSideBySide,
 }
 
 impl<'a> DetailsPanelRenderContext<'a> {
@@ -82,14 +174,35 @@ impl<'a> DetailsPanelRenderContext<'a> {
         };
         // Create content widget that uses border
         let paragraph_area = border.inner(area);
-        let paragraph = self
-            .panel
-            .render(content_text.clone(), paragraph_area)
-            .block(border);
+        let content_text = if self.panel.reflow {
+            reflow_description(content_text, paragraph_area.width)
+        } else {
+            content_text.clone()
+        };
+        let paragraph = self.panel.render(content_text, paragraph_area);
+        if let Some(search) = self.panel.search.as_ref() {
+            let position = self
+                .panel
+                .search_line
+                .and_then(|line| self.panel.search_matches.iter().position(|l| *l == line))
+                .map_or(0, |index| index + 1);
+            border = border.title_bottom(
+                Line::from(format!(
+                    " {search}: {position}/{} ",
+                    self.panel.search_matches.len()
+                ))
+                .right_aligned(),
+            );
+        }
+        let paragraph = paragraph.block(border);
 
         // render content and border
         f.render_widget(paragraph, area);
 
+        if let Some(search_textarea) = self.panel.search_textarea.as_ref() {
+            draw_prompt(f, area, " Search ", search_textarea);
+        }
+
         // render scrollbar on top of border
         if self.panel.lines > paragraph_area.height {
             let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight);
@@ -114,12 +227,63 @@ impl DetailsPanel {
         Self {
             panel_rect: Rect::ZERO,
             scroll: 0,
+            hscroll: 0,
             height: 0,
+            width: 0,
             lines: 0,
+            longest_line: 0,
             wrap: true,
+            reflow: false,
+            side_by_side: false,
+            syntax_highlighting: false,
+            theme_colors: ThemeColors::default(),
+            tab_width: 4,
+            cursor: None,
+            anchor: None,
+            pending_click: None,
+            selected_text: None,
+            pending_copy_all: false,
+            copied_text: None,
+            highlight_color: Color::DarkGray,
+            search: None,
+            search_textarea: None,
+            search_matches: vec![],
+            search_line: None,
+            pending_search_jump: None,
+            word_diff_cache: None,
         }
     }
 
+    /// Set the distance between tab stops used when expanding tabs
+    pub fn with_tab_width(mut self, tab_width: usize) -> Self {
+        self.tab_width = tab_width.max(1);
+        self
+    }
+
+    /// Set whether git diffs get syntax highlighting
+    pub fn with_syntax_highlighting(mut self, syntax_highlighting: bool) -> Self {
+        self.syntax_highlighting = syntax_highlighting;
+        self
+    }
+
+    /// Set the colors replacing jj's diff colors
+    pub fn with_theme_colors(mut self, theme_colors: ThemeColors) -> Self {
+        self.theme_colors = theme_colors;
+        self
+    }
+
+    /// Set whether long lines wrap
+    pub fn with_wrap(mut self, wrap: bool) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Set the background of the text matching the search
+    pub fn with_highlight_color(mut self, highlight_color: Color) -> Self {
+        self.highlight_color = highlight_color;
+        self
+    }
+
     pub fn render_context(&mut self) -> DetailsPanelRenderContext<'_> {
         DetailsPanelRenderContext::new(self)
     }
@@ -129,28 +293,279 @@ impl DetailsPanel {
     where
         T: Into<Text<'a>>,
     {
-        let mut paragraph = Paragraph::new(content);
+        let text = content.into();
+        if std::mem::take(&mut self.pending_copy_all) {
+            self.copied_text = Some((
+                "The content",
+                text.lines
+                    .iter()
+                    .map(line_string)
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            ));
+        }
+
+        let mut text = recolor_diff(expand_tabs(text, self.tab_width), &self.theme_colors);
+        if self.syntax_highlighting {
+            text = highlight_diff_syntax(text);
+        }
+        if text.lines.len() <= MAX_WORD_DIFF_LINES {
+            let plain = text.lines.iter().map(line_string).collect();
+            text = highlight_word_diff(text, self.word_diff_ranges(plain));
+        }
+        if self.side_by_side
+            && let Some(side_by_side) = side_by_side_diff(&text, area.width)
+        {
+            text = side_by_side;
+        }
+
+        if let Some((row, extend)) = self.pending_click.take() {
+            let line = line_at_row(&text, row, area.width, self.wrap);
+            if extend && self.cursor.is_some() && line.is_some() {
+                self.cursor = line;
+            } else if line == self.cursor && self.anchor == self.cursor {
+                // Clicking the only selected line again clears the selection
+                self.clear_cursor();
+            } else {
+                self.cursor = line;
+                self.anchor = line;
+            }
+        }
+
+        self.search_matches.clear();
+        if let Some(search) = self.search.as_deref() {
+            let search_style = Style::new().bg(self.highlight_color).bold().underlined();
+            for (index, line) in text.lines.iter_mut().enumerate() {
+                let ranges = search_ranges(&line_string(line), search);
+                if ranges.is_empty() {
+                    continue;
+                }
+                self.search_matches.push(index);
+                for range in ranges {
+                    *line = style_chars(std::mem::take(line), range, search_style);
+                }
+            }
+        }
+        if let Some(jump) = self.pending_search_jump.take()
+            && let Some(line) = self.next_search_match(jump)
+        {
+            self.search_line = Some(line);
+            self.scroll = row_of_line(&text, line, area.width, self.wrap);
+        }
+
+        self.selected_text = None;
+        if let Some(last_line) = text.lines.len().checked_sub(1) {
+            self.cursor = self.cursor.map(|cursor| cursor.min(last_line));
+            self.anchor = self.anchor.map(|anchor| anchor.min(last_line));
+        } else {
+            self.clear_cursor();
+        }
+        if let Some(selection) = self.selection() {
+            let lines = &mut text.lines[selection];
+            self.selected_text = Some(
+                lines
+                    .iter()
+                    .map(|line| {
+                        let plain: String = line
+                            .spans
+                            .iter()
+                            .map(|span| span.content.as_ref())
+                            .collect();
+                        strip_diff_marker(&plain).to_owned()
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n"),
+            );
+            for line in lines {
+                line.style = line.style.patch(CURSOR_STYLE);
+            }
+        }
+
+        let mut paragraph = Paragraph::new(text);
 
         if self.wrap {
             paragraph = paragraph.wrap(Wrap { trim: false });
         }
 
         self.height = area.height;
+        self.width = area.width;
         self.lines = paragraph.line_count(area.width) as u16;
+        self.longest_line = u16::try_from(paragraph.line_width()).unwrap_or(u16::MAX);
 
-        paragraph = paragraph.scroll((self.scroll.min(self.lines.saturating_sub(1)), 0));
+        let hscroll = if self.wrap {
+            0
+        } else {
+            self.hscroll
+                .min(self.longest_line.saturating_sub(self.width))
+        };
+        paragraph = paragraph.scroll((self.scroll.min(self.lines.saturating_sub(1)), hscroll));
 
         paragraph
     }
 
+    /// Changed characters of each line, reused while the content is unchanged
+    fn word_diff_ranges(&mut self, plain: Vec<String>) -> &[Option<Range<usize>>] {
+        if self
+            .word_diff_cache
+            .as_ref()
+            .is_none_or(|(cached, _)| *cached != plain)
+        {
+            let ranges = word_diff_ranges(&plain);
+            self.word_diff_cache = Some((plain, ranges));
+        }
+        self.word_diff_cache
+            .as_ref()
+            .map(|(_, ranges)| ranges.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Scroll position, to restore it later with [DetailsPanel::set_scroll]
+    pub fn get_scroll(&self) -> u16 {
+        self.scroll
+    }
+
+    /// Restore a scroll position. Unlike [DetailsPanel::scroll_to], it is not limited to the
+    /// current content, as the content it belongs to may not be rendered yet
+    pub fn set_scroll(&mut self, scroll: u16) {
+        self.scroll = scroll;
+    }
+
     pub fn scroll_to(&mut self, line_no: u16) {
         self.scroll = line_no.min(self.lines.saturating_sub(1))
     }
 
+    /// Content line marked by the cursor
+    pub fn cursor(&self) -> Option<usize> {
+        self.cursor
+    }
+
+    /// Mark a single content line, scrolling to keep it visible when lines don't wrap
+    pub fn select_line(&mut self, line: usize) {
+        self.cursor = Some(line);
+        self.anchor = Some(line);
+        let row = u16::try_from(line).unwrap_or(u16::MAX);
+        if row < self.scroll {
+            self.scroll = row;
+        } else if self.height > 0 && row >= self.scroll.saturating_add(self.height) {
+            self.scroll = row + 1 - self.height;
+        }
+    }
+
+    /// Open the prompt to search the content
+    pub fn start_search(&mut self) {
+        let mut textarea = TextArea::new(vec![self.search.clone().unwrap_or_default()]);
+        textarea.move_cursor(CursorMove::End);
+        self.search_textarea = Some(textarea);
+    }
+
+    /// Remove the search and its highlights, keeping the scroll position
+    pub fn clear_search(&mut self) {
+        self.search = None;
+        self.search_textarea = None;
+        self.search_matches.clear();
+        self.search_line = None;
+        self.pending_search_jump = None;
+    }
+
+    /// Content line of the match to jump to, wrapping around at the end of the content
+    fn next_search_match(&self, jump: SearchJump) -> Option<usize> {
+        let current = self.search_line;
+        match jump {
+            SearchJump::First => self.search_matches.first(),
+            SearchJump::Next => self
+                .search_matches
+                .iter()
+                .find(|line| current.is_none_or(|current| **line > current))
+                .or(self.search_matches.first()),
+            SearchJump::Previous => self
+                .search_matches
+                .iter()
+                .rev()
+                .find(|line| current.is_none_or(|current| **line < current))
+                .or(self.search_matches.last()),
+        }
+        .copied()
+    }
+
+    /// Handle a key while the search prompt is open
+    fn input_search(&mut self, key: KeyEvent) {
+        let Some(search_textarea) = self.search_textarea.as_mut() else {
+            return;
+        };
+        match key.code {
+            // The prompt stays open to jump between matches, closing it clears the search
+            KeyCode::Enter | KeyCode::Down if self.search.is_some() => {
+                self.pending_search_jump = Some(SearchJump::Next)
+            }
+            KeyCode::Up if self.search.is_some() => {
+                self.pending_search_jump = Some(SearchJump::Previous)
+            }
+            KeyCode::Enter | KeyCode::Esc => self.clear_search(),
+            _ => {
+                // Search as you type
+                if search_textarea.input(key) {
+                    let search = search_textarea.lines().join("");
+                    self.search = (!search.is_empty()).then_some(search);
+                    self.search_line = None;
+                    self.pending_search_jump = Some(SearchJump::First);
+                }
+            }
+        }
+    }
+
+    /// Remove the line selection, e.g. when the content changes
+    pub fn clear_cursor(&mut self) {
+        self.cursor = None;
+        self.anchor = None;
+        self.pending_click = None;
+        self.selected_text = None;
+    }
+
+    /// Range of selected content lines
+    fn selection(&self) -> Option<RangeInclusive<usize>> {
+        let (cursor, anchor) = (self.cursor?, self.anchor?);
+        Some(cursor.min(anchor)..=cursor.max(anchor))
+    }
+
+    /// Move the cursor, extending the selection from the anchor
+    fn extend_selection(&mut self, lines: isize) {
+        self.cursor = self
+            .cursor
+            .map(|cursor| cursor.saturating_add_signed(lines));
+    }
+
+    /// Copy the selected lines to the clipboard
+    fn copy_selection(&mut self) {
+        if let Some(selected_text) = &self.selected_text {
+            self.copied_text = Some(("The selection", selected_text.clone()));
+        }
+    }
+
+    /// Copy the text requested with `Y`, confirming large copies and showing clipboard errors.
+    /// Components using the panel call this when updating, as the whole content is only known
+    /// once rendered
+    pub fn take_copy_action(&mut self) -> Result<Option<ComponentAction>> {
+        self.copied_text
+            .take()
+            .map(|(what, text)| copy_action(what, text))
+            .transpose()
+    }
+
     pub fn scroll(&mut self, scroll: isize) {
         self.scroll_to(self.scroll.saturating_add_signed(scroll as i16))
     }
 
+    /// Scroll long lines sideways. Only lines which don't wrap can be scrolled
+    pub fn scroll_horizontal(&mut self, columns: isize) {
+        if self.wrap {
+            return;
+        }
+        self.hscroll = self
+            .hscroll
+            .saturating_add_signed(columns as i16)
+            .min(self.longest_line.saturating_sub(self.width));
+    }
+
     pub fn handle_event(&mut self, details_panel_event: DetailsPanelEvent) {
         match details_panel_event {
             DetailsPanelEvent::ScrollDown => self.scroll(1),
@@ -161,13 +576,27 @@ impl DetailsPanel {
             }
             DetailsPanelEvent::ScrollDownPage => self.scroll(self.height as isize),
             DetailsPanelEvent::ScrollUpPage => self.scroll((self.height as isize).saturating_neg()),
+            DetailsPanelEvent::ScrollLeft => {
+                self.scroll_horizontal(-(HORIZONTAL_SCROLL_COLUMNS as isize))
+            }
+            DetailsPanelEvent::ScrollRight => {
+                self.scroll_horizontal(HORIZONTAL_SCROLL_COLUMNS as isize)
+            }
             DetailsPanelEvent::ToggleWrap => self.wrap = !self.wrap,
+            DetailsPanelEvent::ToggleReflow => self.reflow = !self.reflow,
+            DetailsPanelEvent::ToggleSideBySide => self.side_by_side = !self.side_by_side,
         }
     }
 
     /// Handle input. Returns bool of if event was handled
     pub fn input(&mut self, key: KeyEvent) -> bool {
+        if self.search_textarea.is_some() {
+            self.input_search(key);
+            return true;
+        }
+
         match key.code {
+            KeyCode::Char('#') => self.start_search(),
             KeyCode::Char('e') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 self.handle_event(DetailsPanelEvent::ScrollDown)
             }
@@ -186,7 +615,23 @@ impl DetailsPanel {
             KeyCode::Char('b') if key.modifiers.contains(KeyModifiers::CONTROL) => {
                 self.handle_event(DetailsPanelEvent::ScrollUpPage)
             }
+            KeyCode::Char('w') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.handle_event(DetailsPanelEvent::ToggleReflow)
+            }
             KeyCode::Char('W') => self.handle_event(DetailsPanelEvent::ToggleWrap),
+            KeyCode::Char('<') => self.handle_event(DetailsPanelEvent::ScrollLeft),
+            KeyCode::Char('>') => self.handle_event(DetailsPanelEvent::ScrollRight),
+            KeyCode::Char('V') => self.handle_event(DetailsPanelEvent::ToggleSideBySide),
+            KeyCode::Down
+                if key.modifiers.contains(KeyModifiers::SHIFT) && self.cursor.is_some() =>
+            {
+                self.extend_selection(1)
+            }
+            KeyCode::Up if key.modifiers.contains(KeyModifiers::SHIFT) && self.cursor.is_some() => {
+                self.extend_selection(-1)
+            }
+            KeyCode::Char('Y') if self.cursor.is_some() => self.copy_selection(),
+            KeyCode::Char('Y') => self.pending_copy_all = true,
             _ => return false,
         };
 
@@ -204,6 +649,15 @@ impl DetailsPanel {
         }
         trace!("mouse {:?} inside  rect {:?}", &mouse, &self.panel_rect);
         match mouse.kind {
+            // Terminals send shift+wheel as a vertical scroll with the shift modifier
+            MouseEventKind::ScrollLeft => self.handle_event(DetailsPanelEvent::ScrollLeft),
+            MouseEventKind::ScrollRight => self.handle_event(DetailsPanelEvent::ScrollRight),
+            MouseEventKind::ScrollUp if mouse.modifiers.contains(KeyModifiers::SHIFT) => {
+                self.handle_event(DetailsPanelEvent::ScrollLeft)
+            }
+            MouseEventKind::ScrollDown if mouse.modifiers.contains(KeyModifiers::SHIFT) => {
+                self.handle_event(DetailsPanelEvent::ScrollRight)
+            }
             MouseEventKind::ScrollUp => {
                 self.handle_event(DetailsPanelEvent::ScrollUp);
                 self.handle_event(DetailsPanelEvent::ScrollUp);
@@ -214,8 +668,717 @@ impl DetailsPanel {
                 self.handle_event(DetailsPanelEvent::ScrollDown);
                 self.handle_event(DetailsPanelEvent::ScrollDown);
             }
+            MouseEventKind::Down(MouseButton::Left) => {
+                // Ignore clicks on the top and bottom border
+                let content_top = self.panel_rect.y + 1;
+                if mouse.row < content_top || mouse.row >= content_top + self.height {
+                    return false;
+                }
+                let scroll = self.scroll.min(self.lines.saturating_sub(1));
+                self.pending_click = Some((
+                    scroll + mouse.row - content_top,
+                    mouse.modifiers.contains(KeyModifiers::SHIFT),
+                ));
+            }
             _ => return false,
         }
         true
     }
 }
+
+/// Line numbers of the color words diff format, e.g. `   12   14: `
+static LINE_NUMBERS_REGEX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^ *(\d+ +\d*|\d* +\d+): ").unwrap());
+
+/// Remove the line numbers of color words diffs, or the `+`/`-`/` ` marker of git diffs
+fn strip_diff_marker(line: &str) -> &str {
+    if let Some(line_numbers) = LINE_NUMBERS_REGEX.find(line) {
+        return &line[line_numbers.end()..];
+    }
+    if line.starts_with("+++ ") || line.starts_with("--- ") {
+        return line;
+    }
+    line.strip_prefix(['+', '-', ' ']).unwrap_or(line)
+}
+
+/// Number of rows a content line is rendered on
+fn line_height(line: &Line<'_>, width: u16, wrap: bool) -> u16 {
+    if wrap {
+        Paragraph::new(line.clone())
+            .wrap(Wrap { trim: false })
+            .line_count(width)
+            .max(1) as u16
+    } else {
+        1
+    }
+}
+
+/// Find the content line rendered at `row`, counting rows from the top of the content.
+/// Returns `None` when the row is past the end of the content.
+fn line_at_row(text: &Text<'_>, row: u16, width: u16, wrap: bool) -> Option<usize> {
+    let mut line_top = 0;
+    for (index, line) in text.lines.iter().enumerate() {
+        let height = line_height(line, width, wrap);
+        if row < line_top + height {
+            return Some(index);
+        }
+        line_top += height;
+    }
+    None
+}
+
+/// First row a content line is rendered on, counting rows from the top of the content
+fn row_of_line(text: &Text<'_>, line: usize, width: u16, wrap: bool) -> u16 {
+    text.lines
+        .iter()
+        .take(line)
+        .map(|line| line_height(line, width, wrap))
+        .fold(0, u16::saturating_add)
+}
+
+/// Whether a git diff line is a removed (`-`) or added (`+`) line, excluding file headers
+fn diff_line_marker(line: &str) -> Option<char> {
+    if line.starts_with("--- ") || line.starts_with("+++ ") {
+        return None;
+    }
+    line.chars()
+        .next()
+        .filter(|marker| matches!(marker, '-' | '+'))
+}
+
+/// Range of characters which differ between two lines, after their common prefix and suffix.
+/// The range is widened to whole words. Returns `None` when the lines have nothing in common,
+/// as highlighting everything would not help.
+fn changed_chars(line: &[char], other: &[char]) -> Option<Range<usize>> {
+    let is_word = |c: &char| c.is_alphanumeric() || *c == '_';
+
+    let mut prefix = line.iter().zip(other).take_while(|(a, b)| a == b).count();
+    let max_suffix = line.len().min(other.len()) - prefix;
+    let suffix = line
+        .iter()
+        .rev()
+        .zip(other.iter().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let mut end = line.len() - suffix;
+
+    while prefix > 0 && prefix < line.len() && is_word(&line[prefix - 1]) && is_word(&line[prefix])
+    {
+        prefix -= 1;
+    }
+    while end > 0 && end < line.len() && is_word(&line[end - 1]) && is_word(&line[end]) {
+        end += 1;
+    }
+
+    // Leading whitespace doesn't count as being in common
+    let indent = line.iter().take_while(|c| c.is_whitespace()).count();
+    if prefix <= indent && end == line.len() || prefix >= end {
+        return None;
+    }
+    Some(prefix..end)
+}
+
+/// Replace the red and green jj uses for removed and added text with the configured colors
+fn recolor_diff<'a>(mut text: Text<'a>, theme_colors: &ThemeColors) -> Text<'a> {
+    if theme_colors.diff_added.is_none() && theme_colors.diff_removed.is_none() {
+        return text;
+    }
+    for span in text.lines.iter_mut().flat_map(|line| line.spans.iter_mut()) {
+        let color = match span.style.fg {
+            Some(Color::Green | Color::LightGreen) => theme_colors.diff_added,
+            Some(Color::Red | Color::LightRed) => theme_colors.diff_removed,
+            _ => None,
+        };
+        if let Some(color) = color {
+            span.style.fg = Some(color);
+        }
+    }
+    text
+}
+
+/// Syntax highlight the added and removed lines of git diffs, based on the extension of the
+/// file named in the `diff --git` header above them
+fn highlight_diff_syntax(text: Text<'_>) -> Text<'_> {
+    let mut language = None;
+    let lines = text
+        .lines
+        .into_iter()
+        .enumerate()
+        .map(|(index, line)| {
+            if index >= MAX_SYNTAX_HIGHLIGHT_LINES {
+                return line;
+            }
+            let plain = line_string(&line);
+            if let Some(paths) = plain.strip_prefix("diff --git a/") {
+                language = paths
+                    .rsplit_once(" b/")
+                    .and_then(|(_, path)| Language::for_path(path));
+                return line;
+            }
+            match (language, diff_line_marker(&plain)) {
+                (Some(language), Some(_)) => highlight_line(line, 1, language),
+                _ => line,
+            }
+        })
+        .collect();
+    Text { lines, ..text }
+}
+
+/// Find the changed characters of modified lines of git diffs, by index of the plain line.
+///
+/// Each block of removed lines directly followed by added lines is paired line by line, and
+/// the part of each line between the common prefix and suffix is changed. Other content, like
+/// color words diffs, has no changed characters.
+fn word_diff_ranges(plain: &[String]) -> Vec<Option<Range<usize>>> {
+    let markers: Vec<Option<char>> = plain.iter().map(|line| diff_line_marker(line)).collect();
+    let marker = |index: usize| markers.get(index).copied().flatten();
+
+    let mut ranges = vec![None; plain.len()];
+    let mut index = 0;
+    while index < plain.len() {
+        let removed_start = index;
+        while marker(index) == Some('-') {
+            index += 1;
+        }
+        let added_start = index;
+        while added_start > removed_start && marker(index) == Some('+') {
+            index += 1;
+        }
+        if index == removed_start {
+            index += 1;
+            continue;
+        }
+
+        for (removed, added) in (removed_start..added_start).zip(added_start..index) {
+            // Skip the markers when comparing
+            let old: Vec<char> = plain[removed].chars().skip(1).collect();
+            let new: Vec<char> = plain[added].chars().skip(1).collect();
+            ranges[removed] = changed_chars(&old, &new).map(|range| range.start + 1..range.end + 1);
+            ranges[added] = changed_chars(&new, &old).map(|range| range.start + 1..range.end + 1);
+        }
+    }
+    ranges
+}
+
+/// Show the changed characters found by [word_diff_ranges] reversed
+fn highlight_word_diff<'a>(text: Text<'a>, ranges: &[Option<Range<usize>>]) -> Text<'a> {
+    let lines = text
+        .lines
+        .into_iter()
+        .enumerate()
+        .map(|(index, line)| match ranges.get(index).cloned().flatten() {
+            Some(range) => style_chars(line, range, Style::new().add_modifier(Modifier::REVERSED)),
+            None => line,
+        })
+        .collect();
+    Text { lines, ..text }
+}
+
+/// Cut or pad a line with spaces to exactly `width` columns
+fn fit_line<'a>(line: Option<&Line<'a>>, width: usize) -> Vec<Span<'a>> {
+    let mut spans = vec![];
+    let mut used = 0;
+    for span in line.iter().flat_map(|line| line.spans.iter()) {
+        if used + span.width() <= width {
+            used += span.width();
+            spans.push(span.clone());
+            continue;
+        }
+
+        let mut content = String::new();
+        for c in span.content.chars() {
+            let c_width = Span::raw(c.to_string()).width();
+            if used + c_width > width {
+                break;
+            }
+            used += c_width;
+            content.push(c);
+        }
+        spans.push(Span::styled(content, span.style));
+        break;
+    }
+    spans.push(Span::raw(" ".repeat(width - used)));
+    spans
+}
+
+/// Lay out a git diff in two columns, removed lines on the left and added lines on the right.
+///
+/// Context lines are shown in both columns, and blocks of removed lines directly followed by
+/// added lines are aligned line by line. Headers span the whole width. Returns `None` when the
+/// content is not a git diff or `width` is too narrow to split.
+fn side_by_side_diff<'a>(text: &Text<'a>, width: u16) -> Option<Text<'a>> {
+    let plain: Vec<String> = text.lines.iter().map(line_string).collect();
+    if (width as usize) < MIN_SIDE_BY_SIDE_WIDTH
+        || !plain.iter().any(|line| line.starts_with("@@ "))
+    {
+        return None;
+    }
+    let column_width = (width as usize - SIDE_BY_SIDE_SEPARATOR.chars().count()) / 2;
+    let row = |left: Option<&Line<'a>>, right: Option<&Line<'a>>| {
+        let mut spans = fit_line(left, column_width);
+        spans.push(Span::raw(SIDE_BY_SIDE_SEPARATOR).fg(Color::DarkGray));
+        spans.extend(fit_line(right, column_width));
+        Line::from(spans)
+    };
+
+    let mut lines = vec![];
+    let mut in_hunk = false;
+    let mut index = 0;
+    while index < text.lines.len() {
+        let line = &text.lines[index];
+        if !in_hunk || !plain[index].starts_with([' ', '-', '+']) {
+            in_hunk = plain[index].starts_with("@@ ");
+            lines.push(line.clone());
+            index += 1;
+            continue;
+        }
+        if plain[index].starts_with(' ') {
+            lines.push(row(Some(line), Some(line)));
+            index += 1;
+            continue;
+        }
+
+        let removed_start = index;
+        while plain.get(index).is_some_and(|line| line.starts_with('-')) {
+            index += 1;
+        }
+        let added_start = index;
+        while plain.get(index).is_some_and(|line| line.starts_with('+')) {
+            index += 1;
+        }
+        let removed = &text.lines[removed_start..added_start];
+        let added = &text.lines[added_start..index];
+        lines.extend(removed.iter().zip_longest(added).map(|pair| match pair {
+            EitherOrBoth::Both(left, right) => row(Some(left), Some(right)),
+            EitherOrBoth::Left(left) => row(Some(left), None),
+            EitherOrBoth::Right(right) => row(None, Some(right)),
+        }));
+    }
+
+    Some(Text::from(lines))
+}
+
+/// Indentation used by `jj show` for the description body
+const DESCRIPTION_INDENT: &str = "    ";
+
+/// Replace tabs by spaces up to the next tab stop.
+///
+/// ratatui doesn't handle tabs, so tab-indented code would otherwise be
+/// misaligned. Columns are counted across the spans of a line.
+fn expand_tabs(text: Text<'_>, tab_width: usize) -> Text<'_> {
+    let lines = text
+        .lines
+        .into_iter()
+        .map(|line| {
+            if !line.spans.iter().any(|span| span.content.contains('\t')) {
+                return line;
+            }
+
+            let mut x = 0;
+            let spans = line
+                .spans
+                .into_iter()
+                .map(|span| {
+                    if !span.content.contains('\t') {
+                        x += span.width();
+                        return span;
+                    }
+
+                    let mut content = String::new();
+                    for (i, segment) in span.content.split('\t').enumerate() {
+                        if i > 0 {
+                            let spaces = tab_width - x % tab_width;
+                            content.push_str(&" ".repeat(spaces));
+                            x += spaces;
+                        }
+                        content.push_str(segment);
+                        x += Span::raw(segment).width();
+                    }
+                    Span::styled(content, span.style)
+                })
+                .collect();
+            Line { spans, ..line }
+        })
+        .collect();
+    Text { lines, ..text }
+}
+
+/// Rewrap the description of `jj show` output to fit `width`.
+///
+/// Only the indented description block following the commit header is touched.
+/// Consecutive prose lines are joined and refilled, while blank lines,
+/// fenced/indented code blocks and list items keep their line breaks.
+/// Content which is not `jj show` output is returned unchanged.
+fn reflow_description<'a>(text: &Text<'a>, width: u16) -> Text<'a> {
+    // The log tab shows the diff stat and a blank line above the header
+    let Some(header_start) = text.lines.iter().take(3).position(|line| {
+        let plain = line_string(line);
+        plain.starts_with("Commit ID:") || plain.starts_with("Change ID:")
+    }) else {
+        return text.clone();
+    };
+
+    let mut lines: Vec<Line<'a>> = vec![];
+    // Words of the paragraph currently being collected
+    let mut paragraph: Vec<(String, Style)> = vec![];
+    let mut in_header = true;
+    let mut in_body = false;
+    let mut in_fence = false;
+
+    for (index, line) in text.lines.iter().enumerate() {
+        if index < header_start {
+            lines.push(line.clone());
+            continue;
+        }
+        let plain: String = line
+            .spans
+            .iter()
+            .map(|span| span.content.as_ref())
+            .collect();
+
+        if in_header {
+            if plain.trim().is_empty() {
+                in_header = false;
+                in_body = true;
+            }
+            lines.push(line.clone());
+            continue;
+        }
+
+        if in_body && !plain.trim().is_empty() && !plain.starts_with(DESCRIPTION_INDENT) {
+            // First line after the description
+            in_body = false;
+        }
+        if !in_body {
+            fill_paragraph(&mut lines, &mut paragraph, width);
+            lines.push(line.clone());
+            continue;
+        }
+
+        let body = plain.strip_prefix(DESCRIPTION_INDENT).unwrap_or(&plain);
+        let is_fence = body.trim_start().starts_with("```");
+        let is_code = body.starts_with(char::is_whitespace);
+        let is_list_item = body.starts_with("- ")
+            || body.starts_with("* ")
+            || body
+                .split_once(". ")
+                .is_some_and(|(number, _)| number.parse::<u32>().is_ok());
+
+        if plain.trim().is_empty() || is_fence || in_fence || is_code {
+            fill_paragraph(&mut lines, &mut paragraph, width);
+            lines.push(line.clone());
+            if is_fence {
+                in_fence = !in_fence;
+            }
+            continue;
+        }
+
+        if is_list_item {
+            fill_paragraph(&mut lines, &mut paragraph, width);
+        }
+        for span in line.spans.iter() {
+            paragraph.extend(
+                span.content
+                    .split_whitespace()
+                    .map(|word| (word.to_owned(), span.style)),
+            );
+        }
+    }
+    fill_paragraph(&mut lines, &mut paragraph, width);
+
+    Text {
+        lines,
+        style: text.style,
+        alignment: text.alignment,
+    }
+}
+
+/// Greedily fill the collected words into indented lines no wider than `width`
+fn fill_paragraph<'a>(lines: &mut Vec<Line<'a>>, words: &mut Vec<(String, Style)>, width: u16) {
+    let width = (width as usize).max(DESCRIPTION_INDENT.len() + 1);
+    let mut current = Line::from(DESCRIPTION_INDENT);
+    let mut current_width = DESCRIPTION_INDENT.len();
+
+    for (word, style) in words.drain(..) {
+        let word_width = word.chars().count();
+        let is_line_start = current_width == DESCRIPTION_INDENT.len();
+        if !is_line_start && current_width + 1 + word_width > width {
+            lines.push(std::mem::replace(
+                &mut current,
+                Line::from(DESCRIPTION_INDENT),
+            ));
+            current_width = DESCRIPTION_INDENT.len();
+        } else if !is_line_start {
+            current.spans.push(Span::raw(" "));
+            current_width += 1;
+        }
+        current_width += word_width;
+        current.spans.push(Span::styled(word, style));
+    }
+
+    if current_width > DESCRIPTION_INDENT.len() {
+        lines.push(current);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{commander::tests::TestRepo, env::DiffFormat};
+
+    fn to_strings(text: &Text) -> Vec<String> {
+        text.lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn expand_tabs_aligns_to_tab_stops() {
+        let text = Text::from(vec![
+            Line::from("\tfoo\tbar"),
+            Line::from(vec![Span::raw("+ab"), Span::raw("\tc")]),
+            Line::from("no tabs"),
+        ]);
+
+        assert_eq!(
+            to_strings(&expand_tabs(text, 4)),
+            ["    foo bar", "+ab c", "no tabs"]
+        );
+    }
+
+    #[test]
+    fn line_at_row_accounts_for_wrapped_lines() {
+        let text = Text::from(vec![
+            Line::from("short"),
+            Line::from("a line long enough to wrap"),
+            Line::from("last"),
+        ]);
+
+        assert_eq!(line_at_row(&text, 0, 15, true), Some(0));
+        assert_eq!(line_at_row(&text, 1, 15, true), Some(1));
+        assert_eq!(line_at_row(&text, 2, 15, true), Some(1));
+        assert_eq!(line_at_row(&text, 3, 15, true), Some(2));
+        assert_eq!(line_at_row(&text, 4, 15, true), None);
+        assert_eq!(line_at_row(&text, 2, 15, false), Some(2));
+    }
+
+    #[test]
+    fn row_of_line_accounts_for_wrapped_lines() {
+        let text = Text::from(vec![
+            Line::from("short"),
+            Line::from("a line long enough to wrap"),
+            Line::from("last"),
+        ]);
+
+        assert_eq!(row_of_line(&text, 0, 15, true), 0);
+        assert_eq!(row_of_line(&text, 1, 15, true), 1);
+        assert_eq!(row_of_line(&text, 2, 15, true), 3);
+        assert_eq!(row_of_line(&text, 2, 15, false), 2);
+    }
+
+    #[test]
+    fn search_scrolls_between_matches() {
+        let mut panel = DetailsPanel::new();
+        let text = || {
+            Text::from(vec![
+                Line::from("fn main() {"),
+                Line::from("    let a = 1;"),
+                Line::from("    let b = 2;"),
+                Line::from("}"),
+            ])
+        };
+        let area = Rect::new(0, 0, 40, 2);
+
+        panel.start_search();
+        for c in "LET".chars() {
+            panel.input(KeyEvent::from(KeyCode::Char(c)));
+        }
+        panel.render(text(), area);
+        assert_eq!(panel.search_matches, vec![1, 2]);
+        assert_eq!(panel.scroll, 1);
+
+        panel.input(KeyEvent::from(KeyCode::Enter));
+        panel.render(text(), area);
+        assert_eq!(panel.scroll, 2);
+
+        // Wraps around to the first match
+        panel.input(KeyEvent::from(KeyCode::Down));
+        panel.render(text(), area);
+        assert_eq!(panel.scroll, 1);
+
+        panel.input(KeyEvent::from(KeyCode::Up));
+        panel.render(text(), area);
+        assert_eq!(panel.scroll, 2);
+
+        // Closing the prompt clears the search, keeping the scroll position
+        panel.input(KeyEvent::from(KeyCode::Esc));
+        panel.render(text(), area);
+        assert!(panel.search_matches.is_empty());
+        assert_eq!(panel.scroll, 2);
+    }
+
+    #[test]
+    fn strip_diff_marker_removes_markers() {
+        assert_eq!(strip_diff_marker("   12   14: let a = 1;"), "let a = 1;");
+        assert_eq!(strip_diff_marker("        14: let a = 1;"), "let a = 1;");
+        assert_eq!(strip_diff_marker("+let a = 1;"), "let a = 1;");
+        assert_eq!(strip_diff_marker("- let a = 1;"), " let a = 1;");
+        assert_eq!(strip_diff_marker("+++ b/src/main.rs"), "+++ b/src/main.rs");
+        assert_eq!(
+            strip_diff_marker("Modified regular file a:"),
+            "Modified regular file a:"
+        );
+    }
+
+    #[test]
+    fn highlight_word_diff_reverses_changed_words() {
+        let text = Text::from(vec![
+            Line::from("--- a/src/main.rs"),
+            Line::from("+++ b/src/main.rs"),
+            Line::from(vec![Span::raw("-let "), Span::raw("count = 1;")]),
+            Line::from("+let total = 1;"),
+            Line::from(" unchanged"),
+            Line::from("-removed only"),
+            Line::from("+completely different"),
+        ]);
+
+        let plain: Vec<String> = text.lines.iter().map(line_string).collect();
+        let highlighted = highlight_word_diff(text.clone(), &word_diff_ranges(&plain));
+        let reversed: Vec<String> = highlighted
+            .lines
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .filter(|span| span.style.add_modifier.contains(Modifier::REVERSED))
+                    .map(|span| span.content.as_ref())
+                    .collect()
+            })
+            .collect();
+
+        assert_eq!(reversed, ["", "", "count", "total", "", "", ""]);
+        assert_eq!(to_strings(&highlighted), to_strings(&text));
+    }
+
+    #[test]
+    fn side_by_side_diff_aligns_hunks() {
+        let text = Text::from(vec![
+            Line::from("diff --git a/a b/a"),
+            Line::from("@@ -1,3 +1,2 @@"),
+            Line::from(" same"),
+            Line::from("-old 1"),
+            Line::from("-old 2"),
+            Line::from("+new 1"),
+        ]);
+
+        let lines = to_strings(&side_by_side_diff(&text, 83).unwrap());
+        let row = |left: &str, right: &str| format!("{left:<40} │ {right:<40}");
+        assert_eq!(
+            lines,
+            [
+                "diff --git a/a b/a".to_owned(),
+                "@@ -1,3 +1,2 @@".to_owned(),
+                row(" same", " same"),
+                row("-old 1", "+new 1"),
+                row("-old 2", ""),
+            ]
+        );
+
+        assert!(side_by_side_diff(&text, 40).is_none());
+        assert!(side_by_side_diff(&Text::from("Commit ID: abc"), 83).is_none());
+    }
+
+    #[test]
+    fn reflow_description_joins_and_wraps_paragraphs() {
+        let text = Text::from(
+            "Commit ID: abc\n\n    First line of a\n    paragraph here\n\n    Second\nModified regular file a:",
+        );
+
+        assert_eq!(
+            to_strings(&reflow_description(&text, 20)),
+            [
+                "Commit ID: abc",
+                "",
+                "    First line of a",
+                "    paragraph here",
+                "",
+                "    Second",
+                "Modified regular file a:",
+            ]
+        );
+        assert_eq!(
+            to_strings(&reflow_description(&text, 80)),
+            [
+                "Commit ID: abc",
+                "",
+                "    First line of a paragraph here",
+                "",
+                "    Second",
+                "Modified regular file a:",
+            ]
+        );
+    }
+
+    #[test]
+    fn reflow_description_keeps_code_and_lists() {
+        let text = Text::from(
+            "Commit ID: abc\n\n    ```\n    let a = 1;\n    let b = 2;\n    ```\n        indented\n        code\n    - item one\n    - item two",
+        );
+
+        assert_eq!(
+            to_strings(&reflow_description(&text, 80)),
+            [
+                "Commit ID: abc",
+                "",
+                "    ```",
+                "    let a = 1;",
+                "    let b = 2;",
+                "    ```",
+                "        indented",
+                "        code",
+                "    - item one",
+                "    - item two",
+            ]
+        );
+    }
+
+    #[test]
+    fn reflow_description_of_show_output_below_stat() -> Result<()> {
+        let test_repo = TestRepo::new()?;
+        test_repo
+            .commander
+            .run_describe("@", "First line of a\nparagraph here")?;
+        let head = test_repo.commander.get_current_head()?;
+        let show =
+            test_repo
+                .commander
+                .get_commit_show(&head.commit_id, &DiffFormat::ColorWords, false)?;
+
+        let mut text = Text::from(show);
+        text.lines.splice(
+            0..0,
+            [Line::from("0 files changed, +0 -0"), Line::default()],
+        );
+        let reflowed = to_strings(&reflow_description(&text, 80));
+
+        assert_eq!(reflowed[0], "0 files changed, +0 -0");
+        assert!(reflowed[2].starts_with("Change ID:"));
+        assert!(reflowed.contains(&"    First line of a paragraph here".to_owned()));
+
+        Ok(())
+    }
+
+    #[test]
+    fn reflow_description_ignores_other_content() {
+        let text = Text::from("diff --git a/a b/a\n\n    short\n    lines");
+
+        assert_eq!(reflow_description(&text, 80), text);
+    }
+}
@@ -0,0 +1,125 @@
+/*! The file picker panel lists the files of a change with a checkbox each,
+to pick which files an operation applies to. */
+
+use ratatui::{
+    crossterm::event::{KeyCode, KeyEvent},
+    layout::Rect,
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, List, ListState},
+};
+
+use crate::commander::files::File;
+
+/// List of files which can be checked, used by popups operating on some files of a change
+pub struct FilePickerPanel {
+    files: Vec<File>,
+    checked: Vec<bool>,
+    list_state: ListState,
+}
+
+impl FilePickerPanel {
+    /// Create the panel with the files at `checked_paths` checked, selecting the first of them
+    pub fn new(files: Vec<File>, checked_paths: &[String]) -> Self {
+        let checked = files
+            .iter()
+            .map(|file| {
+                file.target_path()
+                    .is_some_and(|path| checked_paths.iter().any(|checked| checked == path))
+            })
+            .collect();
+        let selected = files
+            .iter()
+            .position(|file| {
+                file.target_path()
+                    .is_some_and(|path| checked_paths.first().is_some_and(|first| first == path))
+            })
+            .unwrap_or(0);
+
+        Self {
+            files,
+            checked,
+            list_state: ListState::default().with_selected(Some(selected)),
+        }
+    }
+
+    /// Index of the highlighted file
+    pub fn selected(&self) -> usize {
+        self.list_state.selected().unwrap_or(0)
+    }
+
+    pub fn selected_file(&self) -> Option<&File> {
+        self.files.get(self.selected())
+    }
+
+    fn select(&mut self, index: usize) {
+        let index = index.min(self.files.len().saturating_sub(1));
+        self.list_state.select(Some(index));
+    }
+
+    /// Paths of the checked files
+    pub fn checked_paths(&self) -> Vec<&str> {
+        self.files
+            .iter()
+            .zip(&self.checked)
+            .filter(|(_, checked)| **checked)
+            .filter_map(|(file, _)| file.target_path())
+            .collect()
+    }
+
+    pub fn draw(
+        &mut self,
+        f: &mut ratatui::prelude::Frame<'_>,
+        area: Rect,
+        title: &str,
+        border_style: Style,
+        highlight_color: Color,
+    ) {
+        let lines: Vec<Line> = self
+            .files
+            .iter()
+            .zip(&self.checked)
+            .map(|(file, checked)| {
+                let color = file
+                    .diff_type
+                    .as_ref()
+                    .map_or(Color::default(), |diff_type| diff_type.color());
+                Line::from(vec![
+                    Span::raw(if *checked { "[x] " } else { "[ ] " }),
+                    Span::raw(file.line.clone()).fg(color),
+                ])
+            })
+            .collect();
+        let list = List::new(lines)
+            .block(
+                Block::bordered()
+                    .title(format!(" {title} "))
+                    .border_type(BorderType::Rounded)
+                    .border_style(border_style),
+            )
+            .scroll_padding(3)
+            .highlight_style(Style::default().bg(highlight_color));
+        f.render_stateful_widget(list, area, &mut self.list_state);
+    }
+
+    /// Select with j/k, toggle the selected file with Space and all files with a.
+    /// Returns whether the key was handled
+    pub fn input(&mut self, key: KeyEvent) -> bool {
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => self.select(self.selected() + 1),
+            KeyCode::Char('k') | KeyCode::Up => self.select(self.selected().saturating_sub(1)),
+            KeyCode::Char(' ') => {
+                let selected = self.selected();
+                if let Some(checked) = self.checked.get_mut(selected) {
+                    *checked = !*checked;
+                }
+            }
+            KeyCode::Char('a') => {
+                let check = !self.checked.iter().all(|checked| *checked);
+                self.checked.fill(check);
+            }
+            _ => return false,
+        }
+        true
+    }
+}
@@ -1,6 +1,11 @@
 /*! The log panel shows the list of changes on the left side of the
 log tab. */
 
+use std::{
+    collections::{HashMap, HashSet},
+    time::{Duration, Instant},
+};
+
 use ansi_to_tui::IntoText;
 use anyhow::Result;
 use ratatui::{
@@ -14,13 +19,15 @@ use ratatui::{
 use crate::{
     commander::{
         CommandError, Commander,
-        log::{Head, LogOutput},
+        ids::CommitId,
+        log::{Head, LogOutput, SignatureStatus},
     },
-    env::Config,
+    env::{Config, MouseScroll},
     keybinds::{LogTabEvent, LogTabKeybinds},
     ui::Component,
     ui::ComponentAction,
     ui::ComponentInputResult,
+    ui::utils::{line_string, position_indicator, search_ranges, signature_span, style_chars},
 };
 
 /**
@@ -47,12 +54,47 @@ pub struct LogPanel<'a> {
     /// The revision set to show in the log
     pub log_revset: Option<String>,
 
+    /// Only show changes touching this path, relative to the repo root
+    pub log_path: Option<String>,
+
+    /// Show a single line per change instead of two
+    pub compact: bool,
+
+    /// Draw the commit graph, or list the changes flat
+    pub graph: bool,
+
+    /// Number of changes listed, more are loaded when scrolling to the end. `None` lists all
+    limit: Option<usize>,
+
+    /// Revset widened to show a change jumped to, which the log revset or path hides.
+    /// Used until the filter changes or the log is refreshed
+    revealed: Option<String>,
+
+    /// The limit is lifted to show the change jumped to, as it is older than the loaded ones
+    reveal_unlimited: bool,
+
     /// Currently selected change
     pub head: Head,
 
+    /// Changes marked as the parents of the next merge or duplicate, in the order they were marked
+    pub marked: Vec<Head>,
+
+    /// Text searched for in the log. Changes with a matching line are highlighted
+    pub search: Option<String>,
+
+    /// Signature status of the signed commits in the log, shown next to the graph
+    signatures: HashMap<CommitId, SignatureStatus>,
+
     /// Rect used last time draw was called. Can be used to check if mouse clicks
     panel_rect: Rect,
 
+    /// Time and change of the last click, to detect double clicks
+    last_click: Option<(Instant, Head)>,
+
+    /// The list was scrolled away from the selection with the mouse wheel, with
+    /// `lazyjj.mouse-scroll = "list"`. The selection is revealed again when it moves
+    free_scroll: bool,
+
     config: Config,
 }
 
@@ -91,10 +133,16 @@ fn get_head_index(head: &Head, log_output: &Result<LogOutput, CommandError>) ->
 impl<'a> LogPanel<'a> {
     pub fn new(commander: &mut Commander) -> Result<Self> {
         let log_revset = commander.env.default_revset.clone();
-        let log_output = commander.get_log(&log_revset);
+        let compact = commander.env.config.log_compact();
+        let graph = commander.env.config.log_graph();
+        let limit = commander.env.config.log_limit();
+        let log_output = commander.get_log(&log_revset, compact, graph, limit);
         let head = commander.get_current_head()?;
 
         let log_list_state = ListState::default().with_selected(get_head_index(&head, &log_output));
+        let signatures = commander
+            .get_signed_commits(&log_revset, limit)
+            .unwrap_or_default();
 
         let mut keybinds = LogTabKeybinds::default();
         if let Some(new_keybinds) = commander
@@ -121,11 +169,24 @@ impl<'a> LogPanel<'a> {
             log_rect: Rect::ZERO,
 
             log_revset,
+            log_path: None,
+            compact,
+            graph,
+            limit,
+            revealed: None,
+            reveal_unlimited: false,
 
             head,
+            marked: Vec::new(),
+            search: None,
+
+            signatures,
 
             panel_rect: Rect::ZERO,
 
+            last_click: None,
+            free_scroll: false,
+
             config: commander.env.config.clone(),
         })
     }
@@ -134,9 +195,21 @@ impl<'a> LogPanel<'a> {
     //  Handle jj log output
     //
 
-    /// Run jj log and store output for display
+    /// Run jj log and store output for display. When jj fails because of commands running at the
+    /// same time, a log shown before is kept until the next refresh
     pub fn refresh_log_output(&mut self, commander: &mut Commander) {
-        self.log_output = commander.get_log(&self.log_revset);
+        let (revset, limit) = self.log_query();
+        let log_output = commander.get_log(&revset, self.compact, self.graph, limit);
+        if self.log_output.is_ok()
+            && let Err(err) = log_output.as_ref()
+            && err.is_concurrent_operation()
+        {
+            return;
+        }
+        self.log_output = log_output;
+        self.signatures = commander
+            .get_signed_commits(&revset, limit)
+            .unwrap_or_default();
         self.log_output_text = match self.log_output.as_ref() {
             Ok(log_output) => log_output
                 .graph
@@ -146,6 +219,92 @@ impl<'a> LogPanel<'a> {
         };
     }
 
+    /// Revset and limit of the jj log, widened while a change jumped to is revealed
+    fn log_query(&self) -> (Option<String>, Option<usize>) {
+        match self.revealed.as_ref() {
+            Some(revealed) => (
+                Some(revealed.clone()),
+                if self.reveal_unlimited {
+                    None
+                } else {
+                    self.limit
+                },
+            ),
+            None => (self.filtered_revset(), self.limit),
+        }
+    }
+
+    /// Widen the log to include the selected head, when the log doesn't show it.
+    /// The log revset and limit are left as is, see [`Self::clear_reveal`]
+    pub fn reveal_head(&mut self, commander: &mut Commander) {
+        if self.log_output.is_err() || self.get_current_head_index().is_some() {
+            return;
+        }
+        let revset = match self.filtered_revset() {
+            Some(revset) => revset,
+            None => match commander.get_default_log_revset() {
+                Ok(revset) => revset,
+                Err(_) => return,
+            },
+        };
+        self.revealed = Some(format!("({revset}) | {}", self.head.commit_id.as_str()));
+        self.reveal_unlimited = false;
+        self.refresh_log_output(commander);
+
+        // The change can be older than the changes loaded so far
+        if self.get_current_head_index().is_none() && self.limit.is_some() {
+            self.reveal_unlimited = true;
+            self.refresh_log_output(commander);
+        }
+    }
+
+    /// Go back to the log revset and limit after [`Self::reveal_head`]
+    pub fn clear_reveal(&mut self) {
+        self.revealed = None;
+        self.reveal_unlimited = false;
+    }
+
+    /// Whether the log was cut off by the limit, so more changes can be loaded
+    fn has_more(&self) -> bool {
+        let (_, limit) = self.log_query();
+        limit.is_some_and(|limit| self.heads().len() >= limit)
+    }
+
+    /// Load the next batch of changes after the ones listed, keeping the selection
+    fn load_more(&mut self, commander: &mut Commander) {
+        self.limit = self
+            .limit
+            .map(|limit| limit.saturating_add(self.config.log_load_more()));
+        self.refresh_log_output(commander);
+    }
+
+    /// Error from the last jj log, e.g. an invalid revset
+    pub fn log_error(&self) -> Option<&CommandError> {
+        self.log_output.as_ref().err()
+    }
+
+    /// Changes shown in the log, empty if jj log failed
+    pub fn heads(&self) -> &[Head] {
+        self.log_output
+            .as_ref()
+            .map_or(&[], |log_output| log_output.heads.as_slice())
+    }
+
+    /// The log revset, limited to changes touching the log path
+    pub fn filtered_revset(&self) -> Option<String> {
+        let Some(log_path) = self.log_path.as_ref() else {
+            return self.log_revset.clone();
+        };
+        let files = format!(
+            r#"files("{}")"#,
+            log_path.replace('\\', r"\\").replace('"', r#"\""#)
+        );
+        Some(match self.log_revset.as_ref() {
+            Some(log_revset) => format!("({log_revset}) & {files}"),
+            None => files,
+        })
+    }
+
     /// Convert log output to a list of formatted lines
     fn output_to_lines(&self, log_output: &LogOutput) -> Vec<Line<'a>> {
         // Set the background color of the line
@@ -159,23 +318,97 @@ impl<'a> LogPanel<'a> {
             }
         }
 
+        // Gutter marking conflicted changes, only when the log shows some
+        let conflict_gutter = !log_output.conflicted.is_empty();
+        // Gutter marking tagged changes, only when the log shows some
+        let tag_gutter = !log_output.tagged.is_empty();
+
         self.log_output_text
             .iter()
             .enumerate()
             .map(|(i, line)| {
                 let mut line = line.to_owned();
+                let line_head = log_output.graph_heads.get(i).unwrap_or(&None);
+
+                // Highlight text matching the search
+                if let Some(search) = self.search.as_deref() {
+                    let search_style = Style::new()
+                        .bg(self.config.highlight_color())
+                        .bold()
+                        .underlined();
+                    for range in search_ranges(&line_string(&line), search) {
+                        line = style_chars(line, range, search_style);
+                    }
+                }
+
+                let is_first_line =
+                    i == 0 || log_output.graph_heads.get(i - 1).unwrap_or(&None) != line_head;
+
+                // Signature column, only when the log shows signed changes
+                if !self.signatures.is_empty() {
+                    let signature = line_head
+                        .as_ref()
+                        .filter(|_| is_first_line)
+                        .and_then(|head| self.signatures.get(&head.commit_id));
+                    match signature {
+                        Some(signature) => line.spans.insert(0, signature_span(*signature)),
+                        None => line.spans.insert(0, Span::raw("  ")),
+                    }
+                }
+
+                // Gutter on the first line of each change, marking tags
+                if tag_gutter {
+                    let tagged = line_head.as_ref().is_some_and(|head| {
+                        is_first_line && log_output.tagged.contains(&head.commit_id)
+                    });
+                    if tagged {
+                        line.spans
+                            .insert(0, Span::raw("T ").fg(Color::Yellow).bold());
+                    } else {
+                        line.spans.insert(0, Span::raw("  "));
+                    }
+                }
 
-                // Add padding at start
-                line.spans.insert(0, Span::from(" "));
+                // Gutter on the first line of each change, marking conflicts
+                if conflict_gutter {
+                    let conflicted = line_head.as_ref().is_some_and(|head| {
+                        is_first_line && log_output.conflicted.contains(&head.commit_id)
+                    });
+                    if conflicted {
+                        line.spans
+                            .insert(0, Span::raw("! ").fg(Color::LightRed).bold());
+                    } else {
+                        line.spans.insert(0, Span::raw("  "));
+                    }
+                }
+
+                // Add padding at start, or `@` marking the working copy, also without the graph
+                let working_copy = line_head.as_ref().is_some_and(|head| {
+                    is_first_line && log_output.working_copy.as_ref() == Some(&head.commit_id)
+                });
+                if working_copy {
+                    line.spans.insert(0, Span::raw("@").fg(Color::Green).bold());
+                } else {
+                    line.spans.insert(0, Span::from(" "));
+                }
 
                 // Highlight lines that correspond to self.head
-                let line_head = log_output.graph_heads.get(i).unwrap_or(&None);
                 if let Some(line_change) = line_head
                     && line_change == &self.head
                 {
                     set_bg(&mut line, self.config.highlight_color());
                 };
 
+                // Underline lines of the marked changes
+                if let Some(line_change) = line_head
+                    && self
+                        .marked
+                        .iter()
+                        .any(|marked| marked.change_id == line_change.change_id)
+                {
+                    line = line.underlined();
+                }
+
                 line
             })
             .collect()
@@ -184,7 +417,15 @@ impl<'a> LogPanel<'a> {
     /// Get lines to show in log list
     fn log_lines(&self) -> Vec<Line<'a>> {
         match self.log_output.as_ref() {
-            Ok(log_output) => self.output_to_lines(log_output),
+            Ok(log_output) => {
+                let mut lines = self.output_to_lines(log_output);
+                if self.has_more() {
+                    lines.push(
+                        Line::from(" … scroll down to load more changes").fg(Color::DarkGray),
+                    );
+                }
+                lines
+            }
             Err(err) => err.into_text("Error getting log").unwrap().lines,
         }
     }
@@ -221,21 +462,112 @@ impl<'a> LogPanel<'a> {
     /// in unit head-index. Moving the head-index this much causes a
     /// full page scroll.
     fn visible_heads(&self) -> u16 {
-        // Every item in the log list is 2 lines high, so divide screen rows
-        // by 2 to get the number of log items that fit in it.
-        self.log_rect.height / 2
+        // Every item in the log list is 2 lines high (1 in compact mode), or as high as a
+        // custom template makes it, so divide screen rows by that to get the number of log
+        // items that fit in it.
+        if self.compact {
+            return self.log_rect.height;
+        }
+        let lines_per_head = match self.log_output.as_ref() {
+            Ok(log_output) if !log_output.heads.is_empty() => {
+                (log_output.graph_heads.len() / log_output.heads.len()).max(1)
+            }
+            _ => 2,
+        };
+        self.log_rect.height / lines_per_head as u16
+    }
+
+    /// Scroll the list by `lines`, leaving the selection where it is
+    fn scroll_list(&mut self, lines: isize) {
+        let last_line = self.log_output_text.lines.len().saturating_sub(1);
+        let offset = self.log_list_state.offset().saturating_add_signed(lines);
+        *self.log_list_state.offset_mut() = offset.min(last_line);
+        self.free_scroll = true;
     }
 
     /// Move selection to a specific head. This may cause the next draw to
     /// scroll to a different line.
     pub fn set_head(&mut self, head: Head) {
         head.clone_into(&mut self.head);
+        self.free_scroll = false;
+    }
+
+    /// Head indexes of the changes with a line matching the search
+    fn search_matches(&self) -> Vec<usize> {
+        let (Ok(log_output), Some(search)) = (self.log_output.as_ref(), self.search.as_deref())
+        else {
+            return vec![];
+        };
+
+        let matching: HashSet<&Head> = self
+            .log_output_text
+            .lines
+            .iter()
+            .zip(&log_output.graph_heads)
+            .filter_map(|(line, head)| {
+                head.as_ref()
+                    .filter(|_| !search_ranges(&line_string(line), search).is_empty())
+            })
+            .collect();
+        log_output
+            .heads
+            .iter()
+            .enumerate()
+            .filter(|(_, head)| matching.contains(head))
+            .map(|(index, _)| index)
+            .collect()
+    }
+
+    /// Select the next change matching the search, wrapping around at the end of the log.
+    /// With `include_current`, the selected change is kept if it matches.
+    /// Returns false if no change matches
+    pub fn select_search_match(&mut self, backwards: bool, include_current: bool) -> bool {
+        let matches = self.search_matches();
+        let current = self.get_current_head_index().unwrap_or(0);
+        let next = if backwards {
+            matches
+                .iter()
+                .rev()
+                .find(|index| **index < current || (include_current && **index == current))
+                .or(matches.last())
+        } else {
+            matches
+                .iter()
+                .find(|index| **index > current || (include_current && **index == current))
+                .or(matches.first())
+        };
+
+        let next_head = next.and_then(|index| {
+            self.log_output
+                .as_ref()
+                .ok()
+                .and_then(|log_output| log_output.heads.get(*index))
+                .cloned()
+        });
+        match next_head {
+            Some(head) => {
+                self.set_head(head);
+                true
+            }
+            None => false,
+        }
     }
 
     /// Move selection relative to the current position.
     /// The scroll is relative to head-index, not line-index.
+    /// Reaching the last change loads more changes, if the log was cut off.
     /// This will update self.head
-    fn scroll_relative(&mut self, _commander: &mut Commander, scroll: isize) {
+    fn scroll_relative(&mut self, commander: &mut Commander, scroll: isize) {
+        if self.has_more()
+            && self
+                .get_current_head_index()
+                .is_some_and(|current_head_index| {
+                    current_head_index.saturating_add_signed(scroll) + 1 >= self.heads().len()
+                })
+        {
+            self.load_more(commander);
+        }
+
         let log_output = match self.log_output.as_ref() {
             Ok(log_output) => log_output,
             Err(_) => return,
@@ -283,6 +615,14 @@ impl<'a> LogPanel<'a> {
                     (self.visible_heads() as isize / 2).saturating_neg(),
                 );
             }
+            LogTabEvent::ToggleCompact => {
+                self.compact = !self.compact;
+                self.refresh_log_output(commander);
+            }
+            LogTabEvent::ToggleGraph => {
+                self.graph = !self.graph;
+                self.refresh_log_output(commander);
+            }
             _ => {
                 return Ok(ComponentInputResult::NotHandled);
             }
@@ -304,18 +644,33 @@ impl Component for LogPanel<'_> {
     fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
         self.panel_rect = area;
 
-        let title = match &self.log_revset {
-            Some(log_revset) => &format!(" Log for: {log_revset} "),
-            None => " Log ",
+        let title = match (&self.log_revset, &self.log_path) {
+            (Some(log_revset), Some(log_path)) => {
+                &format!(" Log for: {log_revset} | Path: {log_path} ")
+            }
+            (Some(log_revset), None) => &format!(" Log for: {log_revset} "),
+            (None, Some(log_path)) => &format!(" Log for path: {log_path} "),
+            (None, None) => " Log ",
         };
 
         let log_lines = self.log_lines();
         let log_length: usize = log_lines.len();
-        let log_block = Block::bordered()
+        let mut log_block = Block::bordered()
             .title(title)
             .border_type(BorderType::Rounded);
+        if let Ok(log_output) = self.log_output.as_ref() {
+            log_block = log_block.title_bottom(position_indicator(
+                get_head_index(&self.head, &self.log_output),
+                log_output.heads.len(),
+            ));
+        }
         self.log_rect = log_block.inner(area);
-        self.log_list_state.select(self.selected_log_line());
+        // Without a selection, the list keeps its offset instead of scrolling to the selection
+        self.log_list_state.select(if self.free_scroll {
+            None
+        } else {
+            self.selected_log_line()
+        });
         let log = List::new(log_lines).block(log_block).scroll_padding(7);
         f.render_stateful_widget(log, area, &mut self.log_list_state);
 
@@ -349,16 +704,24 @@ impl Component for LogPanel<'_> {
             }
 
             // Execute command dependent on panel and event kind
-            match mouse_event.kind {
-                MouseEventKind::ScrollUp => {
+            match (mouse_event.kind, self.config.mouse_scroll()) {
+                (MouseEventKind::ScrollUp, MouseScroll::Selection) => {
                     self.handle_event(commander, LogTabEvent::ScrollUp)?;
                     return Ok(ComponentInputResult::Handled);
                 }
-                MouseEventKind::ScrollDown => {
+                (MouseEventKind::ScrollDown, MouseScroll::Selection) => {
                     self.handle_event(commander, LogTabEvent::ScrollDown)?;
                     return Ok(ComponentInputResult::Handled);
                 }
-                MouseEventKind::Up(_) => {
+                (MouseEventKind::ScrollUp, MouseScroll::List) => {
+                    self.scroll_list(-(MOUSE_SCROLL_LINES as isize));
+                    return Ok(ComponentInputResult::Handled);
+                }
+                (MouseEventKind::ScrollDown, MouseScroll::List) => {
+                    self.scroll_list(MOUSE_SCROLL_LINES as isize);
+                    return Ok(ComponentInputResult::Handled);
+                }
+                (MouseEventKind::Up(_), _) => {
                     // Check all items in list
 
                     // TODO make a function that constructs the log list
@@ -376,7 +739,17 @@ impl Component for LogPanel<'_> {
                         &mouse_event,
                     ) && let Some(head) = self.head_at_log_line(inx)
                     {
-                        self.set_head(head);
+                        let double_click = self.last_click.take().is_some_and(|(time, clicked)| {
+                            clicked == head && time.elapsed() < DOUBLE_CLICK_INTERVAL
+                        });
+                        self.set_head(head.clone());
+                        if double_click {
+                            return Ok(ComponentInputResult::HandledAction(
+                                ComponentAction::ViewFiles(head),
+                            ));
+                        }
+                        self.last_click = Some((Instant::now(), head));
+                        return Ok(ComponentInputResult::Handled);
                     }
                 }
                 _ => {} // Handle other mouse events if necessary
@@ -387,6 +760,12 @@ impl Component for LogPanel<'_> {
     }
 }
 
+/// Maximum time between the clicks of a double click
+const DOUBLE_CLICK_INTERVAL: Duration = Duration::from_millis(400);
+
+/// Lines scrolled by a mouse wheel step with `lazyjj.mouse-scroll = "list"`
+const MOUSE_SCROLL_LINES: usize = 3;
+
 // Determine which list item a mouse event is related to
 fn list_item_from_mouse_event(
     list: &[ListItem],
@@ -1,5 +1,7 @@
 mod details_panel;
+mod file_picker_panel;
 mod log_panel;
 
 pub use details_panel::DetailsPanel;
+pub use file_picker_panel::FilePickerPanel;
 pub use log_panel::LogPanel;
@@ -0,0 +1,557 @@
+use ansi_to_tui::IntoText;
+use anyhow::{Result, bail};
+use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEventKind},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListState, Paragraph},
+};
+
+use crate::{
+    ComponentInputResult,
+    commander::{Commander, log::Head},
+    env::Config,
+    ui::{
+        Component, ComponentAction, message_popup::MessagePopup, styles::create_popup_block,
+        utils::centered_rect,
+    },
+};
+
+/// Maximum number of changes which can be edited at once
+const MAX_STACK_LENGTH: usize = 50;
+
+/// What to do with a change of the stack
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum RebaseAction {
+    Pick,
+    /// Squash into the change below it
+    Squash,
+    Drop,
+}
+
+struct RebaseItem {
+    head: Head,
+    description: String,
+    action: RebaseAction,
+}
+
+/// A jj command applying part of the plan
+#[derive(PartialEq, Eq, Debug)]
+enum RebaseStep {
+    Abandon(Head),
+    Rebase {
+        head: Head,
+        destination: Head,
+    },
+    SquashIntoParent(Head),
+    /// Move a descendant of the stack, which is not part of it, onto the new top of the stack
+    RebaseChild {
+        head: Head,
+        destination: Head,
+    },
+}
+
+fn short_change_id(head: &Head) -> String {
+    head.change_id.as_str().chars().take(8).collect()
+}
+
+impl RebaseStep {
+    /// Command shown in the preview
+    fn command(&self) -> String {
+        match self {
+            RebaseStep::Abandon(head) => format!("jj abandon {}", short_change_id(head)),
+            RebaseStep::Rebase { head, destination } => format!(
+                "jj rebase -r {} -d {}",
+                short_change_id(head),
+                short_change_id(destination)
+            ),
+            RebaseStep::SquashIntoParent(head) => {
+                format!("jj squash -u -r {}", short_change_id(head))
+            }
+            RebaseStep::RebaseChild { head, destination } => format!(
+                "jj rebase -s {} -d {}",
+                short_change_id(head),
+                short_change_id(destination)
+            ),
+        }
+    }
+
+    /// Run the command. Commits are looked up by change id, as previous steps rewrite them
+    fn run(&self, commander: &mut Commander) -> Result<()> {
+        match self {
+            RebaseStep::Abandon(head) => {
+                let head = commander.get_head_latest(head)?;
+                commander.run_abandon(&head.commit_id, false)
+            }
+            RebaseStep::Rebase { head, destination } => {
+                let head = commander.get_head_latest(head)?;
+                let destination = commander.get_head_latest(destination)?;
+                commander.run_rebase(head.commit_id.as_str(), &destination.commit_id)
+            }
+            RebaseStep::SquashIntoParent(head) => {
+                let head = commander.get_head_latest(head)?;
+                commander.run_squash_into_parent(&head.commit_id)
+            }
+            RebaseStep::RebaseChild { head, destination } => {
+                let head = commander.get_head_latest(head)?;
+                let destination = commander.get_head_latest(destination)?;
+                commander.run_rebase_source(&head.commit_id, &destination.commit_id)
+            }
+        }
+    }
+}
+
+/// Translate the edited stack into jj commands.
+///
+/// `items` and `original` are newest first. Dropped changes are abandoned first,
+/// then changes are rebased one by one from the first one which moved, then changes
+/// are squashed into the change below them, oldest first.
+fn plan_rebase(
+    items: &[RebaseItem],
+    original: &[Head],
+    base: &Head,
+    children: &[Head],
+) -> Result<Vec<RebaseStep>, String> {
+    let mut steps: Vec<RebaseStep> = items
+        .iter()
+        .rev()
+        .filter(|item| item.action == RebaseAction::Drop)
+        .map(|item| RebaseStep::Abandon(item.head.clone()))
+        .collect();
+
+    let kept: Vec<&RebaseItem> = items
+        .iter()
+        .rev()
+        .filter(|item| item.action != RebaseAction::Drop)
+        .collect();
+    if kept
+        .first()
+        .is_some_and(|item| item.action == RebaseAction::Squash)
+    {
+        return Err("The oldest change can't be squashed, as there is no change below it".into());
+    }
+
+    let original_kept: Vec<&Head> = original
+        .iter()
+        .rev()
+        .filter(|head| kept.iter().any(|item| &item.head == *head))
+        .collect();
+    let first_moved = kept
+        .iter()
+        .zip(&original_kept)
+        .position(|(item, head)| &item.head != *head);
+    if let Some(first_moved) = first_moved {
+        for index in first_moved..kept.len() {
+            let destination = match index {
+                0 => base,
+                _ => &kept[index - 1].head,
+            };
+            steps.push(RebaseStep::Rebase {
+                head: kept[index].head.clone(),
+                destination: destination.clone(),
+            });
+        }
+    }
+
+    steps.extend(
+        kept.iter()
+            .filter(|item| item.action == RebaseAction::Squash)
+            .map(|item| RebaseStep::SquashIntoParent(item.head.clone())),
+    );
+
+    // Moving the top of the stack with `jj rebase -r` leaves its children behind
+    if first_moved.is_some()
+        && let Some(top) = kept
+            .iter()
+            .rev()
+            .find(|item| item.action != RebaseAction::Squash)
+    {
+        steps.extend(children.iter().map(|child| RebaseStep::RebaseChild {
+            head: child.clone(),
+            destination: top.head.clone(),
+        }));
+    }
+
+    Ok(steps)
+}
+
+/// Popup to reorder, drop and squash the stack of mutable changes ending at a change.
+/// The first Enter previews the jj commands to run, the second one runs them.
+pub struct RebaseEditorPopup {
+    /// Parent of the oldest change of the stack
+    base: Head,
+    /// Changes of the stack as loaded, newest first
+    original: Vec<Head>,
+    /// Changes of the stack as edited, newest first
+    items: Vec<RebaseItem>,
+    /// Children of the top of the stack
+    children: Vec<Head>,
+    list_state: ListState,
+    preview: Option<Result<Vec<RebaseStep>, String>>,
+    config: Config,
+}
+
+impl RebaseEditorPopup {
+    /// Load the mutable ancestors of `top`, which must form a linear stack
+    pub fn new(commander: &mut Commander, config: Config, top: &Head) -> Result<Self> {
+        let stack = commander.get_revset_commits(&format!("::{} & mutable()", top.commit_id))?;
+        if stack.first().is_none_or(|(head, _)| head != top) {
+            bail!("The change is immutable");
+        }
+        if stack.len() > MAX_STACK_LENGTH {
+            bail!(
+                "The stack has {} mutable changes, more than the {MAX_STACK_LENGTH} which can be edited at once",
+                stack.len()
+            );
+        }
+
+        for (index, (head, _)) in stack.iter().enumerate() {
+            let parents = commander.get_commit_parents(&head.commit_id)?;
+            let linear = match stack.get(index + 1) {
+                Some((parent, _)) => parents == [parent.commit_id.clone()],
+                None => parents.len() == 1,
+            };
+            if !linear {
+                bail!("The mutable ancestors of the change are not a linear stack");
+            }
+        }
+
+        let (oldest, _) = stack.last().expect("stack contains top");
+        let base = commander.get_commit_parent(&oldest.commit_id)?;
+        let children = commander
+            .get_revset_commits(&format!("children({})", top.commit_id))?
+            .into_iter()
+            .map(|(head, _)| head)
+            .collect();
+
+        Ok(Self {
+            base,
+            original: stack.iter().map(|(head, _)| head.clone()).collect(),
+            items: stack
+                .into_iter()
+                .map(|(head, description)| RebaseItem {
+                    head,
+                    description,
+                    action: RebaseAction::Pick,
+                })
+                .collect(),
+            children,
+            list_state: ListState::default().with_selected(Some(0)),
+            preview: None,
+            config,
+        })
+    }
+
+    fn selected(&self) -> usize {
+        self.list_state.selected().unwrap_or(0)
+    }
+
+    fn set_action(&mut self, action: RebaseAction) {
+        let selected = self.selected();
+        self.items[selected].action = action;
+        self.preview = None;
+    }
+
+    /// Move the selected change up (towards newer) or down (towards older)
+    fn move_selected(&mut self, offset: isize) {
+        let selected = self.selected();
+        let Some(target) = selected
+            .checked_add_signed(offset)
+            .filter(|target| *target < self.items.len())
+        else {
+            return;
+        };
+        self.items.swap(selected, target);
+        self.list_state.select(Some(target));
+        self.preview = None;
+    }
+
+    fn apply(
+        &self,
+        commander: &mut Commander,
+        steps: &[RebaseStep],
+    ) -> Result<ComponentInputResult> {
+        for (index, step) in steps.iter().enumerate() {
+            if let Err(err) = step.run(commander) {
+                // The previous steps were applied, show what is left to clean up
+                let undo = match index {
+                    0 => "No command was applied.".to_owned(),
+                    1 => "The previous command was applied, revert it with `jj undo`.".to_owned(),
+                    _ => format!(
+                        "The {index} previous commands were applied, revert them from the operation log or with `jj undo`."
+                    ),
+                };
+                return Ok(ComponentInputResult::HandledAction(
+                    ComponentAction::Multiple(vec![
+                        ComponentAction::RefreshTab(),
+                        ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                            title: "Interactive rebase".into(),
+                            messages: format!(
+                                "Step {} of {} failed: {}\n\n{err:#}\n\n{undo}",
+                                index + 1,
+                                steps.len(),
+                                step.command()
+                            )
+                            .into_text()?,
+                            text_align: None,
+                        }))),
+                    ]),
+                ));
+            }
+        }
+
+        let top = self
+            .items
+            .iter()
+            .find(|item| item.action == RebaseAction::Pick)
+            .map_or(&self.base, |item| &item.head);
+        let top = commander.get_head_latest(top)?;
+        Ok(ComponentInputResult::HandledAction(
+            ComponentAction::Multiple(vec![
+                ComponentAction::SetPopup(None),
+                ComponentAction::ViewLog(top),
+            ]),
+        ))
+    }
+}
+
+impl Component for RebaseEditorPopup {
+    fn draw(&mut self, f: &mut ratatui::prelude::Frame<'_>, area: Rect) -> Result<()> {
+        let block = create_popup_block("Interactive rebase");
+        let area = centered_rect(area, 70, 70);
+        f.render_widget(Clear, area);
+        f.render_widget(&block, area);
+
+        let popup_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Percentage(60),
+                Constraint::Fill(1),
+                Constraint::Length(2),
+            ])
+            .split(block.inner(area));
+
+        let mut lines: Vec<Line> = self
+            .items
+            .iter()
+            .map(|item| {
+                let (action, color) = match item.action {
+                    RebaseAction::Pick => ("pick  ", Color::Green),
+                    RebaseAction::Squash => ("squash", Color::Yellow),
+                    RebaseAction::Drop => ("drop  ", Color::Red),
+                };
+                let description = if item.description.is_empty() {
+                    Span::raw("(no description set)").fg(Color::Yellow)
+                } else {
+                    Span::raw(item.description.clone())
+                };
+                let mut line = Line::from(vec![
+                    Span::raw(action).fg(color),
+                    Span::raw(" "),
+                    Span::raw(short_change_id(&item.head)).fg(Color::Magenta),
+                    Span::raw(" "),
+                    description,
+                ]);
+                if item.action == RebaseAction::Drop {
+                    line = line.add_modifier(Modifier::CROSSED_OUT);
+                }
+                line
+            })
+            .collect();
+        lines.push(
+            Line::from(format!("onto   {}", short_change_id(&self.base))).fg(Color::DarkGray),
+        );
+        let list = List::new(lines)
+            .block(
+                Block::bordered()
+                    .title(" Stack, newest first ")
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(Color::DarkGray)),
+            )
+            .scroll_padding(3)
+            .highlight_style(Style::default().bg(self.config.highlight_color()));
+        f.render_stateful_widget(list, popup_chunks[0], &mut self.list_state);
+
+        match self.preview.as_ref() {
+            None => {
+                f.render_widget(
+                    Paragraph::new("Press Enter to preview the commands to run")
+                        .fg(Color::DarkGray)
+                        .alignment(Alignment::Center),
+                    popup_chunks[1],
+                );
+            }
+            Some(Ok(steps)) if steps.is_empty() => {
+                f.render_widget(
+                    Paragraph::new("Nothing to change").alignment(Alignment::Center),
+                    popup_chunks[1],
+                );
+            }
+            Some(Ok(steps)) => {
+                let mut lines = vec![Line::from("Commands to run:").fg(Color::Yellow)];
+                lines.extend(steps.iter().map(|step| Line::raw(step.command())));
+                f.render_widget(List::new(lines), popup_chunks[1]);
+            }
+            Some(Err(err)) => {
+                f.render_widget(Paragraph::new(err.as_str()).fg(Color::Red), popup_chunks[1]);
+            }
+        }
+
+        let help = Paragraph::new(vec![
+            format!(
+                "j/k: select | J/K: move | p: pick | s: squash | d: drop | Enter: {} | Escape: cancel",
+                match self.preview {
+                    Some(Ok(_)) => "apply",
+                    _ => "preview",
+                }
+            )
+            .into(),
+        ])
+        .fg(Color::DarkGray)
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::TOP)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+        f.render_widget(help, popup_chunks[2]);
+
+        Ok(())
+    }
+
+    fn input(&mut self, commander: &mut Commander, event: Event) -> Result<ComponentInputResult> {
+        if let Event::Key(key) = event
+            && key.kind == KeyEventKind::Press
+        {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => return Ok(ComponentInputResult::NotHandled),
+                KeyCode::Char('j') | KeyCode::Down => {
+                    let selected = (self.selected() + 1).min(self.items.len() - 1);
+                    self.list_state.select(Some(selected));
+                }
+                KeyCode::Char('k') | KeyCode::Up => {
+                    self.list_state
+                        .select(Some(self.selected().saturating_sub(1)));
+                }
+                KeyCode::Char('J') => self.move_selected(1),
+                KeyCode::Char('K') => self.move_selected(-1),
+                KeyCode::Char('p') => self.set_action(RebaseAction::Pick),
+                KeyCode::Char('s') => self.set_action(RebaseAction::Squash),
+                KeyCode::Char('d') => self.set_action(RebaseAction::Drop),
+                KeyCode::Enter => {
+                    if let Some(Ok(steps)) = self.preview.as_ref()
+                        && !steps.is_empty()
+                    {
+                        return self.apply(commander, steps);
+                    }
+                    self.preview = Some(plan_rebase(
+                        &self.items,
+                        &self.original,
+                        &self.base,
+                        &self.children,
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(ComponentInputResult::Handled)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commander::ids::{ChangeId, CommitId};
+
+    fn head(id: &str) -> Head {
+        Head {
+            commit_id: CommitId(id.to_owned()),
+            change_id: ChangeId(id.to_owned()),
+            divergent: false,
+            immutable: false,
+        }
+    }
+
+    fn items(actions: &[(&str, RebaseAction)]) -> Vec<RebaseItem> {
+        actions
+            .iter()
+            .map(|(id, action)| RebaseItem {
+                head: head(id),
+                description: String::new(),
+                action: *action,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn plan_rebase_unchanged_stack() {
+        let original = [head("c"), head("b"), head("a")];
+        let items = items(&[
+            ("c", RebaseAction::Pick),
+            ("b", RebaseAction::Pick),
+            ("a", RebaseAction::Pick),
+        ]);
+
+        assert_eq!(
+            plan_rebase(&items, &original, &head("base"), &[head("child")]),
+            Ok(vec![])
+        );
+    }
+
+    #[test]
+    fn plan_rebase_reorders_from_first_moved_change() {
+        let original = [head("c"), head("b"), head("a")];
+        let items = items(&[
+            ("b", RebaseAction::Pick),
+            ("c", RebaseAction::Pick),
+            ("a", RebaseAction::Pick),
+        ]);
+
+        assert_eq!(
+            plan_rebase(&items, &original, &head("base"), &[head("child")]),
+            Ok(vec![
+                RebaseStep::Rebase {
+                    head: head("c"),
+                    destination: head("a"),
+                },
+                RebaseStep::Rebase {
+                    head: head("b"),
+                    destination: head("c"),
+                },
+                RebaseStep::RebaseChild {
+                    head: head("child"),
+                    destination: head("b"),
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn plan_rebase_drops_and_squashes() {
+        let original = [head("c"), head("b"), head("a")];
+        let items = items(&[
+            ("c", RebaseAction::Squash),
+            ("b", RebaseAction::Drop),
+            ("a", RebaseAction::Pick),
+        ]);
+
+        assert_eq!(
+            plan_rebase(&items, &original, &head("base"), &[]),
+            Ok(vec![
+                RebaseStep::Abandon(head("b")),
+                RebaseStep::SquashIntoParent(head("c")),
+            ])
+        );
+    }
+
+    #[test]
+    fn plan_rebase_rejects_squashing_oldest_change() {
+        let original = [head("b"), head("a")];
+        let items = items(&[("b", RebaseAction::Pick), ("a", RebaseAction::Squash)]);
+
+        assert!(plan_rebase(&items, &original, &head("base"), &[]).is_err());
+    }
+}
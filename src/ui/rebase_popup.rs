@@ -0,0 +1,256 @@
+use ansi_to_tui::IntoText;
+use anyhow::Result;
+use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEventKind},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, Paragraph},
+};
+use tui_textarea::TextArea;
+
+use crate::{
+    ComponentInputResult,
+    commander::{Commander, jj::RebaseMode, log::Head},
+    env::Config,
+    ui::{
+        Component, ComponentAction,
+        message_popup::MessagePopup,
+        styles::create_popup_block,
+        utils::{centered_rect, head_id_spans},
+    },
+};
+
+/// Popup to rebase a revset onto a destination, moving only its commits, also their
+/// descendants or their whole branch.
+/// The first Enter previews the commits which will be moved, the second one rebases them.
+pub struct RebasePopup<'a> {
+    destination: Head,
+    revset: TextArea<'a>,
+    mode: RebaseMode,
+    /// Commits matched by the revset when it was last previewed
+    preview: Option<Result<Vec<(Head, String)>, String>>,
+    config: Config,
+}
+
+impl RebasePopup<'_> {
+    pub fn new(config: Config, destination: Head) -> Self {
+        Self {
+            destination,
+            revset: TextArea::default(),
+            mode: RebaseMode::Revisions,
+            preview: None,
+            config,
+        }
+    }
+
+    fn revset(&self) -> String {
+        self.revset.lines().join("\n").trim().to_owned()
+    }
+
+    /// Rebase the previewed commits, then show the bottom-most one in the log
+    fn rebase(
+        &mut self,
+        commander: &mut Commander,
+        revset: &str,
+        moved: &Head,
+    ) -> Result<ComponentInputResult> {
+        let messages =
+            match commander.run_rebase_mode(self.mode, revset, &self.destination.commit_id) {
+                Ok(messages) => messages,
+                Err(err) => {
+                    // Keep the popup open with jj's error, e.g. about immutable commits
+                    self.preview = Some(Err(err.to_string()));
+                    return Ok(ComponentInputResult::Handled);
+                }
+            };
+
+        let mut actions = vec![
+            ComponentAction::SetPopup(None),
+            ComponentAction::ViewLog(commander.get_head_latest(moved)?),
+        ];
+        // Tell which commits got conflicts
+        if messages.contains("conflict") {
+            actions.push(ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                title: "Rebase".into(),
+                messages: messages.into_text()?,
+                text_align: None,
+            }))));
+        }
+        Ok(ComponentInputResult::HandledAction(
+            ComponentAction::Multiple(actions),
+        ))
+    }
+}
+
+fn mode_label(mode: RebaseMode) -> &'static str {
+    match mode {
+        RebaseMode::Revisions => "revisions",
+        RebaseMode::Source => "with descendants",
+        RebaseMode::Branch => "whole branch",
+    }
+}
+
+impl Component for RebasePopup<'_> {
+    fn draw(&mut self, f: &mut ratatui::prelude::Frame<'_>, area: Rect) -> Result<()> {
+        let block = create_popup_block("Rebase revset");
+        let area = centered_rect(area, 60, 60);
+        f.render_widget(Clear, area);
+        f.render_widget(&block, area);
+
+        let popup_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(1),
+                Constraint::Length(1),
+                Constraint::Length(3),
+                Constraint::Fill(1),
+                Constraint::Length(2),
+            ])
+            .split(block.inner(area));
+
+        f.render_widget(
+            Line::from(
+                [
+                    vec![Span::raw("Destination: ")],
+                    head_id_spans(&self.destination, self.config.primary_id()),
+                ]
+                .concat(),
+            ),
+            popup_chunks[0],
+        );
+
+        let mut mode_spans = vec![Span::raw("Move: ")];
+        for mode in [
+            RebaseMode::Revisions,
+            RebaseMode::Source,
+            RebaseMode::Branch,
+        ] {
+            let label = Span::raw(format!(" {} {} ", mode.flag(), mode_label(mode)));
+            mode_spans.push(if mode == self.mode {
+                label.bg(self.config.highlight_color())
+            } else {
+                label.fg(Color::DarkGray)
+            });
+        }
+        f.render_widget(Line::from(mode_spans), popup_chunks[1]);
+
+        self.revset.set_block(
+            Block::bordered()
+                .title(" Revset ")
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+        f.render_widget(&self.revset, popup_chunks[2]);
+
+        match self.preview.as_ref() {
+            None => {
+                f.render_widget(
+                    Paragraph::new("Press Enter to preview the commits to rebase")
+                        .fg(Color::DarkGray)
+                        .alignment(Alignment::Center),
+                    popup_chunks[3],
+                );
+            }
+            Some(Ok(commits)) if commits.is_empty() => {
+                f.render_widget(
+                    Paragraph::new("No commits in the revset").alignment(Alignment::Center),
+                    popup_chunks[3],
+                );
+            }
+            Some(Ok(commits)) => {
+                let mut items = vec![
+                    Line::from(format!(
+                        "{} {} will be rebased:",
+                        commits.len(),
+                        if commits.len() == 1 {
+                            "commit"
+                        } else {
+                            "commits"
+                        }
+                    ))
+                    .fg(Color::Yellow),
+                ];
+                items.extend(commits.iter().map(|(head, description)| {
+                    let mut change_id = head.change_id.to_string();
+                    change_id.truncate(8);
+                    let description = if description.is_empty() {
+                        Span::raw("(no description set)").fg(Color::Yellow)
+                    } else {
+                        Span::raw(description.clone())
+                    };
+                    Line::from(vec![
+                        Span::raw(change_id).fg(Color::Magenta),
+                        Span::raw(" "),
+                        description,
+                    ])
+                }));
+                f.render_widget(List::new(items), popup_chunks[3]);
+            }
+            Some(Err(err)) => {
+                f.render_widget(Paragraph::new(err.as_str()).fg(Color::Red), popup_chunks[3]);
+            }
+        }
+
+        let help = Paragraph::new(vec![
+            match self.preview {
+                Some(Ok(_)) => "Tab: change what to move | Enter: rebase | Escape: cancel",
+                _ => "Tab: change what to move | Enter: preview | Escape: cancel",
+            }
+            .into(),
+        ])
+        .fg(Color::DarkGray)
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::TOP)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+        f.render_widget(help, popup_chunks[4]);
+
+        Ok(())
+    }
+
+    fn input(&mut self, commander: &mut Commander, event: Event) -> Result<ComponentInputResult> {
+        if let Event::Key(key) = event
+            && key.kind == KeyEventKind::Press
+        {
+            match key.code {
+                KeyCode::Esc => return Ok(ComponentInputResult::NotHandled),
+                KeyCode::Enter => {
+                    let revset = self.revset();
+                    if revset.is_empty() {
+                        return Ok(ComponentInputResult::Handled);
+                    }
+
+                    if let Some(Ok(commits)) = self.preview.as_ref()
+                        && let Some((moved, _)) = commits.last().cloned()
+                    {
+                        return self.rebase(commander, &revset, &moved);
+                    }
+
+                    let moved_revset = self.mode.moved_revset(&revset, &self.destination.commit_id);
+                    self.preview = Some(
+                        commander
+                            .get_revset_commits(&moved_revset)
+                            .map_err(|err| format!("{err:#}")),
+                    );
+                    return Ok(ComponentInputResult::Handled);
+                }
+                KeyCode::Tab => {
+                    self.mode = self.mode.next();
+                    self.preview = None;
+                    return Ok(ComponentInputResult::Handled);
+                }
+                _ => {}
+            }
+        }
+
+        // Any edit of the revset invalidates the preview
+        if self.revset.input(event) {
+            self.preview = None;
+        }
+        Ok(ComponentInputResult::Handled)
+    }
+}
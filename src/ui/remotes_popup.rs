@@ -0,0 +1,309 @@
+use anyhow::Result;
+use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListState, Paragraph},
+};
+use tui_textarea::{CursorMove, TextArea};
+
+use crate::{
+    ComponentInputResult,
+    app::FetchTarget,
+    commander::{Commander, remotes::GitRemote},
+    env::Config,
+    ui::{Component, ComponentAction, styles::create_popup_block, utils::centered_rect},
+};
+
+/// Form to add a remote, or to change the URL of an existing one
+struct RemoteForm<'a> {
+    name: TextArea<'a>,
+    url: TextArea<'a>,
+    editing_url: bool,
+    /// Name of the remote whose URL is changed
+    existing: Option<String>,
+}
+
+/// Popup listing the git remotes, to add, edit, remove or fetch from them.
+/// As a fetch picker, it only fetches.
+pub struct RemotesPopup<'a> {
+    fetch_picker: bool,
+    remotes: Result<Vec<GitRemote>, String>,
+    list_state: ListState,
+    list_height: u16,
+    form: Option<RemoteForm<'a>>,
+    /// Remote waiting for confirmation to be removed
+    removing: Option<String>,
+    error: Option<String>,
+    config: Config,
+}
+
+impl RemotesPopup<'_> {
+    pub fn new(config: Config, commander: &mut Commander) -> Self {
+        let mut popup = Self {
+            fetch_picker: false,
+            remotes: Ok(vec![]),
+            list_state: ListState::default().with_selected(Some(0)),
+            list_height: 0,
+            form: None,
+            removing: None,
+            error: None,
+            config,
+        };
+        popup.refresh(commander);
+        popup
+    }
+
+    /// Create popup which only picks the remote to fetch from
+    pub fn new_fetch_picker(config: Config, commander: &mut Commander) -> Self {
+        let mut popup = Self::new(config, commander);
+        popup.fetch_picker = true;
+        popup
+    }
+
+    fn refresh(&mut self, commander: &mut Commander) {
+        self.remotes = commander.get_git_remotes().map_err(|err| err.to_string());
+        self.scroll(0);
+    }
+
+    fn scroll(&mut self, scroll: isize) {
+        let len = self.remotes.as_ref().map_or(0, |remotes| remotes.len());
+        self.list_state.select(Some(
+            self.list_state
+                .selected()
+                .map(|selected| selected.saturating_add_signed(scroll))
+                .unwrap_or(0)
+                .min(len.saturating_sub(1)),
+        ));
+    }
+
+    fn selected_remote(&self) -> Option<&GitRemote> {
+        self.remotes
+            .as_ref()
+            .ok()
+            .and_then(|remotes| remotes.get(self.list_state.selected()?))
+    }
+
+    /// Add the remote of the form, or change its URL
+    fn save_form(&mut self, commander: &mut Commander) {
+        let Some(form) = self.form.as_ref() else {
+            return;
+        };
+        let name = form.name.lines().join("").trim().to_owned();
+        let url = form.url.lines().join("").trim().to_owned();
+        if name.is_empty() || url.is_empty() {
+            return;
+        }
+
+        let result = match form.existing.as_ref() {
+            Some(existing) => commander.set_git_remote_url(existing, &url),
+            None => commander.add_git_remote(&name, &url),
+        };
+        match result {
+            Ok(()) => {
+                self.form = None;
+                self.error = None;
+                self.refresh(commander);
+            }
+            Err(err) => self.error = Some(err.to_string()),
+        }
+    }
+}
+
+fn field_block(title: &str, focused: bool) -> Block<'_> {
+    Block::bordered()
+        .title(format!(" {title} "))
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(if focused {
+            Color::Green
+        } else {
+            Color::DarkGray
+        }))
+}
+
+impl Component for RemotesPopup<'_> {
+    fn draw(&mut self, f: &mut ratatui::prelude::Frame<'_>, area: Rect) -> Result<()> {
+        let block = create_popup_block(if self.fetch_picker {
+            "Fetch from remote"
+        } else {
+            "Git remotes"
+        });
+        let area = centered_rect(area, 60, 60);
+        f.render_widget(Clear, area);
+        f.render_widget(&block, area);
+
+        let popup_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Fill(1),
+                Constraint::Length(if self.form.is_some() { 6 } else { 0 }),
+                Constraint::Length(if self.error.is_some() { 1 } else { 0 }),
+                Constraint::Length(2),
+            ])
+            .split(block.inner(area));
+        self.list_height = popup_chunks[0].height;
+
+        let items: Vec<Line> = match self.remotes.as_ref() {
+            Ok(remotes) if remotes.is_empty() => {
+                vec![Line::from("No git remotes").fg(Color::DarkGray)]
+            }
+            Ok(remotes) => remotes
+                .iter()
+                .map(|remote| {
+                    Line::from(vec![
+                        Span::raw(remote.name.clone()).fg(Color::Magenta),
+                        Span::raw(" "),
+                        Span::raw(remote.url.clone()),
+                    ])
+                })
+                .collect(),
+            Err(err) => vec![Line::from(err.clone()).fg(Color::Red)],
+        };
+        let list = List::new(items)
+            .scroll_padding(3)
+            .highlight_style(Style::default().bg(self.config.highlight_color()));
+        f.render_stateful_widget(list, popup_chunks[0], &mut self.list_state);
+
+        if let Some(form) = self.form.as_mut() {
+            let form_chunks = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Length(3), Constraint::Length(3)])
+                .split(popup_chunks[1]);
+            form.name.set_block(field_block(
+                "Name",
+                !form.editing_url && form.existing.is_none(),
+            ));
+            form.url.set_block(field_block("URL", form.editing_url));
+            f.render_widget(&form.name, form_chunks[0]);
+            f.render_widget(&form.url, form_chunks[1]);
+        }
+
+        if let Some(error) = self.error.as_ref() {
+            f.render_widget(
+                Paragraph::new(error.as_str()).fg(Color::Red),
+                popup_chunks[2],
+            );
+        }
+
+        let help = match (self.removing.as_ref(), self.form.as_ref()) {
+            (Some(name), _) => Paragraph::new(vec![
+                Line::from(format!("Remove remote {name}? y: remove | n: cancel"))
+                    .fg(Color::Yellow),
+            ]),
+            (None, Some(_)) => Paragraph::new(vec![
+                "Tab: next field | Enter: save | Escape: cancel".into(),
+            ])
+            .fg(Color::DarkGray),
+            (None, None) if self.fetch_picker => {
+                Paragraph::new(vec!["j/k: scroll | Enter: fetch | Escape: cancel".into()])
+                    .fg(Color::DarkGray)
+            }
+            (None, None) => Paragraph::new(vec![
+                "j/k: scroll | f/Enter: fetch | a: add | e: edit URL | d: remove | Escape: close"
+                    .into(),
+            ])
+            .fg(Color::DarkGray),
+        }
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::TOP)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+        f.render_widget(help, popup_chunks[3]);
+
+        Ok(())
+    }
+
+    fn input(&mut self, commander: &mut Commander, event: Event) -> Result<ComponentInputResult> {
+        let Event::Key(key) = event else {
+            return Ok(ComponentInputResult::Handled);
+        };
+        if key.kind != KeyEventKind::Press {
+            return Ok(ComponentInputResult::Handled);
+        }
+
+        if let Some(name) = self.removing.take() {
+            if key.code == KeyCode::Char('y') {
+                match commander.remove_git_remote(&name) {
+                    Ok(()) => {
+                        self.error = None;
+                        self.refresh(commander);
+                    }
+                    Err(err) => self.error = Some(err.to_string()),
+                }
+            }
+            return Ok(ComponentInputResult::Handled);
+        }
+
+        if let Some(form) = self.form.as_mut() {
+            match key.code {
+                KeyCode::Esc => {
+                    self.form = None;
+                    self.error = None;
+                }
+                KeyCode::Enter => self.save_form(commander),
+                KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    self.save_form(commander);
+                }
+                // The name of an existing remote is not editable
+                KeyCode::Tab if form.existing.is_none() => form.editing_url = !form.editing_url,
+                _ if form.editing_url => {
+                    form.url.input(event);
+                }
+                _ if form.existing.is_none() => {
+                    form.name.input(event);
+                }
+                _ => {}
+            }
+            return Ok(ComponentInputResult::Handled);
+        }
+
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => self.scroll(1),
+            KeyCode::Char('k') | KeyCode::Up => self.scroll(-1),
+            KeyCode::Char('J') => self.scroll(self.list_height as isize / 2),
+            KeyCode::Char('K') => self.scroll((self.list_height as isize / 2).saturating_neg()),
+            KeyCode::Char('a' | 'e' | 'd') if self.fetch_picker => {}
+            KeyCode::Char('a') => {
+                self.form = Some(RemoteForm {
+                    name: TextArea::default(),
+                    url: TextArea::default(),
+                    editing_url: false,
+                    existing: None,
+                });
+            }
+            KeyCode::Char('e') => {
+                if let Some(remote) = self.selected_remote() {
+                    let mut url = TextArea::new(vec![remote.url.clone()]);
+                    url.move_cursor(CursorMove::End);
+                    self.form = Some(RemoteForm {
+                        name: TextArea::new(vec![remote.name.clone()]),
+                        url,
+                        editing_url: true,
+                        existing: Some(remote.name.clone()),
+                    });
+                }
+            }
+            KeyCode::Char('d') => {
+                self.removing = self.selected_remote().map(|remote| remote.name.clone());
+            }
+            KeyCode::Char('f') | KeyCode::Enter => {
+                if let Some(remote) = self.selected_remote() {
+                    return Ok(ComponentInputResult::HandledAction(
+                        ComponentAction::Multiple(vec![
+                            ComponentAction::SetPopup(None),
+                            ComponentAction::Fetch(FetchTarget::Remote(remote.name.clone())),
+                        ]),
+                    ));
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('q') => return Ok(ComponentInputResult::NotHandled),
+            _ => {}
+        }
+
+        Ok(ComponentInputResult::Handled)
+    }
+}
@@ -0,0 +1,141 @@
+use anyhow::Result;
+use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+};
+use tui_textarea::{CursorMove, TextArea};
+
+use crate::{
+    ComponentInputResult,
+    commander::{Commander, sparse::is_full_checkout},
+    ui::{Component, ComponentAction, styles::create_popup_block, utils::centered_rect},
+};
+
+/// Popup to edit the sparse patterns of the working copy, one pattern per line.
+/// Saving replaces all patterns, so the working copy only has the matching files.
+pub struct SparsePopup<'a> {
+    patterns_textarea: TextArea<'a>,
+    /// Whether the working copy had all files when the popup opened
+    full_checkout: bool,
+    /// Error of the last save, the patterns are kept to fix them
+    error: Option<String>,
+}
+
+impl SparsePopup<'_> {
+    pub fn new(commander: &mut Commander) -> Result<Self> {
+        let patterns = commander.get_sparse_patterns()?;
+        let full_checkout = is_full_checkout(&patterns);
+        let mut patterns_textarea = TextArea::new(patterns);
+        patterns_textarea.move_cursor(CursorMove::Bottom);
+        patterns_textarea.move_cursor(CursorMove::End);
+
+        Ok(Self {
+            patterns_textarea,
+            full_checkout,
+            error: None,
+        })
+    }
+
+    fn save(&mut self, commander: &mut Commander) -> ComponentInputResult {
+        let patterns: Vec<String> = self
+            .patterns_textarea
+            .lines()
+            .iter()
+            .map(|line| line.trim().to_owned())
+            .filter(|line| !line.is_empty())
+            .collect();
+        if patterns.is_empty() {
+            self.error =
+                Some("Add at least one pattern, `.` materializes the whole repo".to_owned());
+            return ComponentInputResult::Handled;
+        }
+
+        match commander.set_sparse_patterns(&patterns) {
+            // The files of the working copy changed
+            Ok(()) => ComponentInputResult::HandledAction(ComponentAction::Multiple(vec![
+                ComponentAction::SetPopup(None),
+                ComponentAction::RefreshTab(),
+            ])),
+            Err(err) => {
+                self.error = Some(format!("{err:#}"));
+                ComponentInputResult::Handled
+            }
+        }
+    }
+}
+
+impl Component for SparsePopup<'_> {
+    fn draw(&mut self, f: &mut ratatui::prelude::Frame<'_>, area: Rect) -> Result<()> {
+        let block = create_popup_block("Sparse patterns");
+        let area = centered_rect(area, 50, 50);
+        f.render_widget(Clear, area);
+        f.render_widget(&block, area);
+
+        let popup_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(if self.full_checkout { 3 } else { 0 }),
+                Constraint::Fill(1),
+                Constraint::Length(if self.error.is_some() { 2 } else { 0 }),
+                Constraint::Length(2),
+            ])
+            .split(block.inner(area));
+
+        if self.full_checkout {
+            f.render_widget(
+                Paragraph::new(
+                    "This working copy isn't sparse, `.` materializes the whole repo. \
+                     Replace it with the paths to keep, one per line.",
+                )
+                .fg(Color::Yellow)
+                .wrap(Wrap { trim: false }),
+                popup_chunks[0],
+            );
+        }
+
+        f.render_widget(&self.patterns_textarea, popup_chunks[1]);
+
+        if let Some(error) = self.error.as_ref() {
+            f.render_widget(
+                Paragraph::new(error.as_str())
+                    .fg(Color::Red)
+                    .wrap(Wrap { trim: false }),
+                popup_chunks[2],
+            );
+        }
+
+        let help = Paragraph::new(vec![
+            "Enter: new line | Ctrl+s: save | Escape: cancel".into(),
+        ])
+        .fg(Color::DarkGray)
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::TOP)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+        f.render_widget(help, popup_chunks[3]);
+
+        Ok(())
+    }
+
+    fn input(&mut self, commander: &mut Commander, event: Event) -> Result<ComponentInputResult> {
+        if let Event::Key(key) = event
+            && key.kind == KeyEventKind::Press
+        {
+            match key.code {
+                KeyCode::Esc => return Ok(ComponentInputResult::NotHandled),
+                KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(self.save(commander));
+                }
+                _ => {}
+            }
+        }
+
+        self.patterns_textarea.input(event);
+        Ok(ComponentInputResult::Handled)
+    }
+}
@@ -0,0 +1,108 @@
+use ansi_to_tui::IntoText;
+use anyhow::Result;
+use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEventKind},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+use crate::{
+    ComponentInputResult,
+    commander::{Commander, files::File, log::Head},
+    env::Config,
+    ui::{
+        Component, ComponentAction, message_popup::MessagePopup, panel::FilePickerPanel,
+        styles::create_popup_block, utils::centered_rect,
+    },
+};
+
+/// Popup to pick which files of a change go into the first of the two changes it is split into.
+pub struct SplitFilesPopup {
+    head: Head,
+    files: FilePickerPanel,
+    config: Config,
+}
+
+impl SplitFilesPopup {
+    pub fn new(head: Head, files: Vec<File>, config: Config) -> Self {
+        Self {
+            head,
+            files: FilePickerPanel::new(files, &[]),
+            config,
+        }
+    }
+
+    fn split(&self, commander: &mut Commander) -> Result<ComponentInputResult> {
+        let paths = self.files.checked_paths();
+        if paths.is_empty() {
+            return Ok(ComponentInputResult::Handled);
+        }
+
+        let action = match commander.run_split(&self.head.commit_id, &paths) {
+            Ok(()) => ComponentAction::Multiple(vec![
+                ComponentAction::SetPopup(None),
+                ComponentAction::RefreshTab(),
+            ]),
+            Err(err) => ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                title: "Split".into(),
+                messages: format!("{err:#}").into_text()?,
+                text_align: None,
+            }))),
+        };
+        Ok(ComponentInputResult::HandledAction(action))
+    }
+}
+
+impl Component for SplitFilesPopup {
+    fn draw(&mut self, f: &mut ratatui::prelude::Frame<'_>, area: Rect) -> Result<()> {
+        let block = create_popup_block("Split change");
+        let area = centered_rect(area, 60, 60);
+        f.render_widget(Clear, area);
+        f.render_widget(&block, area);
+
+        let popup_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Fill(1), Constraint::Length(2)])
+            .split(block.inner(area));
+
+        self.files.draw(
+            f,
+            popup_chunks[0],
+            "Files to move into the first change",
+            Style::default().fg(Color::DarkGray),
+            self.config.highlight_color(),
+        );
+
+        let help = Paragraph::new(vec![
+            "j/k: select | Space: toggle | a: toggle all | Enter: split | Escape: cancel".into(),
+        ])
+        .fg(Color::DarkGray)
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::TOP)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+        f.render_widget(help, popup_chunks[1]);
+
+        Ok(())
+    }
+
+    fn input(&mut self, commander: &mut Commander, event: Event) -> Result<ComponentInputResult> {
+        if let Event::Key(key) = event
+            && key.kind == KeyEventKind::Press
+        {
+            match key.code {
+                KeyCode::Esc | KeyCode::Char('q') => return Ok(ComponentInputResult::NotHandled),
+                KeyCode::Enter => return self.split(commander),
+                _ => {
+                    self.files.input(key);
+                }
+            }
+        }
+
+        Ok(ComponentInputResult::Handled)
+    }
+}
@@ -0,0 +1,119 @@
+use anyhow::Result;
+use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Line,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+use tui_textarea::TextArea;
+
+use crate::{
+    ComponentInputResult,
+    commander::{Commander, log::Head},
+    ui::{Component, ComponentAction, styles::create_popup_block, utils::centered_rect},
+};
+
+/// Maximum number of paths listed in the popup
+const MAX_PATHS_SHOWN: usize = 5;
+
+/// Popup prompting for the description of a new change made of files split out of a change.
+pub struct SplitPopup<'a> {
+    head: Head,
+    paths: Vec<String>,
+    describe_textarea: TextArea<'a>,
+}
+
+impl SplitPopup<'_> {
+    pub fn new(head: Head, paths: Vec<String>) -> Self {
+        Self {
+            head,
+            paths,
+            describe_textarea: TextArea::default(),
+        }
+    }
+}
+
+impl Component for SplitPopup<'_> {
+    fn draw(&mut self, f: &mut ratatui::prelude::Frame<'_>, area: Rect) -> Result<()> {
+        let block = create_popup_block("Split files into a new change");
+        let area = centered_rect(area, 50, 50);
+        f.render_widget(Clear, area);
+        f.render_widget(&block, area);
+
+        let mut paths: Vec<Line> = self
+            .paths
+            .iter()
+            .take(MAX_PATHS_SHOWN)
+            .map(|path| Line::raw(path.clone()).fg(Color::Cyan))
+            .collect();
+        if self.paths.len() > MAX_PATHS_SHOWN {
+            paths.push(
+                Line::raw(format!("and {} more", self.paths.len() - MAX_PATHS_SHOWN))
+                    .fg(Color::DarkGray),
+            );
+        }
+        let paths_height = paths.len() as u16 + 1;
+
+        let popup_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(paths_height),
+                Constraint::Fill(1),
+                Constraint::Length(2),
+            ])
+            .split(block.inner(area));
+
+        f.render_widget(Paragraph::new(paths), popup_chunks[0]);
+
+        self.describe_textarea.set_block(
+            Block::bordered()
+                .title(" Description ")
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+        f.render_widget(&self.describe_textarea, popup_chunks[1]);
+
+        let help = Paragraph::new(vec!["Ctrl+s: split | Escape: cancel".into()])
+            .fg(Color::DarkGray)
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::TOP)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(Color::DarkGray)),
+            );
+        f.render_widget(help, popup_chunks[2]);
+
+        Ok(())
+    }
+
+    fn input(&mut self, commander: &mut Commander, event: Event) -> Result<ComponentInputResult> {
+        if let Event::Key(key) = event
+            && key.kind == KeyEventKind::Press
+        {
+            match key.code {
+                KeyCode::Esc => return Ok(ComponentInputResult::NotHandled),
+                KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    let paths: Vec<&str> = self.paths.iter().map(String::as_str).collect();
+                    commander.run_split_paths(
+                        &self.head.commit_id,
+                        &paths,
+                        &self.describe_textarea.lines().join("\n"),
+                    )?;
+                    let head = commander.get_head_latest(&self.head)?;
+                    return Ok(ComponentInputResult::HandledAction(
+                        ComponentAction::Multiple(vec![
+                            ComponentAction::SetPopup(None),
+                            ComponentAction::ChangeHead(head),
+                        ]),
+                    ));
+                }
+                _ => {}
+            }
+        }
+
+        self.describe_textarea.input(event);
+        Ok(ComponentInputResult::Handled)
+    }
+}
@@ -0,0 +1,209 @@
+use ansi_to_tui::IntoText;
+use anyhow::Result;
+use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEventKind, KeyModifiers},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::Text,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+use tui_textarea::{CursorMove, TextArea};
+
+use crate::{
+    ComponentInputResult,
+    commander::{Commander, files::File, log::Head},
+    env::{Config, DiffFormat},
+    ui::{
+        Component, ComponentAction, message_popup::MessagePopup, panel::FilePickerPanel,
+        styles::create_popup_block, utils::centered_rect,
+    },
+};
+
+/// Popup to pick which files of a change get squashed, and into which revision.
+/// The diff of the highlighted file is shown next to the list.
+pub struct SquashFilesPopup<'a> {
+    head: Head,
+    files: FilePickerPanel,
+    destination: TextArea<'a>,
+    editing_destination: bool,
+    diff_format: DiffFormat,
+    diff: Option<Text<'static>>,
+    config: Config,
+}
+
+impl SquashFilesPopup<'_> {
+    /// Create the popup with the files at `checked_paths` checked, squashing into the parent
+    /// of the change by default
+    pub fn new(
+        commander: &mut Commander,
+        head: Head,
+        files: Vec<File>,
+        checked_paths: &[String],
+        diff_format: DiffFormat,
+    ) -> Result<Self> {
+        let mut destination = TextArea::new(vec![format!("{}-", head.change_id.as_str())]);
+        destination.move_cursor(CursorMove::End);
+
+        let mut popup = Self {
+            head,
+            files: FilePickerPanel::new(files, checked_paths),
+            destination,
+            editing_destination: false,
+            diff_format,
+            diff: None,
+            config: commander.env.config.clone(),
+        };
+        popup.refresh_diff(commander)?;
+        Ok(popup)
+    }
+
+    fn refresh_diff(&mut self, commander: &mut Commander) -> Result<()> {
+        self.diff = match self.files.selected_file() {
+            Some(file) => {
+                match commander.get_file_diff(&self.head, file, &self.diff_format, true) {
+                    Ok(diff) => diff.map(|diff| diff.into_text()).transpose()?,
+                    Err(err) => Some(err.into_text("Error getting diff")?),
+                }
+            }
+            None => None,
+        };
+        Ok(())
+    }
+
+    fn destination(&self) -> String {
+        self.destination.lines().join("\n").trim().to_owned()
+    }
+
+    fn squash(&self, commander: &mut Commander) -> Result<ComponentInputResult> {
+        let paths = self.files.checked_paths();
+        let destination = self.destination();
+        // Nothing to confirm without files or a destination
+        if paths.is_empty() || destination.is_empty() {
+            return Ok(ComponentInputResult::Handled);
+        }
+
+        let action = match commander.run_squash_paths(&self.head.commit_id, &destination, &paths) {
+            Ok(()) => ComponentAction::Multiple(vec![
+                ComponentAction::SetPopup(None),
+                ComponentAction::RefreshTab(),
+            ]),
+            Err(err) => ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+                title: "Squash".into(),
+                messages: format!("{err:#}").into_text()?,
+                text_align: None,
+            }))),
+        };
+        Ok(ComponentInputResult::HandledAction(action))
+    }
+}
+
+impl Component for SquashFilesPopup<'_> {
+    fn draw(&mut self, f: &mut ratatui::prelude::Frame<'_>, area: Rect) -> Result<()> {
+        let block = create_popup_block("Squash files");
+        let area = centered_rect(area, 80, 80);
+        f.render_widget(Clear, area);
+        f.render_widget(&block, area);
+
+        let popup_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3),
+                Constraint::Fill(1),
+                Constraint::Length(2),
+            ])
+            .split(block.inner(area));
+
+        let focused_style = |focused: bool| {
+            Style::default().fg(if focused {
+                Color::Green
+            } else {
+                Color::DarkGray
+            })
+        };
+
+        self.destination.set_block(
+            Block::bordered()
+                .title(" Squash into ")
+                .border_type(BorderType::Rounded)
+                .border_style(focused_style(self.editing_destination)),
+        );
+        self.destination
+            .set_cursor_style(if self.editing_destination {
+                Style::default().reversed()
+            } else {
+                Style::default()
+            });
+        f.render_widget(&self.destination, popup_chunks[0]);
+
+        let body_chunks = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .split(popup_chunks[1]);
+
+        self.files.draw(
+            f,
+            body_chunks[0],
+            "Files to squash",
+            focused_style(!self.editing_destination),
+            self.config.highlight_color(),
+        );
+
+        let diff = Paragraph::new(self.diff.clone().unwrap_or_default()).block(
+            Block::bordered()
+                .title(" Diff ")
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+        f.render_widget(diff, body_chunks[1]);
+
+        let confirm = if self.files.checked_paths().is_empty() {
+            "Enter: squash (no files selected)"
+        } else {
+            "Enter: squash"
+        };
+        let help = Paragraph::new(vec![
+            format!(
+                "j/k: select | Space: toggle | a: toggle all | Tab: edit destination | {confirm} | Escape: cancel"
+            )
+            .into(),
+        ])
+        .fg(Color::DarkGray)
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::TOP)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+        f.render_widget(help, popup_chunks[2]);
+
+        Ok(())
+    }
+
+    fn input(&mut self, commander: &mut Commander, event: Event) -> Result<ComponentInputResult> {
+        if let Event::Key(key) = event
+            && key.kind == KeyEventKind::Press
+        {
+            match key.code {
+                KeyCode::Esc => return Ok(ComponentInputResult::NotHandled),
+                KeyCode::Tab => self.editing_destination = !self.editing_destination,
+                KeyCode::Enter => return self.squash(commander),
+                KeyCode::Char('s') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return self.squash(commander);
+                }
+                _ if self.editing_destination => {
+                    self.destination.input(event);
+                }
+                KeyCode::Char('q') => return Ok(ComponentInputResult::NotHandled),
+                _ => {
+                    let selected = self.files.selected();
+                    if self.files.input(key) && self.files.selected() != selected {
+                        self.refresh_diff(commander)?;
+                    }
+                }
+            }
+        }
+
+        Ok(ComponentInputResult::Handled)
+    }
+}
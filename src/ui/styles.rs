@@ -1,12 +1,15 @@
 use std::sync::LazyLock;
 
 use ratatui::{
+    buffer::Buffer,
     layout::Alignment,
-    style::{Color, Style, Stylize},
+    style::{Color, Modifier, Style, Stylize},
     text::Span,
     widgets::{Block, BorderType, Padding},
 };
 
+use crate::env::{Config, Theme, ThemeColors};
+
 pub static POPUP_BLOCK: LazyLock<Block<'static>> = LazyLock::new(|| {
     Block::<'static>::bordered()
         .padding(Padding::horizontal(1))
@@ -21,3 +24,157 @@ pub fn create_popup_block(title: &str) -> Block<'_> {
         .title(Span::styled(format!(" {title} "), *POPUP_BLOCK_TITLE_STYLE))
         .title_alignment(Alignment::Center)
 }
+
+/// Characters drawing borders. The log graph uses them too
+const LINE_SYMBOLS: &[&str] = &[
+    "─", "│", "╭", "╮", "╰", "╯", "┌", "┐", "└", "┘", "├", "┤", "┬", "┴", "┼",
+];
+
+/// Apply `lazyjj.color`, `lazyjj.colors` and `lazyjj.theme` to a rendered buffer
+pub fn apply_color_config(buffer: &mut Buffer, config: &Config) {
+    if !config.color() {
+        strip_colors(buffer);
+        return;
+    }
+    apply_theme_colors(buffer, config.theme_colors(), config.highlight_color());
+    match config.theme() {
+        Theme::Default => {}
+        Theme::HighContrast => apply_high_contrast(buffer),
+    }
+}
+
+/// Recolor borders, dimmed help text and highlighted cells of a rendered buffer.
+/// Diff colors are applied by the details panel, as other text uses red and green too.
+fn apply_theme_colors(buffer: &mut Buffer, colors: ThemeColors, highlight_color: Color) {
+    if colors == ThemeColors::default() {
+        return;
+    }
+    for cell in buffer.content.iter_mut() {
+        let (fg, bg) = if cell.bg == highlight_color {
+            (colors.selected_fg, None)
+        } else if LINE_SYMBOLS.contains(&cell.symbol()) {
+            (colors.border_fg, colors.border_bg)
+        } else if cell.fg == Color::DarkGray {
+            (colors.help_fg, colors.help_bg)
+        } else {
+            (None, None)
+        };
+        if let Some(fg) = fg {
+            cell.fg = fg;
+        }
+        if let Some(bg) = bg {
+            cell.bg = bg;
+        }
+    }
+}
+
+/// Remove all colors from a rendered buffer, for `NO_COLOR` and `lazyjj.color = false`.
+/// Highlighted cells are shown reversed and dimmed text stays dimmed, so the UI stays usable.
+fn strip_colors(buffer: &mut Buffer) {
+    for cell in buffer.content.iter_mut() {
+        if cell.bg != Color::Reset {
+            cell.modifier.insert(Modifier::REVERSED);
+        }
+        if cell.fg == Color::DarkGray {
+            cell.modifier.insert(Modifier::DIM);
+        }
+        cell.fg = Color::Reset;
+        cell.bg = Color::Reset;
+    }
+}
+
+/// Map the colors of a rendered buffer, including jj's diff colors, to a high-contrast palette
+fn apply_high_contrast(buffer: &mut Buffer) {
+    for cell in buffer.content.iter_mut() {
+        if cell.bg != Color::Reset {
+            cell.fg = Color::Black;
+            cell.bg = Color::White;
+            cell.modifier.insert(Modifier::BOLD);
+        } else {
+            cell.fg = high_contrast_color(cell.fg);
+        }
+    }
+}
+
+/// Bright variant of a foreground color, readable on a dark terminal background
+fn high_contrast_color(color: Color) -> Color {
+    match color {
+        Color::Reset | Color::Black => color,
+        Color::Red | Color::LightRed => Color::LightRed,
+        Color::Green | Color::LightGreen => Color::LightGreen,
+        Color::Yellow | Color::LightYellow => Color::LightYellow,
+        // Blue is hard to read on black
+        Color::Blue | Color::LightBlue | Color::Cyan | Color::LightCyan => Color::LightCyan,
+        Color::Magenta | Color::LightMagenta => Color::LightMagenta,
+        Color::Gray | Color::DarkGray | Color::White | Color::Rgb(..) | Color::Indexed(_) => {
+            Color::White
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ratatui::layout::Rect;
+
+    use super::*;
+
+    #[test]
+    fn strip_colors_keeps_highlight_visible() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 3, 1));
+        buffer.set_string(0, 0, "a", Style::new().fg(Color::Red));
+        buffer.set_string(1, 0, "b", Style::new().bg(Color::Blue));
+        buffer.set_string(2, 0, "c", Style::new().fg(Color::DarkGray));
+
+        strip_colors(&mut buffer);
+
+        let mut expected = Buffer::empty(Rect::new(0, 0, 3, 1));
+        expected.set_string(0, 0, "a", Style::new());
+        expected.set_string(1, 0, "b", Style::new().reversed());
+        expected.set_string(2, 0, "c", Style::new().dim());
+        assert_eq!(buffer, expected);
+    }
+
+    #[test]
+    fn theme_colors_recolor_borders_help_and_selection() {
+        let highlight_color = Color::Rgb(50, 50, 150);
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 4, 1));
+        buffer.set_string(0, 0, "│", Style::new());
+        buffer.set_string(1, 0, "h", Style::new().fg(Color::DarkGray));
+        buffer.set_string(2, 0, "s", Style::new().bg(highlight_color));
+        buffer.set_string(3, 0, "x", Style::new().fg(Color::Red));
+
+        apply_theme_colors(
+            &mut buffer,
+            ThemeColors {
+                border_fg: Some(Color::Blue),
+                help_fg: Some(Color::Gray),
+                selected_fg: Some(Color::White),
+                ..ThemeColors::default()
+            },
+            highlight_color,
+        );
+
+        let mut expected = Buffer::empty(Rect::new(0, 0, 4, 1));
+        expected.set_string(0, 0, "│", Style::new().fg(Color::Blue));
+        expected.set_string(1, 0, "h", Style::new().fg(Color::Gray));
+        expected.set_string(2, 0, "s", Style::new().fg(Color::White).bg(highlight_color));
+        expected.set_string(3, 0, "x", Style::new().fg(Color::Red));
+        assert_eq!(buffer, expected);
+    }
+
+    #[test]
+    fn high_contrast_brightens_colors() {
+        let mut buffer = Buffer::empty(Rect::new(0, 0, 3, 1));
+        buffer.set_string(0, 0, "a", Style::new().fg(Color::Red));
+        buffer.set_string(1, 0, "b", Style::new().bg(Color::Rgb(50, 50, 150)));
+        buffer.set_string(2, 0, "c", Style::new().fg(Color::DarkGray));
+
+        apply_high_contrast(&mut buffer);
+
+        let mut expected = Buffer::empty(Rect::new(0, 0, 3, 1));
+        expected.set_string(0, 0, "a", Style::new().fg(Color::LightRed));
+        expected.set_string(1, 0, "b", Style::new().black().on_white().bold());
+        expected.set_string(2, 0, "c", Style::new().fg(Color::White));
+        assert_eq!(buffer, expected);
+    }
+}
@@ -0,0 +1,412 @@
+/*! Lightweight syntax highlighting for code shown in diffs.
+
+Lines are highlighted one at a time, so strings and comments spanning several lines are
+only recognized on their first line. This keeps highlighting fast enough to run on every
+render, without pulling in a full grammar engine.
+*/
+
+use ratatui::{
+    style::{Color, Style},
+    text::Line,
+};
+
+use crate::ui::utils::{line_string, style_chars};
+
+/// Lines longer than this are not highlighted, they are usually generated or minified
+const MAX_LINE_LENGTH: usize = 500;
+
+/// How to recognize the tokens of a language
+pub struct Language {
+    line_comments: &'static [&'static str],
+    quotes: &'static [char],
+    keywords: &'static [&'static str],
+}
+
+const RUST: Language = Language {
+    line_comments: &["//"],
+    quotes: &['"'],
+    keywords: &[
+        "as", "async", "await", "break", "const", "continue", "crate", "dyn", "else", "enum",
+        "false", "fn", "for", "if", "impl", "in", "let", "loop", "match", "mod", "move", "mut",
+        "pub", "ref", "return", "self", "Self", "static", "struct", "super", "trait", "true",
+        "type", "unsafe", "use", "where", "while",
+    ],
+};
+
+const PYTHON: Language = Language {
+    line_comments: &["#"],
+    quotes: &['"', '\''],
+    keywords: &[
+        "and", "as", "assert", "async", "await", "break", "class", "continue", "def", "del",
+        "elif", "else", "except", "False", "finally", "for", "from", "global", "if", "import",
+        "in", "is", "lambda", "None", "nonlocal", "not", "or", "pass", "raise", "return", "True",
+        "try", "while", "with", "yield",
+    ],
+};
+
+const JAVASCRIPT: Language = Language {
+    line_comments: &["//"],
+    quotes: &['"', '\'', '`'],
+    keywords: &[
+        "async",
+        "await",
+        "break",
+        "case",
+        "catch",
+        "class",
+        "const",
+        "continue",
+        "default",
+        "delete",
+        "do",
+        "else",
+        "export",
+        "extends",
+        "false",
+        "finally",
+        "for",
+        "from",
+        "function",
+        "if",
+        "import",
+        "in",
+        "instanceof",
+        "interface",
+        "let",
+        "new",
+        "null",
+        "return",
+        "switch",
+        "this",
+        "throw",
+        "true",
+        "try",
+        "type",
+        "typeof",
+        "undefined",
+        "var",
+        "void",
+        "while",
+        "yield",
+    ],
+};
+
+const GO: Language = Language {
+    line_comments: &["//"],
+    quotes: &['"', '`'],
+    keywords: &[
+        "break",
+        "case",
+        "chan",
+        "const",
+        "continue",
+        "default",
+        "defer",
+        "else",
+        "false",
+        "for",
+        "func",
+        "go",
+        "goto",
+        "if",
+        "import",
+        "interface",
+        "map",
+        "nil",
+        "package",
+        "range",
+        "return",
+        "select",
+        "struct",
+        "switch",
+        "true",
+        "type",
+        "var",
+    ],
+};
+
+const C: Language = Language {
+    line_comments: &["//"],
+    quotes: &['"'],
+    keywords: &[
+        "auto",
+        "bool",
+        "break",
+        "case",
+        "char",
+        "class",
+        "const",
+        "continue",
+        "default",
+        "delete",
+        "do",
+        "double",
+        "else",
+        "enum",
+        "extern",
+        "false",
+        "float",
+        "for",
+        "if",
+        "int",
+        "long",
+        "namespace",
+        "new",
+        "nullptr",
+        "private",
+        "protected",
+        "public",
+        "return",
+        "short",
+        "signed",
+        "sizeof",
+        "static",
+        "struct",
+        "switch",
+        "template",
+        "this",
+        "true",
+        "typedef",
+        "union",
+        "unsigned",
+        "using",
+        "virtual",
+        "void",
+        "while",
+    ],
+};
+
+const JAVA: Language = Language {
+    line_comments: &["//"],
+    quotes: &['"'],
+    keywords: &[
+        "abstract",
+        "assert",
+        "boolean",
+        "break",
+        "byte",
+        "case",
+        "catch",
+        "char",
+        "class",
+        "const",
+        "continue",
+        "default",
+        "do",
+        "double",
+        "else",
+        "enum",
+        "extends",
+        "false",
+        "final",
+        "finally",
+        "float",
+        "for",
+        "goto",
+        "if",
+        "implements",
+        "import",
+        "instanceof",
+        "int",
+        "interface",
+        "long",
+        "native",
+        "new",
+        "null",
+        "package",
+        "private",
+        "protected",
+        "public",
+        "record",
+        "return",
+        "short",
+        "static",
+        "super",
+        "switch",
+        "synchronized",
+        "this",
+        "throw",
+        "throws",
+        "transient",
+        "true",
+        "try",
+        "var",
+        "void",
+        "volatile",
+        "while",
+    ],
+};
+
+const KOTLIN: Language = Language {
+    line_comments: &["//"],
+    quotes: &['"'],
+    keywords: &[
+        "abstract",
+        "as",
+        "break",
+        "by",
+        "catch",
+        "class",
+        "companion",
+        "continue",
+        "data",
+        "do",
+        "else",
+        "enum",
+        "false",
+        "finally",
+        "for",
+        "fun",
+        "if",
+        "import",
+        "in",
+        "init",
+        "interface",
+        "internal",
+        "is",
+        "lateinit",
+        "null",
+        "object",
+        "open",
+        "override",
+        "package",
+        "private",
+        "protected",
+        "public",
+        "return",
+        "sealed",
+        "super",
+        "suspend",
+        "this",
+        "throw",
+        "true",
+        "try",
+        "typealias",
+        "val",
+        "var",
+        "when",
+        "while",
+    ],
+};
+
+const SHELL: Language = Language {
+    line_comments: &["#"],
+    quotes: &['"', '\''],
+    keywords: &[
+        "case", "do", "done", "elif", "else", "esac", "export", "fi", "for", "function", "if",
+        "in", "local", "return", "then", "until", "while",
+    ],
+};
+
+const CONFIG: Language = Language {
+    line_comments: &["#"],
+    quotes: &['"', '\''],
+    keywords: &["false", "null", "true"],
+};
+
+impl Language {
+    /// Language of a file, from its extension
+    pub fn for_path(path: &str) -> Option<&'static Language> {
+        let extension = path.rsplit_once('.')?.1;
+        match extension {
+            "rs" => Some(&RUST),
+            "py" => Some(&PYTHON),
+            "js" | "jsx" | "mjs" | "ts" | "tsx" => Some(&JAVASCRIPT),
+            "go" => Some(&GO),
+            "c" | "h" | "cc" | "cpp" | "hpp" => Some(&C),
+            "java" => Some(&JAVA),
+            "kt" | "kts" => Some(&KOTLIN),
+            "sh" | "bash" | "zsh" => Some(&SHELL),
+            "toml" | "yaml" | "yml" => Some(&CONFIG),
+            _ => None,
+        }
+    }
+}
+
+fn is_word(c: char) -> bool {
+    c.is_alphanumeric() || c == '_'
+}
+
+/// Color the keywords, strings, numbers and comments of a line of code, starting at character
+/// `start`. Earlier characters, like diff markers, are left as is.
+pub fn highlight_line<'a>(line: Line<'a>, start: usize, language: &Language) -> Line<'a> {
+    let chars: Vec<char> = line_string(&line).chars().collect();
+    if chars.len() > MAX_LINE_LENGTH {
+        return line;
+    }
+
+    let mut tokens = vec![];
+    let mut index = start;
+    while index < chars.len() {
+        let c = chars[index];
+        let rest = &chars[index..];
+        if language.line_comments.iter().any(|comment| {
+            let comment: Vec<char> = comment.chars().collect();
+            rest.starts_with(&comment)
+        }) {
+            tokens.push((index..chars.len(), Color::DarkGray));
+            break;
+        }
+
+        let end = if language.quotes.contains(&c) {
+            let mut end = index + 1;
+            while end < chars.len() && chars[end] != c {
+                end += if chars[end] == '\\' { 2 } else { 1 };
+            }
+            let end = (end + 1).min(chars.len());
+            tokens.push((index..end, Color::Yellow));
+            end
+        } else if is_word(c) {
+            let end = index + rest.iter().take_while(|c| is_word(**c)).count();
+            let word: String = chars[index..end].iter().collect();
+            if c.is_ascii_digit() {
+                tokens.push((index..end, Color::Cyan));
+            } else if language.keywords.contains(&word.as_str()) {
+                tokens.push((index..end, Color::Magenta));
+            }
+            end
+        } else {
+            index + 1
+        };
+        index = end;
+    }
+
+    tokens.into_iter().fold(line, |line, (range, color)| {
+        style_chars(line, range, Style::new().fg(color))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn highlight_line_colors_tokens() {
+        let line = highlight_line(Line::raw(r#"+let s = "a\"b" + 42; // done"#), 1, &RUST);
+        let colored: Vec<(&str, Option<Color>)> = line
+            .spans
+            .iter()
+            .filter(|span| span.style.fg.is_some())
+            .map(|span| (span.content.as_ref(), span.style.fg))
+            .collect();
+        assert_eq!(
+            colored,
+            vec![
+                ("let", Some(Color::Magenta)),
+                (r#""a\"b""#, Some(Color::Yellow)),
+                ("42", Some(Color::Cyan)),
+                ("// done", Some(Color::DarkGray)),
+            ]
+        );
+    }
+
+    #[test]
+    fn for_path_uses_extension() {
+        assert!(Language::for_path("src/main.rs").is_some());
+        assert!(
+            Language::for_path("Main.java").is_some_and(|java| !java.keywords.contains(&"fun"))
+        );
+        assert!(Language::for_path("Makefile").is_none());
+        assert!(Language::for_path("notes.txt").is_none());
+    }
+}
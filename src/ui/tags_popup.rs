@@ -0,0 +1,143 @@
+use anyhow::Result;
+use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEventKind},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListState, Paragraph},
+};
+
+use crate::{
+    ComponentInputResult,
+    commander::{
+        Commander,
+        tags::{Tag, is_tags_unsupported},
+    },
+    env::Config,
+    ui::{Component, ComponentAction, styles::create_popup_block, utils::centered_rect},
+};
+
+/// Popup listing the tags of the repo, to view the change of a tag in the log
+pub struct TagsPopup {
+    tags: Result<Vec<Tag>, String>,
+    list_state: ListState,
+    list_height: u16,
+    config: Config,
+}
+
+impl TagsPopup {
+    pub fn new(config: Config, commander: &mut Commander) -> Self {
+        Self {
+            tags: commander.get_tags().map_err(|err| {
+                if is_tags_unsupported(&err) {
+                    "Listing tags is not supported by this version of jj".to_owned()
+                } else {
+                    err.to_string()
+                }
+            }),
+            list_state: ListState::default().with_selected(Some(0)),
+            list_height: 0,
+            config,
+        }
+    }
+
+    fn scroll(&mut self, scroll: isize) {
+        let len = self.tags.as_ref().map_or(0, |tags| tags.len());
+        self.list_state.select(Some(
+            self.list_state
+                .selected()
+                .map(|selected| selected.saturating_add_signed(scroll))
+                .unwrap_or(0)
+                .min(len.saturating_sub(1)),
+        ));
+    }
+
+    fn selected_tag(&self) -> Option<&Tag> {
+        self.tags
+            .as_ref()
+            .ok()
+            .and_then(|tags| tags.get(self.list_state.selected()?))
+    }
+}
+
+impl Component for TagsPopup {
+    fn draw(&mut self, f: &mut ratatui::prelude::Frame<'_>, area: Rect) -> Result<()> {
+        let block = create_popup_block("Tags");
+        let area = centered_rect(area, 60, 60);
+        f.render_widget(Clear, area);
+        f.render_widget(&block, area);
+
+        let popup_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Fill(1), Constraint::Length(2)])
+            .split(block.inner(area));
+        self.list_height = popup_chunks[0].height;
+
+        let items: Vec<Line> = match self.tags.as_ref() {
+            Ok(tags) if tags.is_empty() => vec![Line::from("No tags").fg(Color::DarkGray)],
+            Ok(tags) => tags
+                .iter()
+                .map(|tag| {
+                    Line::from(vec![
+                        Span::raw(tag.name.clone()).fg(Color::Yellow),
+                        Span::raw(" "),
+                        Span::raw(tag.target.clone()),
+                    ])
+                })
+                .collect(),
+            Err(err) => vec![Line::from(err.clone()).fg(Color::Red)],
+        };
+        let list = List::new(items)
+            .scroll_padding(3)
+            .highlight_style(Style::default().bg(self.config.highlight_color()));
+        f.render_stateful_widget(list, popup_chunks[0], &mut self.list_state);
+
+        let help = Paragraph::new(vec![
+            "j/k: scroll | Enter: view in log | Escape: close".into(),
+        ])
+        .fg(Color::DarkGray)
+        .alignment(Alignment::Center)
+        .block(
+            Block::default()
+                .borders(Borders::TOP)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        );
+        f.render_widget(help, popup_chunks[1]);
+
+        Ok(())
+    }
+
+    fn input(&mut self, commander: &mut Commander, event: Event) -> Result<ComponentInputResult> {
+        let Event::Key(key) = event else {
+            return Ok(ComponentInputResult::Handled);
+        };
+        if key.kind != KeyEventKind::Press {
+            return Ok(ComponentInputResult::Handled);
+        }
+
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => self.scroll(1),
+            KeyCode::Char('k') | KeyCode::Up => self.scroll(-1),
+            KeyCode::Char('J') => self.scroll(self.list_height as isize / 2),
+            KeyCode::Char('K') => self.scroll((self.list_height as isize / 2).saturating_neg()),
+            KeyCode::Enter => {
+                // Conflicted tags have no single change to view
+                if let Some(commit_id) = self.selected_tag().and_then(|tag| tag.commit_id.as_ref())
+                {
+                    let head = commander.get_revision_head(commit_id.as_str())?;
+                    return Ok(ComponentInputResult::HandledAction(
+                        ComponentAction::Multiple(vec![
+                            ComponentAction::SetPopup(None),
+                            ComponentAction::ViewLog(head),
+                        ]),
+                    ));
+                }
+            }
+            KeyCode::Esc | KeyCode::Char('q') => return Ok(ComponentInputResult::NotHandled),
+            _ => {}
+        }
+
+        Ok(ComponentInputResult::Handled)
+    }
+}
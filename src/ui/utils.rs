@@ -1,4 +1,38 @@
-use ratatui::layout::{Constraint, Direction, Layout, Rect};
+use std::{
+    fs,
+    io::Write,
+    process::{Command, Stdio},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+use ansi_to_tui::IntoText;
+use anyhow::{Context, Result, anyhow};
+use chrono::TimeDelta;
+use itertools::Itertools;
+use ratatui::{
+    Frame,
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Clear},
+};
+use tui_textarea::TextArea;
+
+use crate::{
+    commander::{
+        Commander,
+        log::{Head, SignatureStatus},
+    },
+    env::PrimaryId,
+    ui::{ComponentAction, confirm_popup::ConfirmPopup, message_popup::MessagePopup},
+};
+
+/// Number of characters shown for short change and commit ids
+const SHORT_ID_LENGTH: usize = 8;
 
 pub fn centered_rect(r: Rect, percent_x: u16, percent_y: u16) -> Rect {
     let popup_layout = Layout::default()
@@ -40,65 +74,394 @@ pub fn centered_rect_line_height(r: Rect, percent_x: u16, lines_y: u16) -> Rect
         .split(popup_layout[1])[1]
 }
 
-/// replaces tabs in a string by spaces
-///
-/// ratatui doesn't work well displaying tabs, so any
-/// string that is rendered and might contain tabs
-/// needs to have the tabs converted to spaces.
-///
-/// this function aligns tabs in the input string to
-/// virtual tab stops 4 spaces apart, taking care
-/// to count ansi control sequences as zero width.
-pub fn tabs_to_spaces(line: &str) -> String {
-    const TAB_WIDTH: usize = 4;
-
-    enum AnsiState {
-        Neutral,
-        Escape,
-        Csi,
-    }
-
-    let mut out = String::new();
-    let mut x = 0;
-    let mut ansi_state = AnsiState::Neutral;
-    for c in line.chars() {
-        match ansi_state {
-            AnsiState::Neutral => {
-                if c == '\t' {
-                    loop {
-                        out.push(' ');
-                        x += 1;
-                        if x % TAB_WIDTH == 0 {
-                            break;
-                        }
-                    }
-                } else {
-                    out.push(c);
-                    if c == '\x1b' {
-                        ansi_state = AnsiState::Escape;
-                    } else {
-                        x += 1;
-                    }
-                }
-                if c == '\r' || c == '\n' {
-                    x = 0;
-                }
-            }
-            AnsiState::Escape => {
-                out.push(c);
-                ansi_state = if c == '[' {
-                    AnsiState::Csi
-                } else {
-                    AnsiState::Neutral
-                };
+/// Short change id and commit id of a head, styled like jj does,
+/// with the primary id first and the other in parentheses
+pub fn head_id_spans(head: &Head, primary_id: PrimaryId) -> Vec<Span<'static>> {
+    fn short(id: &str) -> String {
+        id.chars().take(SHORT_ID_LENGTH).collect()
+    }
+
+    let change_id = Span::raw(short(head.change_id.as_str())).fg(Color::Magenta);
+    let commit_id = Span::raw(short(head.commit_id.as_str())).fg(Color::Blue);
+    let (primary, secondary) = match primary_id {
+        PrimaryId::Change => (change_id, commit_id),
+        PrimaryId::Commit => (commit_id, change_id),
+    };
+
+    vec![primary.bold(), Span::raw(" ("), secondary, Span::raw(")")]
+}
+
+/// Position of the selected item in a list, e.g. ` 3/128 `. Shown at the bottom right of list borders
+pub fn position_indicator(selected: Option<usize>, total: usize) -> Line<'static> {
+    let position = selected.map_or("-".to_owned(), |selected| (selected + 1).to_string());
+    Line::from(format!(" {position}/{total} ")).right_aligned()
+}
+
+/// Short description of how long ago something happened, e.g. `2h ago`
+pub fn format_elapsed(elapsed: TimeDelta) -> String {
+    if elapsed.num_minutes() < 1 {
+        "just now".to_owned()
+    } else if elapsed.num_hours() < 1 {
+        format!("{}m ago", elapsed.num_minutes())
+    } else if elapsed.num_days() < 1 {
+        format!("{}h ago", elapsed.num_hours())
+    } else {
+        format!("{}d ago", elapsed.num_days())
+    }
+}
+
+/// Plain text of a line, without styles
+pub fn line_string(line: &Line<'_>) -> String {
+    line.spans
+        .iter()
+        .map(|span| span.content.as_ref())
+        .collect()
+}
+
+/// Character ranges where a line contains the search text, ignoring case
+pub fn search_ranges(line: &str, search: &str) -> Vec<std::ops::Range<usize>> {
+    fn fold(c: char) -> char {
+        c.to_lowercase().next().unwrap_or(c)
+    }
+
+    let line: Vec<char> = line.chars().map(fold).collect();
+    let search: Vec<char> = search.chars().map(fold).collect();
+    if search.is_empty() {
+        return vec![];
+    }
+
+    let mut ranges = vec![];
+    let mut start = 0;
+    while start + search.len() <= line.len() {
+        if line[start..start + search.len()] == search[..] {
+            ranges.push(start..start + search.len());
+            start += search.len();
+        } else {
+            start += 1;
+        }
+    }
+    ranges
+}
+
+/// Patch the style of a range of characters of a line, splitting spans where needed
+pub fn style_chars(line: Line<'_>, range: std::ops::Range<usize>, style: Style) -> Line<'_> {
+    let mut start = 0;
+    let mut spans = vec![];
+    for span in line.spans {
+        let len = span.content.chars().count();
+        let (span_start, span_end) = (start, start + len);
+        start = span_end;
+        if span_end <= range.start || span_start >= range.end {
+            spans.push(span);
+            continue;
+        }
+
+        let chars: Vec<char> = span.content.chars().collect();
+        let from = range.start.saturating_sub(span_start);
+        let to = (range.end - span_start).min(len);
+        for (part, modify) in [(0..from, false), (from..to, true), (to..len, false)] {
+            let part = &chars[part];
+            if part.is_empty() {
+                continue;
             }
-            AnsiState::Csi => {
-                out.push(c);
-                if ('\x40'..='\x7f').contains(&c) {
-                    ansi_state = AnsiState::Neutral;
-                }
+            let part_style = if modify {
+                span.style.patch(style)
+            } else {
+                span.style
+            };
+            spans.push(Span::styled(part.iter().collect::<String>(), part_style));
+        }
+    }
+    Line { spans, ..line }
+}
+
+/// Open a URL in the default browser
+pub fn open_url(url: &str) -> Result<()> {
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+
+    command
+        .arg(url)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("Failed to open {url}"))?;
+    Ok(())
+}
+
+/// Set when a background task finished, so the UI should be drawn with its result
+static BACKGROUND_DONE: AtomicBool = AtomicBool::new(false);
+
+/// Run a slow task, e.g. a jj command, on another thread. The result can be picked up from the
+/// receiver in [crate::ui::Component::update], which runs when the task is done
+pub fn spawn_background<T: Send + 'static>(
+    task: impl FnOnce() -> T + Send + 'static,
+) -> mpsc::Receiver<T> {
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        // The receiver is gone if the result is no longer wanted
+        if tx.send(task()).is_ok() {
+            BACKGROUND_DONE.store(true, Ordering::Relaxed);
+        }
+    });
+    rx
+}
+
+/// Whether a background task finished since the last call
+pub fn take_background_done() -> bool {
+    BACKGROUND_DONE.swap(false, Ordering::Relaxed)
+}
+
+/// Prefix of the lines removed from text edited in the editor, like jj does
+const EDITOR_COMMENT_PREFIX: &str = "JJ:";
+
+/// Edit a description in the editor configured for jj. Run it with the UI suspended, see
+/// [ComponentAction::Suspend]. Returns `None` if the editor fails or the description is left
+/// unchanged.
+pub fn edit_in_editor(commander: &Commander, description: &str) -> Result<Option<String>> {
+    let editor = commander.get_editor()?;
+    let path = std::env::temp_dir().join(format!(
+        "lazyjj-editor-{}.jjdescription",
+        std::process::id()
+    ));
+    fs::write(
+        &path,
+        format!(
+            "{description}\n{EDITOR_COMMENT_PREFIX} Lines starting with \"{EDITOR_COMMENT_PREFIX}\" will be removed.\n"
+        ),
+    )
+    .context("Failed writing description for the editor")?;
+
+    let status = Command::new(&editor[0])
+        .args(&editor[1..])
+        .arg(&path)
+        .current_dir(&commander.env.root)
+        .status();
+    let edited = fs::read_to_string(&path);
+    let _ = fs::remove_file(&path);
+
+    let status = status.with_context(|| format!("Failed to run editor {}", editor[0]))?;
+    if !status.success() {
+        return Ok(None);
+    }
+    let edited = edited
+        .context("Failed reading edited description")?
+        .lines()
+        .filter(|line| !line.starts_with(EDITOR_COMMENT_PREFIX))
+        .join("\n");
+    let edited = edited.trim_end();
+    if edited == description.trim_end() {
+        return Ok(None);
+    }
+    Ok(Some(edited.to_owned()))
+}
+
+/// Describe a change in the editor configured for jj, suspending the UI meanwhile. `then` is
+/// the action showing the described change. Errors of the editor or of jj are shown in a popup
+pub fn describe_in_editor(
+    commander: &Commander,
+    head: &Head,
+    then: fn(Head) -> ComponentAction,
+) -> Result<ComponentAction> {
+    fn error_popup(err: anyhow::Error) -> Result<ComponentAction> {
+        Ok(ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+            title: "Describe".into(),
+            messages: format!("{err:#}").into_text()?,
+            text_align: None,
+        }))))
+    }
+
+    if head.immutable {
+        return error_popup(anyhow!(
+            "The change cannot be described because it is immutable."
+        ));
+    }
+    let description = commander.get_commit_description(&head.commit_id)?;
+    let head = head.clone();
+    Ok(ComponentAction::Suspend(Box::new(move |commander| {
+        let description = match edit_in_editor(commander, &description) {
+            Ok(Some(description)) => description,
+            Ok(None) => return Ok(ComponentAction::Multiple(vec![])),
+            Err(err) => return error_popup(err),
+        };
+        if let Err(err) = commander.run_describe(head.commit_id.as_str(), &description) {
+            return error_popup(err);
+        }
+        Ok(then(commander.get_head_latest(&head)?))
+    })))
+}
+
+/// Commands copying their input to the system clipboard, tried in order
+const CLIPBOARD_COMMANDS: &[&[&str]] = &[
+    &["pbcopy"],
+    &["wl-copy"],
+    &["xclip", "-selection", "clipboard"],
+    &["xsel", "--clipboard", "--input"],
+    &["clip.exe"],
+];
+
+/// Copy text with the first clipboard command which succeeds. Returns false if none did
+fn copy_with_command(text: &str) -> bool {
+    CLIPBOARD_COMMANDS.iter().any(|command| {
+        let Ok(mut child) = Command::new(command[0])
+            .args(&command[1..])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+        else {
+            return false;
+        };
+        let written = child
+            .stdin
+            .take()
+            .is_some_and(|mut stdin| stdin.write_all(text.as_bytes()).is_ok());
+        child.wait().is_ok_and(|status| status.success()) && written
+    })
+}
+
+/// Size in bytes above which copying asks for confirmation first. Terminals may cut off or
+/// ignore larger OSC 52 sequences
+const LARGE_COPY_SIZE: usize = 100 * 1024;
+
+/// Copy text to the system clipboard.
+///
+/// Locally, this uses the platform's clipboard command. Over SSH or when no clipboard command
+/// works, the text is sent to the terminal with the OSC 52 escape sequence, which copies it on
+/// the machine running the terminal, as long as the terminal supports it.
+pub fn copy_to_clipboard(text: &str) -> Result<()> {
+    if std::env::var_os("SSH_CONNECTION").is_none() && copy_with_command(text) {
+        return Ok(());
+    }
+
+    let mut stdout = std::io::stdout();
+    write!(stdout, "\x1b]52;c;{}\x07", base64_encode(text.as_bytes()))
+        .and_then(|_| stdout.flush())
+        .context("Failed to copy to clipboard")
+}
+
+/// Copy text to the clipboard, asking for confirmation first when it is larger than
+/// [LARGE_COPY_SIZE]. `what` names the text in the confirmation. Clipboard errors are shown in
+/// a popup
+pub fn copy_action(what: &str, text: String) -> Result<ComponentAction> {
+    fn copy(text: &str) -> Result<ComponentAction> {
+        match copy_to_clipboard(text) {
+            Ok(()) => Ok(ComponentAction::Multiple(vec![])),
+            Err(err) => copy_error_popup(err),
+        }
+    }
+
+    if text.len() <= LARGE_COPY_SIZE {
+        return copy(&text);
+    }
+    Ok(ComponentAction::SetPopup(Some(Box::new(
+        ConfirmPopup::new(
+            "Copy",
+            vec![
+                Line::from(format!("{what} is {} KiB.", text.len() / 1024)),
+                Line::from("Terminals may cut off large copies over SSH. Copy anyway?"),
+            ],
+            move |_| copy(&text),
+        ),
+    ))))
+}
+
+/// Popup showing why copying to the clipboard failed
+pub fn copy_error_popup(err: anyhow::Error) -> Result<ComponentAction> {
+    Ok(ComponentAction::SetPopup(Some(Box::new(MessagePopup {
+        title: "Copy error".into(),
+        messages: format!("{err:#}").into_text()?,
+        text_align: None,
+    }))))
+}
+
+/// Standard base64 encoding with padding
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, byte)| n | ((*byte as u32) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                encoded.push(ALPHABET[((n >> (18 - 6 * i)) & 0x3f) as usize] as char);
+            } else {
+                encoded.push('=');
             }
         }
     }
-    out
+    encoded
+}
+
+/// Draw a one line prompt over the bottom of a panel, keeping the panel visible while typing,
+/// e.g. to search the log
+pub fn draw_prompt(f: &mut Frame<'_>, area: Rect, title: &str, textarea: &TextArea<'_>) {
+    let height = area.height.min(3);
+    let area = Rect {
+        y: area.bottom() - height,
+        height,
+        ..area
+    };
+    let block = Block::bordered()
+        .title(Span::styled(title.to_owned(), Style::new().bold().cyan()))
+        .title_bottom(Line::from(" Enter: done | Escape: clear ").right_aligned())
+        .border_type(BorderType::Rounded)
+        .border_style(Style::default().fg(Color::Green));
+    f.render_widget(Clear, area);
+    f.render_widget(textarea, block.inner(area));
+    f.render_widget(block, area);
+}
+
+/// Indicator for the signature status of a commit, two columns wide
+pub fn signature_span(signature: SignatureStatus) -> Span<'static> {
+    match signature {
+        SignatureStatus::Good => Span::from("✓ ").fg(Color::Green),
+        SignatureStatus::Bad => Span::from("✗ ").fg(Color::Red),
+        SignatureStatus::Unknown => Span::from("? ").fg(Color::Yellow),
+        SignatureStatus::Unsigned => Span::from("- ").fg(Color::DarkGray),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_elapsed_uses_largest_unit() {
+        assert_eq!(format_elapsed(TimeDelta::seconds(30)), "just now");
+        assert_eq!(format_elapsed(TimeDelta::minutes(5)), "5m ago");
+        assert_eq!(format_elapsed(TimeDelta::minutes(150)), "2h ago");
+        assert_eq!(format_elapsed(TimeDelta::days(3)), "3d ago");
+    }
+
+    #[test]
+    fn base64_encode_pads_output() {
+        assert_eq!(base64_encode(b""), "");
+        assert_eq!(base64_encode(b"f"), "Zg==");
+        assert_eq!(base64_encode(b"fo"), "Zm8=");
+        assert_eq!(base64_encode(b"foo"), "Zm9v");
+        assert_eq!(base64_encode(b"foobar"), "Zm9vYmFy");
+    }
+
+    #[test]
+    fn search_ranges_ignore_case() {
+        assert_eq!(
+            search_ranges("Fix log Search in LOG", "log"),
+            vec![4..7, 18..21]
+        );
+        assert_eq!(search_ranges("aaaa", "aa"), vec![0..2, 2..4]);
+        assert!(search_ranges("log", "").is_empty());
+        assert!(search_ranges("log", "logs").is_empty());
+    }
 }
@@ -0,0 +1,142 @@
+use anyhow::Result;
+use ratatui::{
+    crossterm::event::{Event, KeyCode, KeyEventKind},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Style, Stylize},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, List, ListState, Paragraph},
+};
+
+use crate::{
+    ComponentInputResult,
+    commander::{Commander, workspaces::Workspace},
+    env::Config,
+    ui::{Component, ComponentAction, styles::create_popup_block, utils::centered_rect},
+};
+
+/// Popup listing the workspaces of the repo, to switch lazyjj to another one
+pub struct WorkspacesPopup {
+    workspaces: Result<Vec<Workspace>, String>,
+    list_state: ListState,
+    list_height: u16,
+    config: Config,
+}
+
+impl WorkspacesPopup {
+    pub fn new(config: Config, commander: &mut Commander) -> Self {
+        let workspaces = commander.get_workspaces().map_err(|err| err.to_string());
+        let selected = workspaces
+            .as_ref()
+            .ok()
+            .and_then(|workspaces| workspaces.iter().position(|workspace| workspace.current));
+        Self {
+            workspaces,
+            list_state: ListState::default().with_selected(Some(selected.unwrap_or(0))),
+            list_height: 0,
+            config,
+        }
+    }
+
+    fn scroll(&mut self, scroll: isize) {
+        let len = self
+            .workspaces
+            .as_ref()
+            .map_or(0, |workspaces| workspaces.len());
+        self.list_state.select(Some(
+            self.list_state
+                .selected()
+                .map(|selected| selected.saturating_add_signed(scroll))
+                .unwrap_or(0)
+                .min(len.saturating_sub(1)),
+        ));
+    }
+
+    fn selected_workspace(&self) -> Option<&Workspace> {
+        self.workspaces
+            .as_ref()
+            .ok()
+            .and_then(|workspaces| workspaces.get(self.list_state.selected()?))
+    }
+}
+
+impl Component for WorkspacesPopup {
+    fn draw(&mut self, f: &mut ratatui::prelude::Frame<'_>, area: Rect) -> Result<()> {
+        let block = create_popup_block("Workspaces");
+        let area = centered_rect(area, 60, 60);
+        f.render_widget(Clear, area);
+        f.render_widget(&block, area);
+
+        let popup_chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Fill(1), Constraint::Length(2)])
+            .split(block.inner(area));
+        self.list_height = popup_chunks[0].height;
+
+        let items: Vec<Line> = match self.workspaces.as_ref() {
+            Ok(workspaces) => workspaces
+                .iter()
+                .map(|workspace| {
+                    let mut name = Span::raw(workspace.name.clone()).fg(Color::Magenta);
+                    if workspace.current {
+                        name = name.bold();
+                    }
+                    Line::from(vec![
+                        Span::raw(if workspace.current { "@ " } else { "  " }).fg(Color::Green),
+                        name,
+                        Span::raw(" "),
+                        Span::raw(workspace.path.clone()),
+                    ])
+                })
+                .collect(),
+            Err(err) => vec![Line::from(err.clone()).fg(Color::Red)],
+        };
+        let list = List::new(items)
+            .scroll_padding(3)
+            .highlight_style(Style::default().bg(self.config.highlight_color()));
+        f.render_stateful_widget(list, popup_chunks[0], &mut self.list_state);
+
+        let help = Paragraph::new(vec!["j/k: scroll | Enter: switch | Escape: close".into()])
+            .fg(Color::DarkGray)
+            .alignment(Alignment::Center)
+            .block(
+                Block::default()
+                    .borders(Borders::TOP)
+                    .border_type(BorderType::Rounded)
+                    .border_style(Style::default().fg(Color::DarkGray)),
+            );
+        f.render_widget(help, popup_chunks[1]);
+
+        Ok(())
+    }
+
+    fn input(&mut self, _commander: &mut Commander, event: Event) -> Result<ComponentInputResult> {
+        let Event::Key(key) = event else {
+            return Ok(ComponentInputResult::Handled);
+        };
+        if key.kind != KeyEventKind::Press {
+            return Ok(ComponentInputResult::Handled);
+        }
+
+        match key.code {
+            KeyCode::Char('j') | KeyCode::Down => self.scroll(1),
+            KeyCode::Char('k') | KeyCode::Up => self.scroll(-1),
+            KeyCode::Char('J') => self.scroll(self.list_height as isize / 2),
+            KeyCode::Char('K') => self.scroll((self.list_height as isize / 2).saturating_neg()),
+            KeyCode::Enter => {
+                let mut actions = vec![ComponentAction::SetPopup(None)];
+                if let Some(workspace) = self.selected_workspace()
+                    && !workspace.current
+                {
+                    actions.push(ComponentAction::SwitchWorkspace(workspace.path.clone()));
+                }
+                return Ok(ComponentInputResult::HandledAction(
+                    ComponentAction::Multiple(actions),
+                ));
+            }
+            KeyCode::Esc | KeyCode::Char('q') => return Ok(ComponentInputResult::NotHandled),
+            _ => {}
+        }
+
+        Ok(ComponentInputResult::Handled)
+    }
+}